@@ -1,5 +1,8 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use strobemers_rs::{MinStrobes, RandStrobes};
+use strobemers_rs::{
+    Alphabet, AlphabetHasher, KmerHasher, LaneBatchedHasher, MinStrobes, NtHash64, Protocol,
+    RandStrobes, StrobeGenerator, TieBreak,
+};
 
 use rand::{Rng, SeedableRng};
 use std::hint::black_box;
@@ -37,9 +40,81 @@ fn bench_randstrobes_iter(c: &mut Criterion) {
     });
 }
 
+fn bench_randstrobes_scans(c: &mut Criterion) {
+    let seq = make_seq();
+    c.bench_function("RandStrobes order-3 secondary-hash ties", |b| {
+        b.iter(|| {
+            let mut it = RandStrobes::new(&seq, 3, L, W_MIN, W_MAX).unwrap();
+            it.set_tie_break(TieBreak::SecondaryHash);
+            let _sum: u64 = black_box(it).sum();
+        })
+    });
+    c.bench_function("RandStrobes order-3 distance penalty", |b| {
+        b.iter(|| {
+            let mut it = RandStrobes::new(&seq, 3, L, W_MIN, W_MAX).unwrap();
+            it.set_distance_penalty(1 << 20);
+            let _sum: u64 = black_box(it).sum();
+        })
+    });
+}
+
+/// Short reads spend a large share of their anchors in shrunk end windows,
+/// which MinStrobes scans instead of reading the precomputed minima.
+fn bench_minstrobes_short_reads(c: &mut Criterion) {
+    let seq = make_seq();
+    c.bench_function("MinStrobes order-3 150-bp reads", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for read in seq.chunks_exact(150) {
+                let it = MinStrobes::new(read, 3, 20, 10, 25).unwrap();
+                sum = sum.wrapping_add(black_box(it).sum());
+            }
+            black_box(sum)
+        })
+    });
+}
+
+fn bench_generator_blocks(c: &mut Criterion) {
+    // 4 Mbp, so that the whole-sequence hashes and minima exceed the caches
+    let seq = make_seq().repeat(40);
+    for (name, block) in [("whole sequence", 0), ("32-kbp blocks", 1 << 15)] {
+        c.bench_function(&format!("MinStrobes order-3 generator, {name}"), |b| {
+            let generator = StrobeGenerator::new(Protocol::Min, 3, L, W_MIN, W_MAX).unwrap();
+            let mut generator = generator.with_block_size(block);
+            b.iter(|| {
+                let mut sum = 0u64;
+                generator.for_each_seed(&seq, |s| sum = sum.wrapping_add(s.hash)).unwrap();
+                black_box(sum)
+            })
+        });
+    }
+}
+
+fn bench_hashers(c: &mut Criterion) {
+    let seq = make_seq().repeat(10);
+    let mut out = Vec::new();
+    let hashers: [(&str, Box<dyn KmerHasher>); 3] = [
+        ("NtHash64", Box::new(NtHash64)),
+        ("AlphabetHasher", Box::new(AlphabetHasher::new(Alphabet::dna()))),
+        ("LaneBatchedHasher", Box::new(LaneBatchedHasher::dna())),
+    ];
+    for (name, hasher) in hashers {
+        c.bench_function(&format!("hash_all 1 Mbp, {name}"), |b| {
+            b.iter(|| {
+                hasher.hash_all_into(black_box(&seq), L, &mut out).unwrap();
+                black_box(out.len())
+            })
+        });
+    }
+}
+
 criterion_group!(
     benches,
     bench_minstrobes_iter,
-    bench_randstrobes_iter
+    bench_randstrobes_iter,
+    bench_randstrobes_scans,
+    bench_minstrobes_short_reads,
+    bench_generator_blocks,
+    bench_hashers
 );
 criterion_main!(benches);
@@ -0,0 +1,22 @@
+//! Differential fuzz target: `cargo +nightly fuzz run differential`.
+//!
+//! The first four bytes select the parameters; the rest is the sequence,
+//! mapped onto ACGT.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use strobemers_rs::reference::assert_matches_reference;
+
+fuzz_target!(|data: &[u8]| {
+    let [a, b, c, d, rest @ ..] = data else {
+        return;
+    };
+    let n = 2 + (a & 1);
+    let k = 1 + (*b as usize % 16);
+    let w_min = *c as usize % 17;
+    let w_max = (w_min + (*d as usize % 16)).max(1);
+    let shrink = a & 2 != 0;
+    let seq: Vec<u8> = rest.iter().map(|&x| b"ACGT"[(x & 3) as usize]).collect();
+
+    assert_matches_reference(&seq, n, k, w_min, w_max, shrink);
+});
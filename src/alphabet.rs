@@ -0,0 +1,313 @@
+use crate::{
+    Result, StrobeError,
+    constants::{COMPL_BASES, SEQ_NT4_TABLE},
+    hashes::KmerHasher,
+};
+
+/// Code of bytes outside the alphabet.
+pub(crate) const UNKNOWN: u8 = u8::MAX;
+
+/// Table-driven alphabet: which bytes are symbols, their codes and, optionally,
+/// their complements.
+///
+/// Generalizes the nucleotide tables (`SEQ_NT4_TABLE`, `COMPL_BASES`) to
+/// arbitrary token streams. Symbols with equal codes hash identically in an
+/// [`AlphabetHasher`]; bytes without a code are rejected. A complement table
+/// enables the strand-aware constructors (canonical, reverse complement).
+///
+/// # Example
+/// ```
+/// use strobemers_rs::Alphabet;
+/// let binary = Alphabet::from_symbols(&[0x00, 0xFF]).unwrap();
+/// let binary = binary.with_complement(&[(0x00, 0xFF)]).unwrap();
+/// assert_eq!(binary.reverse_complement(&[0x00, 0x00, 0xFF]).unwrap(), [0x00, 0xFF, 0xFF]);
+/// assert!(binary.validate(&[0x00, 0x7F]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    codes: [u8; 256],
+    complement: Option<[u8; 256]>,
+}
+
+impl Alphabet {
+    /// Nucleotides as encoded by [`nt4`](crate::nt4) (`U` as `T`, any case),
+    /// complemented as by [`complement`](crate::complement). `N` and other
+    /// ambiguity codes are not symbols.
+    pub fn dna() -> Self {
+        let mut codes = [UNKNOWN; 256];
+        for (code, &nt) in codes.iter_mut().zip(SEQ_NT4_TABLE.iter()) {
+            if nt < 4 {
+                *code = nt;
+            }
+        }
+        Self {
+            codes,
+            complement: Some(COMPL_BASES),
+        }
+    }
+
+    /// Builds an alphabet from a code table: `codes[b]` is the code of byte
+    /// `b`, or `u8::MAX` if `b` is not a symbol.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Alphabet)` – On success, without complement.
+    /// * `Err(StrobeError::InvalidAlphabet)` – If no byte is a symbol.
+    pub fn from_codes(codes: [u8; 256]) -> Result<Self> {
+        if codes.iter().all(|&c| c == UNKNOWN) {
+            return Err(StrobeError::InvalidAlphabet);
+        }
+        Ok(Self {
+            codes,
+            complement: None,
+        })
+    }
+
+    /// Builds an alphabet in which each of `symbols` is its own class.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Alphabet)` – On success, without complement.
+    /// * `Err(StrobeError::InvalidAlphabet)` – If `symbols` is empty, longer than
+    ///   255 or repeats a byte.
+    pub fn from_symbols(symbols: &[u8]) -> Result<Self> {
+        if symbols.len() >= UNKNOWN as usize {
+            return Err(StrobeError::InvalidAlphabet);
+        }
+        let mut codes = [UNKNOWN; 256];
+        for (code, &b) in symbols.iter().enumerate() {
+            if codes[b as usize] != UNKNOWN {
+                return Err(StrobeError::InvalidAlphabet);
+            }
+            codes[b as usize] = code as u8;
+        }
+        Self::from_codes(codes)
+    }
+
+    /// Adds a complement from symmetric `pairs` (`(a, a)` for a self-complementary symbol).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Alphabet)` – On success.
+    /// * `Err(StrobeError::InvalidAlphabet)` – If a pair holds a non-symbol, a
+    ///   symbol is paired twice, or a symbol is left without complement.
+    pub fn with_complement(mut self, pairs: &[(u8, u8)]) -> Result<Self> {
+        let mut complement = [0u8; 256];
+        let mut paired = [false; 256];
+        for &(a, b) in pairs {
+            for (x, y) in [(a, b), (b, a)] {
+                if self.code(x).is_none() || (paired[x as usize] && complement[x as usize] != y) {
+                    return Err(StrobeError::InvalidAlphabet);
+                }
+                complement[x as usize] = y;
+                paired[x as usize] = true;
+            }
+        }
+        if (0..=255u8).any(|b| self.code(b).is_some() && !paired[b as usize]) {
+            return Err(StrobeError::InvalidAlphabet);
+        }
+        self.complement = Some(complement);
+        Ok(self)
+    }
+
+    /// Returns the code of `b`, or `None` if `b` is not a symbol.
+    #[inline(always)]
+    pub fn code(&self, b: u8) -> Option<u8> {
+        let code = self.codes[b as usize];
+        (code != UNKNOWN).then_some(code)
+    }
+
+    /// Returns the complement of symbol `b`, if the alphabet has a complement.
+    pub fn complement(&self, b: u8) -> Option<u8> {
+        self.code(b)?;
+        self.complement.map(|c| c[b as usize])
+    }
+
+    /// Checks that every byte of `seq` is a symbol.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If all bytes are symbols.
+    /// * `Err(StrobeError::InvalidSymbol)` – With the position of the first other byte.
+    pub fn validate(&self, seq: &[u8]) -> Result<()> {
+        match seq.iter().position(|&b| self.code(b).is_none()) {
+            Some(pos) => Err(StrobeError::InvalidSymbol(pos)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the reverse complement of `seq`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` – On success.
+    /// * `Err(StrobeError::InvalidAlphabet)` – If the alphabet has no complement.
+    /// * `Err(StrobeError::InvalidSymbol)` – If `seq` contains a non-symbol.
+    pub fn reverse_complement(&self, seq: &[u8]) -> Result<Vec<u8>> {
+        let complement = self.complement.as_ref().ok_or(StrobeError::InvalidAlphabet)?;
+        self.validate(seq)?;
+        Ok(seq.iter().rev().map(|&b| complement[b as usize]).collect())
+    }
+}
+
+/// Rolling k-mer hasher over the symbols of an [`Alphabet`].
+///
+/// Hashes k-mers of symbol codes with the same cyclic polynomial rolling
+/// hash as [`ProteinHasher`](crate::ProteinHasher), so any token stream
+/// whose alphabet fits in a byte can be seeded. Constructors reject
+/// sequences with bytes outside the alphabet
+/// ([`StrobeError::InvalidSymbol`]), and the reverse strand is derived from
+/// the alphabet's complement.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{Alphabet, AlphabetHasher, RandStrobes, StrobeError};
+/// // Tokens 0..=9, e.g. quantized signal levels
+/// let symbols: Vec<u8> = (0..10).collect();
+/// let hasher = AlphabetHasher::new(Alphabet::from_symbols(&symbols).unwrap());
+/// let seq: Vec<u8> = (0..40u32).map(|i| ((i * 7 + i / 3) % 10) as u8).collect();
+/// let rs = RandStrobes::with_hasher(&seq, 2, 3, 3, 5, &hasher).unwrap();
+/// assert!(rs.count() > 0);
+/// let bad = RandStrobes::with_hasher(&[0, 1, 2, 200, 3, 4, 5, 6, 7, 8], 2, 3, 3, 5, &hasher);
+/// assert_eq!(bad.err(), Some(StrobeError::InvalidSymbol(3)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AlphabetHasher {
+    alphabet: Alphabet,
+}
+
+impl AlphabetHasher {
+    /// Hashes symbols of `alphabet`.
+    pub fn new(alphabet: Alphabet) -> Self {
+        Self { alphabet }
+    }
+
+    /// Returns the alphabet.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+}
+
+impl KmerHasher for AlphabetHasher {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        class_hashes(seq, k, |b| self.alphabet.code(b))
+    }
+
+    fn hash_both_strands(&self, seq: &[u8], k: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+        let rev = self.alphabet.reverse_complement(seq)?;
+        Ok((self.hash_all(seq, k)?, self.hash_all(&rev, k)?))
+    }
+
+    fn validity(&self, seq: &[u8], k: usize) -> Option<Vec<bool>> {
+        class_validity(seq, k, |b| self.alphabet.code(b))
+    }
+
+    fn validate_sequence(&self, seq: &[u8]) -> Result<()> {
+        self.alphabet.validate(seq)
+    }
+}
+
+/// Hashes the k-mers of `seq` by symbol class with a cyclic polynomial
+/// rolling hash, finalized by a 64-bit mix.
+///
+/// Bytes without a class hash like class 0; see [`class_validity`].
+pub(crate) fn class_hashes(
+    seq: &[u8],
+    k: usize,
+    class: impl Fn(u8) -> Option<u8>,
+) -> Result<Vec<u64>> {
+    if !(1..=64).contains(&k) {
+        return Err(StrobeError::StrobeLengthTooSmall);
+    }
+    if seq.len() < k {
+        return Err(StrobeError::SequenceTooShort);
+    }
+
+    let seed = |b: u8| CLASS_SEEDS[class(b).unwrap_or(0) as usize];
+    let mut h = seq[..k].iter().fold(0u64, |h, &b| h.rotate_left(1) ^ seed(b));
+    let mut out = Vec::with_capacity(seq.len() - k + 1);
+    out.push(mix(h));
+    for i in k..seq.len() {
+        h = h.rotate_left(1) ^ seed(seq[i - k]).rotate_left(k as u32) ^ seed(seq[i]);
+        out.push(mix(h));
+    }
+    Ok(out)
+}
+
+/// Returns, per k-mer start, whether every byte has a class; `None` if all do.
+pub(crate) fn class_validity(
+    seq: &[u8],
+    k: usize,
+    class: impl Fn(u8) -> Option<u8>,
+) -> Option<Vec<bool>> {
+    if seq.iter().all(|&b| class(b).is_some()) {
+        return None;
+    }
+    let mut valid = Vec::with_capacity((seq.len() + 1).saturating_sub(k));
+    // Distance back to the most recent unclassified byte, capped at k
+    let mut clean = 0usize;
+    for (i, &b) in seq.iter().enumerate() {
+        clean = if class(b).is_none() { 0 } else { (clean + 1).min(k) };
+        if i + 1 >= k {
+            valid.push(clean == k);
+        }
+    }
+    Some(valid)
+}
+
+/// Pseudo-random 64-bit value per class, from SplitMix64.
+pub(crate) const CLASS_SEEDS: [u64; 256] = {
+    let mut seeds = [0u64; 256];
+    let mut state = 0u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        seeds[i] = mix(state);
+        i += 1;
+    }
+    seeds
+};
+
+/// SplitMix64 finalizer, spreading rolling-hash values over all 64 bits.
+pub(crate) const fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reverse_complement;
+
+    #[test]
+    fn dna_matches_nucleotide_tables() {
+        let dna = Alphabet::dna();
+        assert_eq!((dna.code(b'u'), dna.code(b'N')), (Some(3), None));
+        let seq = b"ACGTTGCA";
+        assert_eq!(dna.reverse_complement(seq).unwrap(), reverse_complement(seq));
+        assert_eq!(dna.validate(b"ACGNT"), Err(StrobeError::InvalidSymbol(3)));
+    }
+
+    #[test]
+    fn complement_must_cover_every_symbol() {
+        let abc = Alphabet::from_symbols(b"abc").unwrap();
+        assert!(abc.clone().with_complement(&[(b'a', b'b'), (b'c', b'c')]).is_ok());
+        assert!(abc.clone().with_complement(&[(b'a', b'b')]).is_err());
+        assert!(abc.clone().with_complement(&[(b'a', b'b'), (b'a', b'c')]).is_err());
+        assert!(abc.with_complement(&[(b'a', b'z'), (b'c', b'c')]).is_err());
+        assert!(Alphabet::from_symbols(b"aa").is_err());
+    }
+
+    #[test]
+    fn rolling_matches_direct_hashing() {
+        let seq: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(37)).collect();
+        let class = |b: u8| Some(b % 7);
+        for k in [1, 5, 64] {
+            let hashes = class_hashes(&seq, k, class).unwrap();
+            for (i, &h) in hashes.iter().enumerate() {
+                assert_eq!(h, class_hashes(&seq[i..i + k], k, class).unwrap()[0]);
+            }
+        }
+    }
+}
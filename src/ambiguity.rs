@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+
+use crate::{Result, StrobeError, hashes::KmerHasher, util::nt4};
+
+/// Bytes to hash, with the per-k-mer validity mask if some k-mers are invalid.
+pub(crate) type Prepared<'a> = (Cow<'a, [u8]>, Option<Vec<bool>>);
+
+/// How iterators handle bases other than `A`, `C`, `G`, `T` and `U` (e.g. `N`).
+///
+/// Pass it to `with_ambiguity_policy` on [`RandStrobes`](crate::RandStrobes)
+/// or [`MinStrobes`](crate::MinStrobes). The plain constructors use
+/// [`Hasher`](Self::Hasher).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AmbiguityPolicy {
+    /// Hash the sequence as given and leave ambiguous bases to the hasher
+    /// (the original behavior).
+    #[default]
+    Hasher,
+    /// Reject sequences containing an ambiguous base.
+    Error,
+    /// Exclude k-mers overlapping an ambiguous base: no seed starts at them
+    /// and window selection never picks them. Seeds whose window holds no
+    /// valid k-mer are dropped.
+    SkipAffectedSeeds,
+    /// Hash ambiguous bases as `A` (keeping their case).
+    TreatAsA,
+}
+
+impl AmbiguityPolicy {
+    /// Prepares `seq` for hashing under this policy.
+    ///
+    /// Returns the bytes to hash and, for [`SkipAffectedSeeds`](Self::SkipAffectedSeeds),
+    /// whether each k-mer start is free of ambiguous bases. The mask is `None`
+    /// if every k-mer is valid.
+    pub(crate) fn apply(self, seq: &[u8], k: usize) -> Result<Prepared<'_>> {
+        let first = seq.iter().position(|&b| nt4(b) > 3);
+        let Some(first) = first else {
+            return Ok((Cow::Borrowed(seq), None));
+        };
+        match self {
+            Self::Hasher => Ok((Cow::Borrowed(seq), None)),
+            Self::Error => Err(StrobeError::AmbiguousBase(first)),
+            Self::TreatAsA => Ok((Cow::Owned(replace_ambiguous(seq)), None)),
+            Self::SkipAffectedSeeds => {
+                let valid = kmer_validity(seq, k);
+                Ok((Cow::Owned(replace_ambiguous(seq)), Some(valid)))
+            }
+        }
+    }
+}
+
+/// Replaces every ambiguous base with `A` (`a` if lowercase).
+fn replace_ambiguous(seq: &[u8]) -> Vec<u8> {
+    seq.iter()
+        .map(|&b| match nt4(b) {
+            0..=3 => b,
+            _ if b.is_ascii_lowercase() => b'a',
+            _ => b'A',
+        })
+        .collect()
+}
+
+/// Returns, per k-mer start of `seq`, whether the k-mer has no ambiguous base.
+pub(crate) fn kmer_validity(seq: &[u8], k: usize) -> Vec<bool> {
+    kmers_without(seq, k, |b| nt4(b) > 3)
+}
+
+/// Returns, per k-mer start, whether all k bases reach Phred quality `min_qual`.
+///
+/// `qual` holds Phred+33 ASCII scores, one per base, as in FASTQ.
+pub(crate) fn quality_validity(qual: &[u8], k: usize, min_qual: u8) -> Vec<bool> {
+    kmers_without(qual, k, |q| q.saturating_sub(b'!') < min_qual)
+}
+
+/// Returns, per k-mer start, the selection penalty for its weakest base.
+///
+/// A k-mer whose lowest Phred score is `q` costs `penalty × (target − q)`,
+/// or 0 if `q ≥ target`. `qual` holds Phred+33 ASCII scores, as in FASTQ.
+pub(crate) fn quality_penalties(qual: &[u8], k: usize, target: u8, penalty: u64) -> Vec<u64> {
+    qual.windows(k)
+        .map(|w| {
+            let low = w.iter().min().map_or(0, |q| q.saturating_sub(b'!'));
+            penalty.saturating_mul(target.saturating_sub(low) as u64)
+        })
+        .collect()
+}
+
+/// Returns, per k-mer start of `bytes`, whether no byte in the k-mer is `bad`.
+fn kmers_without(bytes: &[u8], k: usize, bad: impl Fn(u8) -> bool) -> Vec<bool> {
+    let n_kmers = (bytes.len() + 1).saturating_sub(k);
+    let mut valid = Vec::with_capacity(n_kmers);
+    // Distance back to the most recent bad byte, capped at k
+    let mut clean = 0usize;
+    for (i, &b) in bytes.iter().enumerate() {
+        clean = if bad(b) { 0 } else { (clean + 1).min(k) };
+        if i + 1 >= k {
+            valid.push(clean == k);
+        }
+    }
+    valid
+}
+
+/// Fetches `hasher`'s validity mask for the `n_kmers` k-mers of `seq`.
+///
+/// Fails with [`StrobeError::IncompleteHashValues`] if the mask has the wrong length.
+pub(crate) fn hasher_validity<H: KmerHasher + ?Sized>(
+    hasher: &H,
+    seq: &[u8],
+    k: usize,
+    n_kmers: usize,
+) -> Result<Option<Vec<bool>>> {
+    match hasher.validity(seq, k) {
+        Some(valid) if valid.len() != n_kmers => Err(StrobeError::IncompleteHashValues),
+        valid => Ok(valid),
+    }
+}
+
+/// Combines two validity masks: a k-mer is valid only if both allow it.
+pub(crate) fn merge_validity(a: Option<Vec<bool>>, b: Option<Vec<bool>>) -> Option<Vec<bool>> {
+    match (a, b) {
+        (Some(mut a), Some(b)) => {
+            a.iter_mut().zip(b).for_each(|(a, b)| *a &= b);
+            Some(a)
+        }
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validity_marks_overlapping_kmers() {
+        assert_eq!(kmer_validity(b"ACGNACGT", 3), [true, false, false, false, true, true]);
+        assert_eq!(replace_ambiguous(b"ACnNT"), b"ACaAT");
+        assert_eq!(quality_validity(b"II#II", 2, 20), [true, false, false, true]);
+        assert_eq!(quality_penalties(b"II+II", 2, 20, 3), [0, 30, 30, 0]);
+        let merged = merge_validity(Some(vec![true, false, true]), Some(vec![false, true, true]));
+        assert_eq!(merged, Some(vec![false, false, true]));
+    }
+}
@@ -0,0 +1,99 @@
+use std::{slice, vec};
+
+use crate::{Result, StrobeSpec, Strobemer};
+
+/// Iterator over the seeds of many sequences, tagged with their IDs.
+///
+/// Seeds each `(id, seq)` record in turn with the iterator described by a
+/// [`StrobeSpec`] and yields `(id, hash, [m1, m2, m3])`, positions being
+/// relative to the record. Records too short for a single seed (or empty)
+/// yield nothing. Seeds of one record are generated when the iterator
+/// reaches it, so memory holds one record's seeds at a time.
+///
+/// A record the spec's iterator rejects for another reason (such as
+/// non-ASCII bytes) yields its error once, after which iteration stops.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{MultiSeqStrobes, StrobeSpec};
+/// let spec: StrobeSpec = "rand2-k3-w3:5".parse().unwrap();
+/// let records: &[(&str, &[u8])] = &[("r1", b"ACGATCTGGTACCTAG"), ("r2", b"ACG")];
+/// let seeds: Vec<_> = MultiSeqStrobes::new(records, &spec)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert!(!seeds.is_empty() && seeds.iter().all(|&(id, _, _)| id == "r1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiSeqStrobes<'a> {
+    records: slice::Iter<'a, (&'a str, &'a [u8])>,
+    spec: StrobeSpec,
+    id: &'a str,                       // ID of the record being drained
+    pending: vec::IntoIter<Strobemer>, // Remaining seeds of that record
+    failed: bool,                      // Stop after yielding an error
+}
+
+impl<'a> MultiSeqStrobes<'a> {
+    /// Creates an iterator over the seeds of `records`, in order.
+    pub fn new(records: &'a [(&'a str, &'a [u8])], spec: &StrobeSpec) -> Self {
+        Self {
+            records: records.iter(),
+            spec: *spec,
+            id: "",
+            pending: Vec::new().into_iter(),
+            failed: false,
+        }
+    }
+}
+
+impl<'a> Iterator for MultiSeqStrobes<'a> {
+    type Item = Result<(&'a str, u64, [usize; 3])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(s) = self.pending.next() {
+                return Some(Ok((self.id, s.hash, s.positions)));
+            }
+            if self.failed {
+                return None;
+            }
+            let &(id, seq) = self.records.next()?;
+            match self.spec.seeds(seq) {
+                Ok(seeds) => (self.id, self.pending) = (id, seeds.into_iter()),
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StrobeError;
+
+    #[test]
+    fn tags_seeds_with_record_ids() {
+        let spec: StrobeSpec = "min2-k3-w3:5".parse().unwrap();
+        let (a, b): (&[u8], &[u8]) = (b"ACGATCTGGTACCTAG", b"GGATTACACGATCGGA");
+        let records = [("a", a), ("short", &b"AC"[..]), ("b", b)];
+        let got: Vec<_> = MultiSeqStrobes::new(&records, &spec).map(Result::unwrap).collect();
+
+        let mut expected = Vec::new();
+        for (id, seq) in [("a", a), ("b", b)] {
+            let seeds = spec.min_strobes(seq).unwrap().iter_seeds();
+            expected.extend(seeds.map(|s| (id, s.hash, s.positions)));
+        }
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn stops_after_error() {
+        let spec: StrobeSpec = "rand2-k3-w3:5".parse().unwrap();
+        let records = [("bad", "ACGATCTGGTÄCCTAG".as_bytes()), ("ok", b"ACGATCTGGTACCTAG")];
+        let mut it = MultiSeqStrobes::new(&records, &spec);
+        assert_eq!(it.next(), Some(Err(StrobeError::InvalidSequence)));
+        assert_eq!(it.next(), None);
+    }
+}
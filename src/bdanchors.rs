@@ -0,0 +1,163 @@
+use std::mem;
+
+use crate::{
+    Result, StrobeError,
+    hashes::{KmerHasher, NtHash64, canonical_hashes, compute_min_hashes_into},
+    scratch::Scratch,
+    selection::TieBreak,
+};
+
+/// Iterator over bidirectional anchors (bd-anchors) of a DNA/RNA sequence.
+///
+/// For every window of `ell` bases, the bd-anchor is the start of the window's
+/// minimal rotation. This implementation uses the hash-minimal, reduced form:
+/// rotations are ranked by the hash of their first `k` bases, and only the
+/// `ell − k + 1` rotations that do not wrap around are considered.
+///
+/// Anchors are strand-symmetric: k-mers are ranked by the smaller of the
+/// hashes of both strands, and each window contributes both its leftmost and
+/// its rightmost minimum, which swap roles on the reverse complement. The
+/// anchors of the reverse complement are therefore those of `seq` mirrored
+/// (`len − k − p`), whatever the hasher.
+///
+/// Anchors are yielded once each, in increasing order.
+///
+#[derive(Debug, Clone)]
+pub struct BdAnchors {
+    anchors: Vec<usize>, // Distinct anchors in increasing order
+    idx: usize,          // Index of the next anchor to emit
+}
+
+impl BdAnchors {
+    /// Constructs a new [`BdAnchors`] iterator using the default hash function (`NtHash64`).
+    ///
+    /// # Arguments
+    ///
+    /// * `seq` – Input nucleotide sequence as a byte slice (ASCII only).
+    /// * `k` – Length of the rotation prefix used for ranking; must be in `[1, 64]`.
+    /// * `ell` – Window length in bases; must satisfy `k ≤ ell ≤ seq.len()`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BdAnchors)` on success.
+    /// * `Err(StrobeError)` if parameters are invalid or the sequence is too short.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::BdAnchors;
+    /// let anchors: Vec<usize> = BdAnchors::new(b"ACGATCTGGTACCTAG", 3, 6).unwrap().collect();
+    /// assert!(anchors.windows(2).all(|p| p[0] < p[1]));
+    /// ```
+    pub fn new(seq: &[u8], k: usize, ell: usize) -> Result<Self> {
+        Self::with_hasher(seq, k, ell, &NtHash64)
+    }
+
+    /// Constructs a new [`BdAnchors`] iterator with a user-defined hash function.
+    ///
+    /// See [`BdAnchors::new`] for the parameters.
+    pub fn with_hasher<H>(seq: &[u8], k: usize, ell: usize, hasher: &H) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        Self::with_scratch(seq, k, ell, hasher, &mut Scratch::new())
+    }
+
+    /// Constructs a new [`BdAnchors`] iterator, taking its window-minima buffer from `scratch`.
+    ///
+    /// Behaves like [`BdAnchors::with_hasher`]; pass the buffer back with
+    /// [`recycle`](Self::recycle) to reuse it for the next sequence.
+    pub fn with_scratch<H>(
+        seq: &[u8],
+        k: usize,
+        ell: usize,
+        hasher: &H,
+        scratch: &mut Scratch,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        if seq.is_empty() || !seq.is_ascii() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if ell < k {
+            return Err(StrobeError::InvalidWindowLength);
+        }
+        if seq.len() < ell {
+            return Err(StrobeError::SequenceTooShort);
+        }
+
+        let hashes = canonical_hashes(hasher, seq, k)?;
+        let w = ell - k + 1;
+        compute_min_hashes_into::<usize>(&hashes, w, TieBreak::Leftmost, scratch);
+        let mut anchors = mem::take(&mut scratch.minloc);
+        compute_min_hashes_into::<usize>(&hashes, w, TieBreak::Rightmost, scratch);
+        // Windows before the first complete one hold no minimum
+        anchors.drain(..w - 1);
+        anchors.extend_from_slice(&scratch.minloc[w - 1..]);
+        anchors.sort_unstable();
+        anchors.dedup();
+
+        Ok(Self { anchors, idx: 0 })
+    }
+
+    /// Returns the anchor buffer to `scratch` for reuse by the next construction.
+    pub fn recycle(self, scratch: &mut Scratch) {
+        scratch.minloc = self.anchors;
+    }
+}
+
+impl Iterator for BdAnchors {
+    type Item = usize;
+
+    /// Returns the next distinct anchor position.
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = *self.anchors.get(self.idx)?;
+        self.idx += 1;
+        Some(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KmerCodeHasher, reverse_complement};
+
+    #[test]
+    fn every_window_has_an_anchor() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATC";
+        let (k, ell) = (3, 8);
+        let anchors: Vec<usize> = BdAnchors::new(seq, k, ell).unwrap().collect();
+        assert!(anchors.windows(2).all(|p| p[0] < p[1]));
+        for start in 0..=seq.len() - ell {
+            assert!(anchors.iter().any(|&a| a >= start && a + k <= start + ell));
+        }
+    }
+
+    #[test]
+    fn reverse_complement_mirrors_anchors() {
+        // Repeats and a palindrome produce tied minima
+        let seq = b"ACGATCTGGTACCTAGGATTACAACGTTACACGATCGGATCACGATCTGG";
+        let rc = reverse_complement(seq);
+        for (k, ell) in [(3, 8), (5, 12), (4, 4)] {
+            for hasher in [&NtHash64 as &dyn KmerHasher, &KmerCodeHasher] {
+                let fwd: Vec<usize> =
+                    BdAnchors::with_hasher(seq, k, ell, hasher).unwrap().collect();
+                let mut rev: Vec<usize> = BdAnchors::with_hasher(&rc, k, ell, hasher)
+                    .unwrap()
+                    .map(|p| seq.len() - k - p)
+                    .collect();
+                rev.reverse();
+                assert_eq!(fwd, rev, "k={k} ell={ell}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_window_shorter_than_k() {
+        let err = BdAnchors::new(b"ACGTACGT", 5, 4).unwrap_err();
+        assert_eq!(err, StrobeError::InvalidWindowLength);
+    }
+}
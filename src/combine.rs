@@ -0,0 +1,356 @@
+use std::{fmt, sync::Arc};
+
+use crate::{Result, StrobeError, alphabet::mix, hashes::seed_key};
+
+/// Combines the strobe hashes of a seed into the emitted strobemer hash.
+///
+/// `strobes` holds the k-mer hashes `[h1, h2]` (order 2) or `[h1, h2, h3]`
+/// (order 3) in strobe order. Combiners only shape the emitted value: strobe
+/// selection in [`MinStrobes`](crate::MinStrobes) and
+/// [`RandStrobes`](crate::RandStrobes) always uses the legacy partial hashes,
+/// so swapping the combiner never changes which positions are picked.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{RandStrobes, StrobeCombiner};
+///
+/// struct Xor;
+/// impl StrobeCombiner for Xor {
+///     fn combine(&self, strobes: &[u64]) -> u64 {
+///         strobes.iter().fold(0, |acc, &h| acc ^ h)
+///     }
+/// }
+///
+/// let mut rs = RandStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+/// rs.set_combiner(Xor);
+/// assert!(rs.next().is_some());
+/// ```
+pub trait StrobeCombiner: Send + Sync + 'static {
+    fn combine(&self, strobes: &[u64]) -> u64;
+
+    /// Combines the strobe hashes given also the strobes' k-mer positions.
+    ///
+    /// The iterators call this method; the default ignores `positions` and
+    /// calls [`combine`](StrobeCombiner::combine).
+    fn combine_at(&self, strobes: &[u64], positions: &[usize]) -> u64 {
+        let _ = positions;
+        self.combine(strobes)
+    }
+}
+
+/// Any thread-safe closure over the strobe hashes is a combiner.
+///
+/// ```
+/// use strobemers_rs::MinStrobes;
+/// let mut ms = MinStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+/// ms.set_combiner(|s: &[u64]| s.iter().fold(0u64, |acc, &h| acc.rotate_left(7) ^ h));
+/// assert!(ms.next().is_some());
+/// ```
+impl<F> StrobeCombiner for F
+where
+    F: Fn(&[u64]) -> u64 + Send + Sync + 'static,
+{
+    fn combine(&self, strobes: &[u64]) -> u64 {
+        self(strobes)
+    }
+}
+
+/// The position-weighted combination of the original implementation.
+///
+/// Order 2 yields `h1/2 + h2/3`, order 3 `h1/3 + h2/4 + h3/5` (integer
+/// division). Dividing keeps the sum from overflowing while leaving every
+/// strobe with a distinct weight, so swapping strobes changes the result.
+/// It is the formula of the original strobemers implementation, which the
+/// iterators use with [`CombineScheme::Legacy`]. It is lossy: the low bits
+/// of each strobe hash are dropped and the sums cover only part of `u64`
+/// unevenly; the iterators default to the uniform [`CombineScheme::Mix`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LegacyCombiner;
+
+impl StrobeCombiner for LegacyCombiner {
+    fn combine(&self, strobes: &[u64]) -> u64 {
+        match *strobes {
+            [h1, h2] => (h1 >> 1) + h2 / 3,
+            [h1, h2, h3] => h1 / 3 + (h2 >> 2) + h3 / 5,
+            _ => unreachable!("strobemers have two or three strobes"),
+        }
+    }
+}
+
+/// Order-independent combination: [`LegacyCombiner`] applied to the sorted hashes.
+///
+/// `combine(&[a, b]) == combine(&[b, a])`, so a seed and its counterpart with
+/// the strobes visited in reverse order (e.g. on the other strand) hash the same.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SymmetricCombiner;
+
+impl StrobeCombiner for SymmetricCombiner {
+    fn combine(&self, strobes: &[u64]) -> u64 {
+        let mut s = [0u64; 3];
+        let s = &mut s[..strobes.len()];
+        s.copy_from_slice(strobes);
+        s.sort_unstable();
+        LegacyCombiner.combine(s)
+    }
+}
+
+/// Built-in scheme for combining the strobe hashes of a seed.
+///
+/// Select one with `set_combine_scheme` on [`RandStrobes`](crate::RandStrobes)
+/// or [`MinStrobes`](crate::MinStrobes). The iterators default to
+/// [`Mix`](Self::Mix); [`Legacy`](Self::Legacy) reproduces the seeds of the
+/// original implementation and of indexes built with it.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{CombineScheme, LegacyCombiner, RandStrobes, StrobeCombiner};
+/// let seq = b"ACGATCTGGTACCTAG";
+/// let mut rs = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+/// rs.set_combine_scheme(CombineScheme::Legacy);
+/// let mut custom = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+/// custom.set_combiner(LegacyCombiner);
+/// assert!(rs.eq(custom));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CombineScheme {
+    /// `h1/2 + h2/3` (order 2) or `h1/3 + h2/4 + h3/5` (order 3), as
+    /// [`LegacyCombiner`]; kept for compatibility. The divisions drop low
+    /// bits of every strobe hash and the sums cover only part of `u64`.
+    Legacy,
+    /// Rotate-XOR folding with a SplitMix64 finalizer after every strobe:
+    /// `acc = mix(rotl(acc, 23) ^ h)`, starting from 0. Each step is a
+    /// bijection of the strobe hash, so no strobe bits are lost, outputs
+    /// are uniform over `u64` and swapping strobes changes the result.
+    #[default]
+    Mix,
+}
+
+impl StrobeCombiner for CombineScheme {
+    fn combine(&self, strobes: &[u64]) -> u64 {
+        match self {
+            Self::Legacy => LegacyCombiner.combine(strobes),
+            Self::Mix => strobes.iter().fold(0, |acc, &h| mix(acc.rotate_left(23) ^ h)),
+        }
+    }
+}
+
+/// Another combiner's output remapped into a seeded hash universe.
+///
+/// Pairs with [`SeededHasher`](crate::SeededHasher): the combined hash of the
+/// inner combiner (by default [`CombineScheme::Mix`], the iterators' default)
+/// is passed through a bijective mix keyed by `seed`, so runs with different
+/// seeds emit hashes from independent universes even when the strobe hashes
+/// agree. Seeded hashes are thus a fixed remapping of the unseeded ones;
+/// wrap [`CombineScheme::Legacy`] to seed legacy pipelines.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{RandStrobes, SeededCombiner};
+/// let seq = b"ACGATCTGGTACCTAG";
+/// let mut rs = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+/// rs.set_combiner(SeededCombiner::new(7));
+/// let plain = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+/// assert!(rs.zip(plain).all(|(a, b)| a != b));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SeededCombiner<C = CombineScheme> {
+    inner: C,
+    key: u64,
+}
+
+impl SeededCombiner {
+    /// Seeds the default [`CombineScheme::Mix`].
+    pub fn new(seed: u64) -> Self {
+        Self::wrap(CombineScheme::Mix, seed)
+    }
+}
+
+impl<C: StrobeCombiner> SeededCombiner<C> {
+    /// Seeds `inner`.
+    pub fn wrap(inner: C, seed: u64) -> Self {
+        Self { inner, key: seed_key(seed) }
+    }
+}
+
+impl<C: StrobeCombiner> StrobeCombiner for SeededCombiner<C> {
+    fn combine(&self, strobes: &[u64]) -> u64 {
+        mix(self.inner.combine(strobes) ^ self.key)
+    }
+}
+
+/// Exact packing of the strobes' 2-bit codes and offsets for small `k`.
+///
+/// For use with [`KmerCodeHasher`](crate::KmerCodeHasher), whose k-mer
+/// "hashes" are the bases packed two bits each. When `2·n·k ≤ 64` the codes
+/// of all strobes fit side by side in one word: they fill its high `2·n·k`
+/// bits, m1 first, and the remaining low bits hold the offsets `m2 − m1`
+/// (and `m3 − m2`), [`offset_bits`](Self::offset_bits) bits each. Offsets
+/// too large for their field are stored as its maximum. The seed is thus no
+/// hash but the strobes themselves: [`unpack`](Self::unpack) recovers them
+/// for verification or direct lookup of the sequence. Strobe selection is
+/// unchanged; it still runs on the k-mer codes.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerCodeHasher, PackedCombiner, RandStrobes, decode_kmer};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let packer = PackedCombiner::new(2, 4)?;
+/// let mut rs = RandStrobes::with_hasher(seq, 2, 4, 3, 5, &KmerCodeHasher)?;
+/// rs.set_combiner(packer);
+/// while let Some(seed) = rs.next() {
+///     let [m1, m2, _] = rs.indexes();
+///     let (codes, offsets) = packer.unpack(seed);
+///     assert_eq!(decode_kmer(codes[1], 4), &seq[m2..m2 + 4]);
+///     assert_eq!(offsets, [(m2 - m1) as u64]);
+/// }
+/// # Ok::<(), strobemers_rs::StrobeError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedCombiner {
+    n: u32,
+    k: u32,
+}
+
+impl PackedCombiner {
+    /// Packs seeds of order `n` with strobe length `k`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PackedCombiner)` – If the codes fit in 64 bits.
+    /// * `Err(StrobeError::OrderNotSupported)` – If `n` is not 2 or 3.
+    /// * `Err(StrobeError::StrobeLengthTooSmall)` – If `k` is 0.
+    /// * `Err(StrobeError::PackedSeedTooWide)` – If `2·n·k > 64`.
+    pub fn new(n: u8, k: usize) -> Result<Self> {
+        if !matches!(n, 2 | 3) {
+            return Err(StrobeError::OrderNotSupported);
+        }
+        if k == 0 {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if 2 * n as usize * k > 64 {
+            return Err(StrobeError::PackedSeedTooWide);
+        }
+        Ok(Self { n: n as u32, k: k as u32 })
+    }
+
+    /// Returns the width of each offset field; 0 if the codes fill the word.
+    pub fn offset_bits(&self) -> u32 {
+        (64 - 2 * self.n * self.k) / (self.n - 1)
+    }
+
+    /// Splits a packed seed into its strobes' k-mer codes (decode them with
+    /// [`decode_kmer`](crate::decode_kmer)) and offsets.
+    pub fn unpack(&self, seed: u64) -> (Vec<u64>, Vec<u64>) {
+        let (width, bits) = (2 * self.k, self.offset_bits());
+        let low = 64 - self.n * width;
+        let codes = (0..self.n)
+            .map(|i| (seed >> (low + width * (self.n - 1 - i))) & low_mask(width))
+            .collect();
+        let offsets = (0..self.n - 1)
+            .map(|j| (seed >> (bits * (self.n - 2 - j))) & low_mask(bits))
+            .collect();
+        (codes, offsets)
+    }
+}
+
+impl StrobeCombiner for PackedCombiner {
+    /// Packs the codes with all offsets 0.
+    fn combine(&self, strobes: &[u64]) -> u64 {
+        self.combine_at(strobes, &[0; 3][..strobes.len()])
+    }
+
+    fn combine_at(&self, strobes: &[u64], positions: &[usize]) -> u64 {
+        let (width, bits) = (2 * self.k, self.offset_bits());
+        let codes = strobes.iter().fold(0, |acc, &c| acc << width | (c & low_mask(width)));
+        let offsets = positions.windows(2).fold(0, |acc, p| {
+            let off = (p[0].abs_diff(p[1]) as u64).min(low_mask(bits));
+            acc << bits | off
+        });
+        codes << (64 - self.n * width) | offsets
+    }
+}
+
+/// Mask of the `bits` lowest bits (`bits < 64`).
+fn low_mask(bits: u32) -> u64 {
+    (1 << bits) - 1
+}
+
+/// Shared handle to a user-selected combiner, stored by the iterators.
+#[derive(Clone)]
+pub(crate) struct Combiner(Arc<dyn StrobeCombiner>);
+
+impl Combiner {
+    pub(crate) fn new<C: StrobeCombiner>(combiner: C) -> Self {
+        Self(Arc::new(combiner))
+    }
+
+    #[inline(always)]
+    pub(crate) fn combine(&self, strobes: &[u64], positions: &[usize]) -> u64 {
+        self.0.combine_at(strobes, positions)
+    }
+}
+
+impl fmt::Debug for Combiner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Combiner(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RandStrobes;
+
+    #[test]
+    fn symmetric_ignores_order() {
+        let (a, b, c) = (u64::MAX - 7, 12345, 1 << 40);
+        assert_eq!(SymmetricCombiner.combine(&[a, b]), SymmetricCombiner.combine(&[b, a]));
+        assert_eq!(SymmetricCombiner.combine(&[a, b, c]), SymmetricCombiner.combine(&[c, a, b]));
+        assert_eq!(SymmetricCombiner.combine(&[b, a]), LegacyCombiner.combine(&[b, a]));
+        assert_ne!(LegacyCombiner.combine(&[a, b]), LegacyCombiner.combine(&[b, a]));
+    }
+
+    #[test]
+    fn mix_scheme_keeps_every_bit() {
+        let (a, b) = (u64::MAX - 7, 12345);
+        assert_eq!(CombineScheme::Legacy.combine(&[a, b]), LegacyCombiner.combine(&[a, b]));
+        // Legacy drops the lowest bit of h1; Mix does not
+        assert_eq!(LegacyCombiner.combine(&[a, b]), LegacyCombiner.combine(&[a ^ 1, b]));
+        let mixed = CombineScheme::Mix.combine(&[a, b]);
+        assert_ne!(mixed, CombineScheme::Mix.combine(&[a ^ 1, b]));
+        assert_ne!(mixed, CombineScheme::Mix.combine(&[a, b ^ 1]));
+        assert_ne!(mixed, CombineScheme::Mix.combine(&[b, a]));
+    }
+
+    #[test]
+    fn seeded_remaps_the_default_hashes() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCC";
+        let remap = |h: u64| mix(h ^ seed_key(7));
+        for n in [2, 3] {
+            let mut seeded = RandStrobes::new(seq, n, 3, 3, 5).unwrap();
+            seeded.set_combiner(SeededCombiner::new(7));
+            let plain = RandStrobes::new(seq, n, 3, 3, 5).unwrap();
+            assert!(seeded.eq(plain.map(remap)));
+
+            let mut seeded = RandStrobes::new(seq, n, 3, 3, 5).unwrap();
+            seeded.set_combiner(SeededCombiner::wrap(CombineScheme::Legacy, 7));
+            let mut legacy = RandStrobes::new(seq, n, 3, 3, 5).unwrap();
+            legacy.set_combine_scheme(CombineScheme::Legacy);
+            assert!(seeded.eq(legacy.map(remap)));
+        }
+    }
+
+    #[test]
+    fn packed_roundtrip() {
+        let packer = PackedCombiner::new(3, 10).unwrap();
+        assert_eq!(packer.offset_bits(), 2);
+        let seed = packer.combine_at(&[0x2_5A5A, 1, 0xF_FFFF], &[7, 9, 20]);
+        assert_eq!(packer.unpack(seed), (vec![0x2_5A5A, 1, 0xF_FFFF], vec![2, 3]));
+
+        let full = PackedCombiner::new(2, 16).unwrap();
+        assert_eq!(full.offset_bits(), 0);
+        assert_eq!(full.combine_at(&[1, u64::MAX], &[0, 5]), 1 << 32 | 0xFFFF_FFFF);
+        assert_eq!(PackedCombiner::new(2, 17), Err(StrobeError::PackedSeedTooWide));
+        assert_eq!(PackedCombiner::new(4, 2), Err(StrobeError::OrderNotSupported));
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use crate::{Result, StrobeError, randstrobes::RandStrobes};
+
+/// Seed-level containment between an assembly and a read set.
+///
+/// Produced by [`assembly_containment`]. Fractions are computed over distinct
+/// strobemer hashes and are `0.0` when the corresponding set is empty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Containment {
+    /// Number of distinct seeds in the assembly.
+    pub assembly_seeds: usize,
+    /// Number of distinct seeds in the read set.
+    pub read_seeds: usize,
+    /// Number of distinct seeds present in both.
+    pub shared_seeds: usize,
+    /// Fraction of assembly seeds supported by the reads (completeness).
+    pub assembly_in_reads: f64,
+    /// Fraction of read seeds found in the assembly (a low value hints at contamination).
+    pub reads_in_assembly: f64,
+}
+
+/// Sketches an assembly and a read set with RandStrobes and reports their mutual containment.
+///
+/// Every contig and every read is seeded with the same parameters; records too
+/// short to produce a strobemer are skipped.
+///
+/// # Arguments
+///
+/// * `assembly` – Contig sequences.
+/// * `reads` – Read sequences.
+/// * `n`, `k`, `w_min`, `w_max` – Strobemer parameters, as for [`RandStrobes::new`].
+///
+/// # Returns
+///
+/// * `Ok(Containment)` on success.
+/// * `Err(StrobeError)` if a record is invalid or the parameters are rejected.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::assembly_containment;
+/// let contig = b"ACGATCTGGTACCTAGGATTACACGATCGGATC";
+/// let c = assembly_containment(&[contig], &[&contig[..20], &contig[12..]], 2, 3, 3, 5).unwrap();
+/// assert!(c.reads_in_assembly > 0.9);
+/// ```
+pub fn assembly_containment<A, R>(
+    assembly: &[A],
+    reads: &[R],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+) -> Result<Containment>
+where
+    A: AsRef<[u8]>,
+    R: AsRef<[u8]>,
+{
+    let asm = sketch(assembly, n, k, w_min, w_max)?;
+    let rds = sketch(reads, n, k, w_min, w_max)?;
+    let shared = asm.intersection(&rds).count();
+
+    Ok(Containment {
+        assembly_seeds: asm.len(),
+        read_seeds: rds.len(),
+        shared_seeds: shared,
+        assembly_in_reads: fraction(shared, asm.len()),
+        reads_in_assembly: fraction(shared, rds.len()),
+    })
+}
+
+/// Collects the distinct RandStrobe hashes of all `seqs`, skipping records that are too short.
+pub(crate) fn sketch<S: AsRef<[u8]>>(
+    seqs: &[S],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+) -> Result<HashSet<u64>> {
+    let mut set = HashSet::new();
+    for seq in seqs {
+        match RandStrobes::new(seq.as_ref(), n, k, w_min, w_max) {
+            Ok(rs) => set.extend(rs),
+            Err(StrobeError::SequenceTooShort) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(set)
+}
+
+#[inline]
+fn fraction(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_sets_are_fully_contained() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATC";
+        let c = assembly_containment(&[seq], &[&seq[..], b"ACG"], 2, 3, 3, 5).unwrap();
+        assert_eq!(c.assembly_seeds, c.shared_seeds);
+        assert_eq!(c.assembly_in_reads, 1.0);
+        assert_eq!(c.reads_in_assembly, 1.0);
+    }
+}
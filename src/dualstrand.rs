@@ -0,0 +1,137 @@
+use crate::{
+    Result, StrobeError,
+    ambiguity::hasher_validity,
+    hashes::{KmerHasher, NtHash64},
+    randstrobes::RandStrobes,
+    strand::Strand,
+};
+
+/// Iterator over the RandStrobes of both strands of a sequence in one pass.
+///
+/// The k-mer hashes of both strands are computed once, through
+/// [`KmerHasher::hash_both_strands`], and shared by a forward and a
+/// reverse-complement [`RandStrobes`]. Seeds are interleaved, alternating
+/// forward and reverse while both strands have seeds left, and tagged with
+/// their [`Strand`]. The forward seeds equal those of [`RandStrobes::new`], the
+/// reverse seeds those of [`RandStrobes::new_revcomp`]; all positions are
+/// reported in forward coordinates.
+///
+/// Both underlying iterators can be configured (window shrinking, prime, …)
+/// through [`forward_mut`](Self::forward_mut) and [`reverse_mut`](Self::reverse_mut)
+/// before iteration starts.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{DualStrand, Strand};
+/// let seeds: Vec<(Strand, u64)> = DualStrand::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 3, 5)
+///     .unwrap()
+///     .collect();
+/// assert_eq!(seeds[0].0, Strand::Forward);
+/// assert_eq!(seeds[1].0, Strand::Reverse);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DualStrand {
+    fwd: RandStrobes,     // Seeds of the input
+    rev: RandStrobes,     // Seeds of the reverse complement, in forward coordinates
+    next: Strand,         // Strand to poll first on the next call
+    last: Option<Strand>, // Strand of the most recently returned seed
+}
+
+impl DualStrand {
+    /// Constructs a new [`DualStrand`] iterator using the default hash function (`NtHash64`).
+    ///
+    /// See [`DualStrand::with_hasher`] for details.
+    pub fn new(seq: &[u8], n: u8, k: usize, w_min: usize, w_max: usize) -> Result<Self> {
+        Self::with_hasher(seq, n, k, w_min, w_max, &NtHash64)
+    }
+
+    /// Constructs a new [`DualStrand`] iterator with a user-defined hash function.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq`, `n`, `k`, `w_min`, `w_max` – As for [`RandStrobes::new`].
+    /// * `hasher` – Hasher whose [`hash_both_strands`](KmerHasher::hash_both_strands)
+    ///   provides the k-mer hashes of both strands.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DualStrand)` – If input and hashes are valid.
+    /// * `Err(StrobeError)` – On invalid input, hashing errors, or insufficient sequence length.
+    pub fn with_hasher<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+
+        let (fwd, rev) = hasher.hash_both_strands(seq, k)?;
+        // A k-mer is valid on one strand exactly if it is on the other
+        let fwd_valid = hasher_validity(hasher, seq, k, fwd.len())?;
+        let rev_valid = fwd_valid.as_ref().map(|v| v.iter().rev().copied().collect());
+
+        let len = seq.len();
+        Ok(Self {
+            fwd: RandStrobes::from_strand_hashes(len, n, k, w_min, w_max, fwd, fwd_valid, false),
+            rev: RandStrobes::from_strand_hashes(len, n, k, w_min, w_max, rev, rev_valid, true),
+            next: Strand::Forward,
+            last: None,
+        })
+    }
+
+    /// Returns the forward-strand iterator, e.g. to change its settings.
+    pub fn forward_mut(&mut self) -> &mut RandStrobes {
+        &mut self.fwd
+    }
+
+    /// Returns the reverse-strand iterator, e.g. to change its settings.
+    pub fn reverse_mut(&mut self) -> &mut RandStrobes {
+        &mut self.rev
+    }
+
+    /// Returns the strand of the most recently returned seed, or `None` before the first.
+    pub fn strand(&self) -> Option<Strand> {
+        self.last
+    }
+
+    /// Returns the forward-coordinate indices `[m1, m2, (m3)]` of the most recently returned seed.
+    ///
+    /// If no strobe has been generated yet, returns `[0, 0, 0]`.
+    pub fn indexes(&self) -> [usize; 3] {
+        match self.last {
+            Some(Strand::Forward) => self.fwd.indexes(),
+            Some(Strand::Reverse) => self.rev.indexes(),
+            None => [0, 0, 0],
+        }
+    }
+
+    /// Polls the iterator of `strand`.
+    fn poll(&mut self, strand: Strand) -> Option<u64> {
+        match strand {
+            Strand::Forward => self.fwd.next(),
+            Strand::Reverse => self.rev.next(),
+        }
+    }
+}
+
+impl Iterator for DualStrand {
+    type Item = (Strand, u64);
+
+    /// Returns the next seed, alternating strands until one of them runs out.
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.next;
+        for strand in [first, first.flip()] {
+            if let Some(h) = self.poll(strand) {
+                self.next = strand.flip();
+                self.last = Some(strand);
+                return Some((strand, h));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Result, randstrobes::RandStrobes};
+
+/// Why a seed linking two positions is or is not generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedVerdict {
+    /// The seed is emitted by the iterator.
+    Generated,
+    /// The first position cannot start a seed: it lies past the last anchor,
+    /// its windows do not fit the sequence (without window shrinking), or it is
+    /// excluded by `set_first_strobe_positions`, `set_stride` or any of the masks.
+    NotAnchor,
+    /// The second position lies in none of the anchor's search windows.
+    OutsideWindow,
+    /// Another candidate in the window scored lower (or tied further left), or
+    /// the second position is skipped by the performance mode's sampling.
+    Outscored,
+    /// The seed is formed but dropped by the density threshold (`set_density`),
+    /// the soft mask (`set_soft_mask`), masked intervals (`set_masked_intervals`),
+    /// or an invalid linked strobe.
+    Thinned,
+}
+
+/// Report produced by [`explain`] and [`RandStrobes::explain`].
+///
+/// All positions are k-mer start positions on the input sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedExplanation {
+    /// The outcome.
+    pub verdict: SeedVerdict,
+    /// Inclusive search windows `(low, high)` of m2 (and m3) for the anchor;
+    /// empty if the first position is not an anchor.
+    pub windows: Vec<(usize, usize)>,
+    /// Strobe (2 or 3) whose window contains the second position, if any.
+    pub strobe: Option<u8>,
+    /// Position actually selected in that window.
+    pub chosen: Option<usize>,
+    /// Score of every candidate in that window, in scan order; lowest wins.
+    pub scores: Vec<(usize, u64)>,
+}
+
+impl SeedExplanation {
+    /// Returns the explanation for positions that do not form a seed at all.
+    pub(crate) fn not_anchor() -> Self {
+        Self {
+            verdict: SeedVerdict::NotAnchor,
+            windows: Vec::new(),
+            strobe: None,
+            chosen: None,
+            scores: Vec::new(),
+        }
+    }
+
+    /// Returns the score of `pos` in the explained window, if it was a candidate.
+    pub fn score_of(&self, pos: usize) -> Option<u64> {
+        self.scores.iter().find(|&&(p, _)| p == pos).map(|&(_, s)| s)
+    }
+}
+
+/// Explains whether, and why, RandStrobes link `pos_a` (as m1) to `pos_b`.
+///
+/// Convenience wrapper around [`RandStrobes::explain`] with default settings,
+/// meant for debugging matches a downstream mapper expected but did not find.
+///
+/// # Arguments
+///
+/// * `seq` – Input sequence.
+/// * `n`, `k`, `w_min`, `w_max` – Strobemer parameters, as for [`RandStrobes::new`].
+/// * `pos_a` – Start of the first strobe.
+/// * `pos_b` – Start of a later strobe (m2, or m3 for order 3).
+///
+/// # Returns
+///
+/// * `Ok(SeedExplanation)` – The verdict together with window bounds and candidate scores.
+/// * `Err(StrobeError)` if the sequence or the strobemer parameters are rejected.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{SeedVerdict, explain};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let ex = explain(seq, 2, 3, 3, 5, 0, 1).unwrap();
+/// assert_eq!(ex.verdict, SeedVerdict::OutsideWindow);
+/// assert_eq!(ex.windows, vec![(3, 5)]);
+/// ```
+pub fn explain(
+    seq: &[u8],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    pos_a: usize,
+    pos_b: usize,
+) -> Result<SeedExplanation> {
+    Ok(RandStrobes::new(seq, n, k, w_min, w_max)?.explain(pos_a, pos_b))
+}
@@ -0,0 +1,439 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    iter::Peekable,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    vec,
+};
+
+use crate::{Result, StrobeError, Strobemer};
+
+/// Bytes of one [`SeedRecord`] in a run file.
+const RECORD_BYTES: usize = 20;
+
+/// Default number of records held in memory by [`ExternalSeedSorter`] (about 24 MiB).
+const DEFAULT_MAX_IN_MEMORY: usize = 1 << 20;
+
+/// Default number of sorted runs merged at once by [`ExternalSeedSorter`].
+const DEFAULT_MAX_FAN_IN: usize = 128;
+
+/// Counter making run-file names unique within the process.
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// One seed occurrence: its hash, the sequence it came from and its position.
+///
+/// Records order by hash, then sequence, then position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SeedRecord {
+    /// Seed hash.
+    pub hash: u64,
+    /// Caller-assigned id of the sequence.
+    pub seq_id: u32,
+    /// Start of the first strobe.
+    pub pos: u64,
+}
+
+impl SeedRecord {
+    fn to_bytes(self) -> [u8; RECORD_BYTES] {
+        let mut buf = [0; RECORD_BYTES];
+        buf[..8].copy_from_slice(&self.hash.to_le_bytes());
+        buf[8..12].copy_from_slice(&self.seq_id.to_le_bytes());
+        buf[12..].copy_from_slice(&self.pos.to_le_bytes());
+        buf
+    }
+
+    fn from_bytes(buf: &[u8; RECORD_BYTES]) -> Self {
+        let word = |r: std::ops::Range<usize>| {
+            let mut b = [0; 8];
+            b[..r.len()].copy_from_slice(&buf[r]);
+            u64::from_le_bytes(b)
+        };
+        Self { hash: word(0..8), seq_id: word(8..12) as u32, pos: word(12..20) }
+    }
+}
+
+/// Sorts seed records too many to hold in memory.
+///
+/// Records are buffered up to a memory budget; a full buffer is sorted and
+/// spilled to a temporary run file. [`finish`](Self::finish) merges the runs
+/// (and the records still buffered) into one sorted stream, which can be
+/// deduplicated or reduced to per-hash counts, e.g. to count seed
+/// occurrences over a whole genome collection in bounded memory. Input that
+/// fits the budget never touches the disk.
+///
+/// At most [`max_fan_in`](Self::with_max_fan_in) sorted runs are merged at
+/// once, so the number of open files stays bounded: with more runs,
+/// `finish` first merges them in groups into longer runs. Run files are
+/// created in the chosen directory (the system temporary directory by
+/// default), are open only while written or merged, and are removed once
+/// merged or when the sorter or the stream is dropped.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{ExternalSeedSorter, RandStrobes};
+/// let reads = [&b"ACGATCTGGTACCTAGGATTACACG"[..], b"ACGATCTGGTACCTAGGATTACACG"];
+/// let mut sorter = ExternalSeedSorter::new().with_max_in_memory(8);
+/// for (id, read) in reads.iter().enumerate() {
+///     sorter.push_seeds(id as u32, RandStrobes::new(read, 2, 3, 3, 5).unwrap().iter_seeds())
+///         .unwrap();
+/// }
+/// // Identical reads: every hash occurs an even number of times
+/// for count in sorter.finish().unwrap().hash_counts() {
+///     assert_eq!(count.unwrap().1 % 2, 0);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ExternalSeedSorter {
+    dir: PathBuf,            // Directory of the run files
+    max_in_memory: usize,    // Records buffered before a spill
+    max_fan_in: usize,       // Sources merged at once, the buffer included
+    buffer: Vec<SeedRecord>, // Records not yet spilled
+    runs: Vec<RunFile>,      // Spilled sorted runs
+}
+
+impl Default for ExternalSeedSorter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExternalSeedSorter {
+    /// Creates a sorter spilling to the system temporary directory.
+    pub fn new() -> Self {
+        Self::in_dir(std::env::temp_dir())
+    }
+
+    /// Creates a sorter spilling to `dir`, which must exist.
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_in_memory: DEFAULT_MAX_IN_MEMORY,
+            max_fan_in: DEFAULT_MAX_FAN_IN,
+            buffer: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// Sets the number of records held in memory before a spill (default
+    /// 2²⁰; 0 is treated as 1).
+    pub fn with_max_in_memory(mut self, records: usize) -> Self {
+        self.max_in_memory = records.max(1);
+        self
+    }
+
+    /// Sets the number of sorted runs merged at once, and so of run files
+    /// open at once (default 128; values below 2 are treated as 2).
+    pub fn with_max_fan_in(mut self, runs: usize) -> Self {
+        self.max_fan_in = runs.max(2);
+        self
+    }
+
+    /// Returns the number of run files spilled so far.
+    pub fn runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Adds a record, spilling the buffer if it is full.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – Once the record is buffered.
+    /// * `Err(StrobeError::Io)` – If a spill fails.
+    pub fn push(&mut self, record: SeedRecord) -> Result<()> {
+        self.buffer.push(record);
+        if self.buffer.len() >= self.max_in_memory {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Adds a record per seed of sequence `seq_id`, at the start of its first strobe.
+    ///
+    /// See [`push`](Self::push) for errors.
+    pub fn push_seeds<I>(&mut self, seq_id: u32, seeds: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Strobemer>,
+    {
+        for s in seeds {
+            self.push(SeedRecord { hash: s.hash, seq_id, pos: s.positions[0] as u64 })?;
+        }
+        Ok(())
+    }
+
+    /// Merges the runs and the buffered records into one sorted stream.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SortedSeeds)` – Every record pushed, in ascending order.
+    /// * `Err(StrobeError::Io)` – If merging runs in groups or reading them
+    ///   back fails.
+    pub fn finish(mut self) -> Result<SortedSeeds> {
+        self.buffer.sort_unstable();
+        // Merge the oldest runs until the rest and the buffer fit one pass
+        while self.runs.len() >= self.max_fan_in {
+            let group = self.runs.drain(..self.max_fan_in).collect();
+            let merged = self.write_run(SortedSeeds::new(group, Vec::new())?)?;
+            self.runs.push(merged);
+        }
+        let buffer = std::mem::take(&mut self.buffer);
+        let runs = std::mem::take(&mut self.runs);
+        SortedSeeds::new(runs, buffer)
+    }
+
+    /// Sorts the buffer and writes it to a new run file.
+    fn spill(&mut self) -> Result<()> {
+        self.buffer.sort_unstable();
+        let run = self.write_run(self.buffer.iter().copied().map(Ok))?;
+        self.runs.push(run);
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes sorted records to a new run file, closed when done.
+    fn write_run<I>(&self, records: I) -> Result<RunFile>
+    where
+        I: IntoIterator<Item = Result<SeedRecord>>,
+    {
+        let (run, file) = RunFile::create(&self.dir).map_err(io_error)?;
+        let mut out = BufWriter::new(file);
+        for record in records {
+            out.write_all(&record?.to_bytes()).map_err(io_error)?;
+        }
+        out.flush().map_err(io_error)?;
+        Ok(run)
+    }
+}
+
+/// Sorted stream of the records of an [`ExternalSeedSorter`].
+///
+/// Yields every record in ascending order, duplicates included; see
+/// [`dedup`](Self::dedup) and [`hash_counts`](Self::hash_counts) for reduced
+/// streams. A read error is yielded once, after which the stream is exhausted.
+#[derive(Debug)]
+pub struct SortedSeeds {
+    sources: Vec<Source>,                           // Sorted runs being merged
+    heap: BinaryHeap<Reverse<(SeedRecord, usize)>>, // Head record of each source
+    done: bool,                                     // All sources drained or error reached
+}
+
+#[derive(Debug)]
+enum Source {
+    Memory(vec::IntoIter<SeedRecord>),
+    File { reader: BufReader<File>, _run: RunFile },
+}
+
+impl Source {
+    fn next(&mut self) -> Result<Option<SeedRecord>> {
+        match self {
+            Source::Memory(records) => Ok(records.next()),
+            Source::File { reader, .. } => read_record(reader).map_err(io_error),
+        }
+    }
+}
+
+impl SortedSeeds {
+    fn new(runs: Vec<RunFile>, buffer: Vec<SeedRecord>) -> Result<Self> {
+        let mut sources = Vec::with_capacity(runs.len() + 1);
+        for run in runs {
+            let reader = BufReader::new(File::open(&run.path).map_err(io_error)?);
+            sources.push(Source::File { reader, _run: run });
+        }
+        sources.push(Source::Memory(buffer.into_iter()));
+        let mut heap = BinaryHeap::with_capacity(sources.len());
+        for (i, source) in sources.iter_mut().enumerate() {
+            if let Some(record) = source.next()? {
+                heap.push(Reverse((record, i)));
+            }
+        }
+        Ok(Self { sources, heap, done: false })
+    }
+
+    /// Drops repeated records, keeping one per `(hash, seq_id, pos)`.
+    pub fn dedup(self) -> impl Iterator<Item = Result<SeedRecord>> {
+        let mut it = self.peekable();
+        std::iter::from_fn(move || {
+            let record = it.next()?;
+            if let Ok(r) = record {
+                while it.next_if(|next| matches!(next, Ok(n) if *n == r)).is_some() {}
+            }
+            Some(record)
+        })
+    }
+
+    /// Reduces the stream to the distinct hashes, ascending, with their
+    /// number of records.
+    pub fn hash_counts(self) -> impl Iterator<Item = Result<(u64, u64)>> {
+        let mut it: Peekable<Self> = self.peekable();
+        std::iter::from_fn(move || {
+            let hash = match it.next()? {
+                Ok(r) => r.hash,
+                Err(e) => return Some(Err(e)),
+            };
+            let mut count = 1;
+            while it.next_if(|next| matches!(next, Ok(n) if n.hash == hash)).is_some() {
+                count += 1;
+            }
+            Some(Ok((hash, count)))
+        })
+    }
+}
+
+impl Iterator for SortedSeeds {
+    type Item = Result<SeedRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let Some(Reverse((record, i))) = self.heap.pop() else {
+            self.done = true;
+            return None;
+        };
+        match self.sources[i].next() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, i))),
+            Ok(None) => {
+                // Remove the drained run file early
+                self.sources[i] = Source::Memory(Vec::new().into_iter());
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        Some(Ok(record))
+    }
+}
+
+/// Temporary run file, removed on drop.
+#[derive(Debug)]
+struct RunFile {
+    path: PathBuf,
+}
+
+impl RunFile {
+    /// Creates an empty run file in `dir` and opens it for writing.
+    fn create(dir: &Path) -> io::Result<(Self, File)> {
+        loop {
+            let n = RUN_COUNTER.fetch_add(1, Ordering::Relaxed);
+            let path = dir.join(format!("strobemers-{}-{n}.seeds", process::id()));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { path }, file)),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for RunFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Reads one record, or `None` at the end of the file.
+fn read_record(reader: &mut impl Read) -> io::Result<Option<SeedRecord>> {
+    let mut buf = [0; RECORD_BYTES];
+    let mut filled = 0;
+    while filled < RECORD_BYTES {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(Some(SeedRecord::from_bytes(&buf)))
+}
+
+fn io_error(e: io::Error) -> StrobeError {
+    StrobeError::Io(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(count: u64) -> Vec<SeedRecord> {
+        // Small hash range so that hashes and whole records repeat
+        (0..count)
+            .map(|i| {
+                let x = i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40;
+                SeedRecord { hash: x % 37, seq_id: (x % 3) as u32, pos: x % 5 }
+            })
+            .collect()
+    }
+
+    fn sorter(dir: &Path, records: &[SeedRecord]) -> ExternalSeedSorter {
+        let mut sorter = ExternalSeedSorter::in_dir(dir).with_max_in_memory(16);
+        for &r in records {
+            sorter.push(r).unwrap();
+        }
+        sorter
+    }
+
+    #[test]
+    fn merges_runs_like_an_in_memory_sort() {
+        let dir = std::env::temp_dir().join(format!("strobemers-extsort-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = records(1000);
+        let mut sorted = input.clone();
+        sorted.sort_unstable();
+
+        let s = sorter(&dir, &input);
+        assert_eq!(s.runs(), 62);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 62);
+        let merged: Vec<_> = s.finish().unwrap().map(|r| r.unwrap()).collect();
+        assert_eq!(merged, sorted);
+
+        let mut distinct = sorted.clone();
+        distinct.dedup();
+        let deduped = sorter(&dir, &input).finish().unwrap().dedup();
+        assert_eq!(deduped.map(|r| r.unwrap()).collect::<Vec<_>>(), distinct);
+
+        let counts: Vec<_> = sorter(&dir, &input).finish().unwrap().hash_counts().collect();
+        let counts: Vec<(u64, u64)> = counts.into_iter().map(|c| c.unwrap()).collect();
+        assert_eq!(counts.iter().map(|c| c.1).sum::<u64>(), 1000);
+        for (hash, count) in counts {
+            assert_eq!(input.iter().filter(|r| r.hash == hash).count() as u64, count);
+        }
+
+        // Every run file is gone, merged or not
+        drop(sorter(&dir, &input));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn merges_runs_beyond_the_fan_in_in_groups() {
+        let dir = std::env::temp_dir().join(format!("strobemers-fan-in-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = records(1000);
+        let mut sorted = input.clone();
+        sorted.sort_unstable();
+
+        let s = sorter(&dir, &input).with_max_fan_in(4);
+        assert_eq!(s.runs(), 62);
+        let merged = s.finish().unwrap();
+        // One pass remains: at most 3 runs besides the buffer
+        assert!(merged.sources.len() <= 4);
+        assert!(fs::read_dir(&dir).unwrap().count() <= 3);
+        assert_eq!(merged.map(|r| r.unwrap()).collect::<Vec<_>>(), sorted);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn reports_io_errors() {
+        let missing = std::env::temp_dir().join("strobemers-no-such-dir/nested");
+        let mut sorter = ExternalSeedSorter::in_dir(missing).with_max_in_memory(2);
+        sorter.push(SeedRecord { hash: 1, seq_id: 0, pos: 0 }).unwrap();
+        let err = sorter.push(SeedRecord { hash: 2, seq_id: 0, pos: 0 });
+        assert!(matches!(err, Err(StrobeError::Io(_))));
+    }
+}
@@ -0,0 +1,137 @@
+//! Minimal FASTA reader (feature `fasta`).
+//!
+//! Covers the common "seed every record in a file" workflow without an
+//! external parser. Records may span several lines; blank lines and `\r`
+//! line endings are tolerated. Sequence bytes are passed through unchanged,
+//! so case (soft-masking) and ambiguous bases reach the iterators as written.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{Result, StrobeError, StrobeSpec, Strobemer};
+
+/// Calls `f(id, seq)` for every record of the FASTA file at `path`.
+///
+/// `id` is the header up to the first whitespace, without the leading `>`;
+/// `seq` is the record's sequence lines concatenated. Stops at the first
+/// error returned by `f`.
+///
+/// # Returns
+///
+/// * `Ok(())` – After the last record.
+/// * `Err(StrobeError::Io)` – If the file cannot be opened or read.
+/// * `Err(StrobeError::MalformedRecord)` – If sequence data precedes the first header.
+/// * `Err(StrobeError)` – Whatever `f` returned.
+///
+/// # Example
+/// ```no_run
+/// use strobemers_rs::{RandStrobes, fasta};
+/// fasta::for_each_record("genome.fa", |id, seq| {
+///     let n = RandStrobes::new(seq, 2, 20, 21, 100)?.count();
+///     println!("{id}\t{n}");
+///     Ok(())
+/// })?;
+/// # Ok::<(), strobemers_rs::StrobeError>(())
+/// ```
+pub fn for_each_record<P, F>(path: P, f: F) -> Result<()>
+where
+    P: AsRef<Path>,
+    F: FnMut(&str, &[u8]) -> Result<()>,
+{
+    let file = File::open(path).map_err(|e| StrobeError::Io(e.to_string()))?;
+    for_each_record_in(BufReader::new(file), f)
+}
+
+/// Like [`for_each_record`], reading FASTA text from `reader`.
+pub fn for_each_record_in<R, F>(mut reader: R, mut f: F) -> Result<()>
+where
+    R: BufRead,
+    F: FnMut(&str, &[u8]) -> Result<()>,
+{
+    let mut line = Vec::new();
+    let mut id: Option<String> = None;
+    let mut seq = Vec::new();
+    let mut line_no = 0;
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| StrobeError::Io(e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        line_no += 1;
+        let text = line.trim_ascii_end();
+        if let Some(header) = text.strip_prefix(b">") {
+            if let Some(prev) = id.take() {
+                f(&prev, &seq)?;
+            }
+            seq.clear();
+            let header = String::from_utf8_lossy(header);
+            id = Some(header.split_whitespace().next().unwrap_or_default().to_string());
+        } else if !text.is_empty() {
+            if id.is_none() {
+                return Err(StrobeError::MalformedRecord(line_no));
+            }
+            seq.extend_from_slice(text);
+        }
+    }
+    match id {
+        Some(id) => f(&id, &seq),
+        None => Ok(()),
+    }
+}
+
+/// Seeds every record of the FASTA file at `path` as described by `spec`.
+///
+/// Returns one `(id, seeds)` pair per record, in file order. Records too
+/// short for a single seed get an empty seed list rather than an error.
+///
+/// # Returns
+///
+/// * `Ok(Vec<(String, Vec<Strobemer>)>)` – Seeds per record.
+/// * `Err(StrobeError)` – On read errors, malformed input, or if the spec's
+///   iterator rejects a record for another reason.
+pub fn seed_records<P: AsRef<Path>>(
+    path: P,
+    spec: &StrobeSpec,
+) -> Result<Vec<(String, Vec<Strobemer>)>> {
+    let mut out = Vec::new();
+    for_each_record(path, |id, seq| {
+        out.push((id.to_string(), spec.seeds(seq)?));
+        Ok(())
+    })?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(text: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut out = Vec::new();
+        for_each_record_in(text.as_bytes(), |id, seq| {
+            out.push((id.to_string(), seq.to_vec()));
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    #[test]
+    fn joins_multi_line_records() {
+        let got = records(">r1 desc\nACGT\r\nac\n\n>r2\n>r3\nTT").unwrap();
+        let want = [("r1", &b"ACGTac"[..]), ("r2", b""), ("r3", b"TT")];
+        assert_eq!(got.len(), want.len());
+        for ((id, seq), (want_id, want_seq)) in got.iter().zip(want) {
+            assert_eq!((id.as_str(), seq.as_slice()), (want_id, want_seq));
+        }
+    }
+
+    #[test]
+    fn rejects_sequence_before_header() {
+        assert_eq!(records("\nACGT\n>r1\nA"), Err(StrobeError::MalformedRecord(2)));
+    }
+}
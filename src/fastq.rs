@@ -0,0 +1,145 @@
+//! Minimal FASTQ reader (feature `fastq`).
+//!
+//! Reads four-line records (`@header`, sequence, `+`, qualities). Pair it
+//! with `with_quality_mask` on [`RandStrobes`](crate::RandStrobes) or
+//! [`MinStrobes`](crate::MinStrobes) to keep low-quality bases out of the
+//! seeds without editing the record.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{Result, StrobeError};
+
+/// One FASTQ record, borrowed from the reader's buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastqRecord<'a> {
+    /// Header up to the first whitespace, without the leading `@`.
+    pub id: &'a str,
+    /// Rest of the header after the ID (read group, barcode, …), trimmed.
+    pub description: &'a str,
+    /// Sequence bytes as written.
+    pub seq: &'a [u8],
+    /// Phred+33 quality characters, one per base.
+    pub qual: &'a [u8],
+}
+
+/// Calls `f` for every record of the FASTQ file at `path`.
+///
+/// Stops at the first error returned by `f`.
+///
+/// # Returns
+///
+/// * `Ok(())` – After the last record.
+/// * `Err(StrobeError::Io)` – If the file cannot be opened or read.
+/// * `Err(StrobeError::MalformedRecord)` – On a missing `@` or `+` line, a
+///   truncated record, or qualities whose length differs from the sequence.
+/// * `Err(StrobeError)` – Whatever `f` returned.
+///
+/// # Example
+/// ```no_run
+/// use strobemers_rs::{RandStrobes, fastq};
+/// fastq::for_each_record("reads.fq", |rec| {
+///     let rs = RandStrobes::with_quality_mask(rec.seq, rec.qual, 20, 2, 20, 21, 100)?;
+///     println!("{}\t{}", rec.id, rs.count());
+///     Ok(())
+/// })?;
+/// # Ok::<(), strobemers_rs::StrobeError>(())
+/// ```
+pub fn for_each_record<P, F>(path: P, f: F) -> Result<()>
+where
+    P: AsRef<Path>,
+    F: FnMut(&FastqRecord) -> Result<()>,
+{
+    let file = File::open(path).map_err(|e| StrobeError::Io(e.to_string()))?;
+    for_each_record_in(BufReader::new(file), f)
+}
+
+/// Like [`for_each_record`], reading FASTQ text from `reader`.
+pub fn for_each_record_in<R, F>(mut reader: R, mut f: F) -> Result<()>
+where
+    R: BufRead,
+    F: FnMut(&FastqRecord) -> Result<()>,
+{
+    let mut lines: [Vec<u8>; 4] = Default::default();
+    let mut line_no = 0;
+    loop {
+        // Skip blank lines between records; stop cleanly at end of input
+        let header_line = loop {
+            if !read_line(&mut reader, &mut lines[0])? {
+                return Ok(());
+            }
+            line_no += 1;
+            if !lines[0].is_empty() {
+                break line_no;
+            }
+        };
+        for line in &mut lines[1..] {
+            line_no += 1;
+            if !read_line(&mut reader, line)? {
+                return Err(StrobeError::MalformedRecord(line_no));
+            }
+        }
+
+        let [header, seq, plus, qual] = &lines;
+        let header = header
+            .strip_prefix(b"@")
+            .ok_or(StrobeError::MalformedRecord(header_line))?;
+        if !plus.starts_with(b"+") {
+            return Err(StrobeError::MalformedRecord(header_line + 2));
+        }
+        if qual.len() != seq.len() {
+            return Err(StrobeError::MalformedRecord(header_line + 3));
+        }
+        let header = std::str::from_utf8(header)
+            .map_err(|_| StrobeError::MalformedRecord(header_line))?;
+        let (id, description) = header.split_once(char::is_whitespace).unwrap_or((header, ""));
+        f(&FastqRecord {
+            id,
+            description: description.trim(),
+            seq,
+            qual,
+        })?;
+    }
+}
+
+/// Reads one line into `buf` without its line ending; `false` at end of input.
+fn read_line<R: BufRead>(reader: &mut R, buf: &mut Vec<u8>) -> Result<bool> {
+    buf.clear();
+    let read = reader
+        .read_until(b'\n', buf)
+        .map_err(|e| StrobeError::Io(e.to_string()))?;
+    while matches!(buf.last(), Some(b'\n' | b'\r')) {
+        buf.pop();
+    }
+    Ok(read > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_records_with_metadata() {
+        let text = "@r1 RG:Z:grp1 BC:ACGT\r\nACGT\r\n+\r\nII#I\r\n\n@r2\nTT\n+r2\nII\n";
+        let mut got = Vec::new();
+        for_each_record_in(text.as_bytes(), |rec| {
+            got.push((rec.id.to_string(), rec.description.to_string(), rec.qual.to_vec()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(got[0], ("r1".into(), "RG:Z:grp1 BC:ACGT".into(), b"II#I".to_vec()));
+        assert_eq!(got[1], ("r2".into(), String::new(), b"II".to_vec()));
+    }
+
+    #[test]
+    fn rejects_malformed_records() {
+        let err = |text: &str| for_each_record_in(text.as_bytes(), |_| Ok(())).unwrap_err();
+        assert_eq!(err(">r1\nACGT\n+\nIIII\n"), StrobeError::MalformedRecord(1));
+        assert_eq!(err("@r1\nACGT\n-\nIIII\n"), StrobeError::MalformedRecord(3));
+        assert_eq!(err("@r1\nACGT\n+\nIII\n"), StrobeError::MalformedRecord(4));
+        assert_eq!(err("@r1\nACGT\n+\n"), StrobeError::MalformedRecord(4));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Result, StrobeError, hashes::KmerHasher};
+
+/// Multiplier of the Fx hash (as in `rustc-hash` 1.x on 64-bit targets).
+const FX_SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+/// K-mer hasher applying the Fx hash (rustc's `FxHasher`) to each k-mer's bytes.
+///
+/// Fx folds eight bytes per multiply and has no finalizer, so it is the
+/// fastest built-in hasher but also the weakest: it avalanches poorly, low
+/// bits depend mostly on the last bytes, and similar k-mers get correlated
+/// hashes. Strobe selection and density thinning assume uniform hashes, so
+/// seeds chosen with it are less random than with [`NtHash64`](crate::NtHash64).
+/// Use it for prototyping, or where only equality of hashes matters (e.g.
+/// exact deduplication). Bytes are hashed as given: lower- and uppercase
+/// bases differ.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{FxHashHasher, KmerHasher};
+/// let hashes = FxHashHasher.hash_all(b"ACGTACG", 3).unwrap();
+/// assert_eq!(hashes[0], hashes[4]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHashHasher;
+
+impl KmerHasher for FxHashHasher {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+        out.clear();
+        out.extend(seq.windows(k).map(fx_hash));
+        Ok(())
+    }
+}
+
+/// Folds one word into the Fx state.
+fn add(h: u64, word: u64) -> u64 {
+    (h.rotate_left(5) ^ word).wrapping_mul(FX_SEED)
+}
+
+/// Fx hash of `bytes`: 8-byte words, then a 4-, 2- and 1-byte tail.
+fn fx_hash(bytes: &[u8]) -> u64 {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut h = 0;
+    for word in &mut chunks {
+        h = add(h, u64::from_le_bytes(word.try_into().expect("8 bytes")));
+    }
+    let mut rest = chunks.remainder();
+    for width in [4, 2, 1] {
+        if rest.len() >= width {
+            let mut word = [0u8; 8];
+            word[..width].copy_from_slice(&rest[..width]);
+            h = add(h, u64::from_le_bytes(word));
+            rest = &rest[width..];
+        }
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_words_and_tail() {
+        let kmer = b"ACGTACGTACGTACG"; // 8 + 4 + 2 + 1 bytes
+        let mut expected = add(0, u64::from_le_bytes(*b"ACGTACGT"));
+        expected = add(expected, u32::from_le_bytes(*b"ACGT") as u64);
+        expected = add(expected, u16::from_le_bytes(*b"AC") as u64);
+        expected = add(expected, b'G' as u64);
+        assert_eq!(fx_hash(kmer), expected);
+        assert_eq!(FxHashHasher.hash_all(kmer, 15).unwrap(), [expected]);
+    }
+}
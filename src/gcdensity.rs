@@ -0,0 +1,98 @@
+use crate::{Result, StrobeError};
+
+/// Per-GC-bin density thresholds for seed thinning.
+///
+/// Each k-mer start is assigned the GC bin of the `window` bases centered on
+/// the k-mer. Thresholds are calibrated per bin so that the same fraction of
+/// each bin's seeds passes, see `set_gc_density` on the iterators.
+#[derive(Debug, Clone)]
+pub(crate) struct GcDensity {
+    bins: Vec<u8>,        // GC bin of each k-mer start (forward coordinates)
+    thresholds: Vec<u64>, // Largest hash kept per bin
+}
+
+impl GcDensity {
+    /// Assigns every k-mer start of `seq` to one of `n_bins` GC bins.
+    ///
+    /// Thresholds start out as `u64::MAX` until [`calibrate`](Self::calibrate).
+    pub(crate) fn new(
+        seq: &[u8],
+        k: usize,
+        n_kmers: usize,
+        window: usize,
+        n_bins: u8,
+    ) -> Result<Self> {
+        if window == 0 {
+            return Err(StrobeError::InvalidWindowLength);
+        }
+        if n_bins == 0 {
+            return Err(StrobeError::InvalidBinCount);
+        }
+        if seq.len() != n_kmers + k - 1 {
+            return Err(StrobeError::InvalidSequence);
+        }
+
+        // prefix[i] = number of G/C bases in seq[..i]
+        let mut prefix = Vec::with_capacity(seq.len() + 1);
+        prefix.push(0usize);
+        for &b in seq {
+            let gc = matches!(b, b'G' | b'C' | b'g' | b'c') as usize;
+            prefix.push(prefix[prefix.len() - 1] + gc);
+        }
+
+        let bins = (0..n_kmers)
+            .map(|p| {
+                let center = p + k / 2;
+                let start = center.saturating_sub(window / 2);
+                let end = (start + window).min(seq.len());
+                let frac = (prefix[end] - prefix[start]) as f64 / (end - start) as f64;
+                ((frac * n_bins as f64) as u8).min(n_bins - 1)
+            })
+            .collect();
+        Ok(Self {
+            bins,
+            thresholds: vec![u64::MAX; n_bins as usize],
+        })
+    }
+
+    /// Sets each bin's threshold to keep a fraction `f` of the seeds in `samples`.
+    ///
+    /// `samples` yields `(first strobe position, combined hash)` for every seed
+    /// the unthinned iterator emits. Bins without seeds keep everything.
+    pub(crate) fn calibrate<I>(&mut self, samples: I, f: f64)
+    where
+        I: IntoIterator<Item = (usize, u64)>,
+    {
+        let mut per_bin = vec![Vec::new(); self.thresholds.len()];
+        for (pos, h) in samples {
+            per_bin[self.bins[pos] as usize].push(h);
+        }
+        for (threshold, mut hashes) in self.thresholds.iter_mut().zip(per_bin) {
+            if hashes.is_empty() {
+                continue;
+            }
+            let keep = ((f * hashes.len() as f64).ceil() as usize).clamp(1, hashes.len());
+            let (_, &mut nth, _) = hashes.select_nth_unstable(keep - 1);
+            *threshold = nth;
+        }
+    }
+
+    /// Returns the threshold for a seed whose first strobe starts at `pos`.
+    #[inline(always)]
+    pub(crate) fn threshold(&self, pos: usize) -> u64 {
+        self.thresholds[self.bins[pos] as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bins_follow_gc_content() {
+        let seq = b"AAAAAAAAAAGGGGGGGGGG";
+        let gc = GcDensity::new(seq, 2, seq.len() - 1, 4, 2).unwrap();
+        assert_eq!(gc.bins[0], 0);
+        assert_eq!(gc.bins[seq.len() - 2], 1);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{
+    MinStrobes, RandStrobes, Result, StrobeError, Strobemer,
+    hashes::{KmerHasher, NtHash64},
+    scratch::Scratch,
+    spec::Protocol,
+    stream::block_overlap,
+};
+
+/// Strobemer generator configured once and called on many sequences.
+///
+/// Holds the protocol, order, strobe length, window offsets and hasher, plus
+/// a [`Scratch`] whose hash and window-minima buffers every call reuses, so
+/// seeding short reads stops allocating once the buffers have grown to the
+/// longest read. Seeds are those of [`RandStrobes::with_hasher`] or
+/// [`MinStrobes::with_hasher`] with default settings. A sequence too short
+/// for a single seed (or empty) gets no seeds rather than an error.
+///
+/// Long sequences can be seeded in cache-sized blocks; see
+/// [`with_block_size`](Self::with_block_size).
+///
+/// Keep one generator per thread; it is not shared between calls.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{Protocol, RandStrobes, StrobeGenerator};
+/// let mut generator = StrobeGenerator::new(Protocol::Rand, 2, 3, 3, 5).unwrap();
+/// for read in [&b"ACGATCTGGTACCTAG"[..], b"GGATTACACGATCGGATC", b"ACG"] {
+///     let seeds = generator.seeds(read).unwrap();
+///     let expected: Vec<_> = match RandStrobes::new(read, 2, 3, 3, 5) {
+///         Ok(rs) => rs.iter_seeds().collect(),
+///         Err(_) => Vec::new(),
+///     };
+///     assert_eq!(seeds, expected);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StrobeGenerator<H = NtHash64> {
+    protocol: Protocol,
+    n: u8,            // Strobemer order: 2 or 3
+    k: usize,         // Strobe length
+    w_min: usize,     // Minimum window offset
+    w_max: usize,     // Maximum window offset
+    hasher: H,        // k-mer hasher
+    scratch: Scratch, // Buffers reused across calls
+    block: usize,     // Anchors seeded per block; 0 for the whole sequence at once
+}
+
+impl StrobeGenerator {
+    /// Creates a generator using the default hash function (`NtHash64`).
+    ///
+    /// See [`StrobeGenerator::with_hasher`] for details.
+    pub fn new(protocol: Protocol, n: u8, k: usize, w_min: usize, w_max: usize) -> Result<Self> {
+        Self::with_hasher(protocol, n, k, w_min, w_max, NtHash64)
+    }
+}
+
+impl<H: KmerHasher> StrobeGenerator<H> {
+    /// Creates a generator with a user-defined hash function.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StrobeGenerator)` – Ready to seed sequences.
+    /// * `Err(StrobeError::OrderNotSupported)` – If `n` is not 2 or 3.
+    /// * `Err(StrobeError::StrobeLengthTooSmall)` – If `k` is not in `1..=64`.
+    /// * `Err(StrobeError::InvalidWindowOffsets)` – If `w_max` is 0 or below `w_min`.
+    pub fn with_hasher(
+        protocol: Protocol,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: H,
+    ) -> Result<Self> {
+        // The sequence-independent checks of the iterators' constructors
+        if !matches!(n, 2 | 3) {
+            return Err(StrobeError::OrderNotSupported);
+        }
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if w_max == 0 || w_min > w_max {
+            return Err(StrobeError::InvalidWindowOffsets);
+        }
+        Ok(Self { protocol, n, k, w_min, w_max, hasher, scratch: Scratch::new(), block: 0 })
+    }
+
+    /// Seeds sequences in blocks of `bases` anchors (0, the default, seeds
+    /// each sequence at once).
+    ///
+    /// Each block, plus the `(n − 1)·w_max + k − 1` bases its last windows
+    /// reach into, is hashed, its strobes selected and its seeds emitted
+    /// before the next one is hashed, so the hashes (and window minima) a
+    /// block reads stay in cache instead of being evicted by those of the
+    /// rest of the sequence. Blocks of about 2¹⁵ bases fit a 1 MiB L2 cache.
+    /// Seeds are the same; the bases shared by consecutive blocks are hashed
+    /// twice.
+    pub fn with_block_size(mut self, bases: usize) -> Self {
+        self.block = bases;
+        self
+    }
+
+    /// Returns the hasher.
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Calls `f` on each seed of `seq` in iteration order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – Once every seed has been visited.
+    /// * `Err(StrobeError)` – If the iterator's constructor rejects `seq`
+    ///   (e.g. invalid bytes) or hashing fails. With blocks, a hashing error
+    ///   may follow the seeds of earlier blocks.
+    pub fn for_each_seed<F>(&mut self, seq: &[u8], mut f: F) -> Result<()>
+    where
+        F: FnMut(Strobemer),
+    {
+        if seq.is_empty() {
+            return Ok(());
+        }
+        let overlap = block_overlap(self.n, self.k, self.w_max);
+        if self.block == 0 || seq.len() <= self.block + overlap {
+            return self.seed_block(seq, usize::MAX, &mut f);
+        }
+        // Reject invalid bytes before any seed, at their whole-sequence position
+        self.hasher.validate_sequence(seq)?;
+        let mut base = 0;
+        let mut shifted = |mut s: Strobemer, base: usize| {
+            s.positions[..s.order as usize].iter_mut().for_each(|p| *p += base);
+            f(s)
+        };
+        // Anchors before `base + block` have every window inside the block
+        while seq.len() - base > self.block + overlap {
+            let block = &seq[base..base + self.block + overlap];
+            self.seed_block(block, self.block, &mut |s| shifted(s, base))?;
+            base += self.block;
+        }
+        self.seed_block(&seq[base..], usize::MAX, &mut |s| shifted(s, base))
+    }
+
+    /// Calls `f` on the seeds of `seq` whose first strobe starts before `limit`.
+    fn seed_block(&mut self, seq: &[u8], limit: usize, f: &mut dyn FnMut(Strobemer)) -> Result<()> {
+        let (n, k, w_min, w_max) = (self.n, self.k, self.w_min, self.w_max);
+        let (hasher, scratch) = (&self.hasher, &mut self.scratch);
+        let built = match self.protocol {
+            Protocol::Rand => {
+                RandStrobes::with_scratch(seq, n, k, w_min, w_max, hasher, scratch).map(|mut rs| {
+                    while let Some(s) = rs.next_seed().filter(|s| s.positions[0] < limit) {
+                        f(s);
+                    }
+                    rs.recycle(scratch);
+                })
+            }
+            Protocol::Min => {
+                MinStrobes::with_scratch(seq, n, k, w_min, w_max, hasher, scratch).map(|mut ms| {
+                    while let Some(s) = ms.next_seed().filter(|s| s.positions[0] < limit) {
+                        f(s);
+                    }
+                    ms.recycle(scratch);
+                })
+            }
+        };
+        match built {
+            Err(StrobeError::SequenceTooShort) => Ok(()),
+            built => built,
+        }
+    }
+
+    /// Appends the seeds of `seq` to `out`, reusing its capacity.
+    ///
+    /// See [`for_each_seed`](Self::for_each_seed) for errors.
+    pub fn seeds_into(&mut self, seq: &[u8], out: &mut Vec<Strobemer>) -> Result<()> {
+        self.for_each_seed(seq, |s| out.push(s))
+    }
+
+    /// Collects the seeds of `seq`.
+    ///
+    /// See [`for_each_seed`](Self::for_each_seed) for errors.
+    pub fn seeds(&mut self, seq: &[u8]) -> Result<Vec<Strobemer>> {
+        let mut seeds = Vec::new();
+        self.seeds_into(seq, &mut seeds)?;
+        Ok(seeds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KmerCodeHasher;
+
+    #[test]
+    fn matches_fresh_iterators_across_calls() {
+        let reads: Vec<Vec<u8>> = (0..12)
+            .map(|i| b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGT"[i..].repeat(1 + i % 4))
+            .collect();
+        for protocol in [Protocol::Rand, Protocol::Min] {
+            let mut generator =
+                StrobeGenerator::with_hasher(protocol, 3, 4, 2, 6, KmerCodeHasher).unwrap();
+            for read in &reads {
+                let expected: Vec<Strobemer> = match protocol {
+                    Protocol::Rand => RandStrobes::with_hasher(read, 3, 4, 2, 6, &KmerCodeHasher)
+                        .map(|rs| rs.iter_seeds().collect()),
+                    Protocol::Min => MinStrobes::with_hasher(read, 3, 4, 2, 6, &KmerCodeHasher)
+                        .map(|ms| ms.iter_seeds().collect()),
+                }
+                .unwrap_or_default();
+                assert_eq!(generator.seeds(read).unwrap(), expected);
+            }
+            // The longest read's buffers are kept for the next call
+            assert!(generator.scratch.hashes.capacity() >= reads[3].len() - 3);
+            assert!(generator.seeds(b"ACGTN-ACGTACGT").is_err());
+        }
+    }
+
+    #[test]
+    fn blocks_match_whole_sequence() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCATTGCAGGCTAAGCTTGCA".repeat(9);
+        for protocol in [Protocol::Rand, Protocol::Min] {
+            for n in [2, 3] {
+                let mut whole = StrobeGenerator::new(protocol, n, 4, 2, 7).unwrap();
+                let expected = whole.seeds(&seq).unwrap();
+                for block in [1, 5, 64, 500] {
+                    let generator = StrobeGenerator::new(protocol, n, 4, 2, 7).unwrap();
+                    let mut generator = generator.with_block_size(block);
+                    let seeds = generator.seeds(&seq).unwrap();
+                    assert_eq!(seeds, expected, "{protocol:?} n={n} block={block}");
+                }
+            }
+        }
+        // Invalid bytes are reported at their whole-sequence position
+        let mut generator =
+            StrobeGenerator::with_hasher(Protocol::Rand, 2, 3, 2, 5, KmerCodeHasher).unwrap();
+        let bad = [&seq[..300], b"-", &seq[300..]].concat();
+        let err = generator.seeds(&bad).err();
+        assert!(err.is_some());
+        assert_eq!(generator.with_block_size(8).seeds(&bad).err(), err);
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        let new = |n, k, w_min, w_max| StrobeGenerator::new(Protocol::Min, n, k, w_min, w_max);
+        assert_eq!(new(4, 3, 1, 5).err(), Some(StrobeError::OrderNotSupported));
+        assert_eq!(new(2, 0, 1, 5).err(), Some(StrobeError::StrobeLengthTooSmall));
+        assert_eq!(new(2, 3, 6, 5).err(), Some(StrobeError::InvalidWindowOffsets));
+    }
+}
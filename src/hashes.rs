@@ -1,10 +1,143 @@
-use crate::{Result, StrobeError};
+use crate::{
+    Result, StrobeError,
+    alphabet::mix,
+    position::Position,
+    scratch::Scratch,
+    selection::TieBreak,
+    util::{nt4, reverse_complement},
+};
 use nthash_rs::kmer::NtHashBuilder;
+use std::{hash::Hasher, mem};
 
-pub trait KmerHasher: Send + Sync + 'static {
+/// Multiplier seeding the extra hashes of [`KmerHasher::hash_all_multi`] (as in ntHash).
+const MULTI_SEED: u64 = 0x90b4_5d39_fb6d_a1fa;
+/// Shift mixing the extra hashes of [`KmerHasher::hash_all_multi`] (as in ntHash).
+const MULTI_SHIFT: u32 = 27;
+
+pub trait KmerHasher {
     fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>>;
+
+    /// Hashes the k-mers of `seq` into `out`, replacing its contents.
+    ///
+    /// Lets callers seeding many short sequences keep one buffer instead of
+    /// allocating a hash vector per sequence (see [`Scratch`]). The built-in
+    /// hashers write into `out` directly; the default moves the result of
+    /// [`hash_all`](Self::hash_all) into it. On error the contents of `out`
+    /// are unspecified.
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        *out = self.hash_all(seq, k)?;
+        Ok(())
+    }
+
+    /// Hashes every k-mer of `seq` with `m` hash functions.
+    ///
+    /// Returns `m` values per k-mer, flattened: `out[i * m + j]` is hash `j`
+    /// of the k-mer starting at `i`, and hash 0 is the one of
+    /// [`hash_all`](Self::hash_all). Useful for Bloom-filter insertion or
+    /// multi-seed schemes from a single pass. The default derives hash `j ≥ 1`
+    /// from hash 0 with ntHash's multiplicative scheme; [`NtHash64`] returns
+    /// ntHash's own extra hashes.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidHashCount` if `m` is zero (or above 255 for [`NtHash64`]).
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerHasher, NtHash64};
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let multi = NtHash64.hash_all_multi(seq, 5, 3).unwrap();
+    /// let single = NtHash64.hash_all(seq, 5).unwrap();
+    /// assert_eq!(multi.len(), 3 * single.len());
+    /// assert!(multi.chunks(3).zip(&single).all(|(hs, &h)| hs[0] == h));
+    /// ```
+    fn hash_all_multi(&self, seq: &[u8], k: usize, m: usize) -> Result<Vec<u64>> {
+        if m == 0 {
+            return Err(StrobeError::InvalidHashCount);
+        }
+        let hashes = self.hash_all(seq, k)?;
+        let mut out = Vec::with_capacity(hashes.len() * m);
+        for h in hashes {
+            out.push(h);
+            out.extend((1..m).map(|j| extra_hash(h, k, j)));
+        }
+        Ok(out)
+    }
+
+    /// Hashes the k-mers of `seq` and of its reverse complement.
+    ///
+    /// Returns `(forward, reverse)`, where `reverse[i]` is the hash of the
+    /// i-th k-mer of the reverse complement. The default runs
+    /// [`hash_all`](Self::hash_all) on both strands; strand-symmetric hashers
+    /// can derive one vector from the other instead.
+    fn hash_both_strands(&self, seq: &[u8], k: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+        let fwd = self.hash_all(seq, k)?;
+        let rev = self.hash_all(&reverse_complement(seq), k)?;
+        if fwd.len() != rev.len() {
+            return Err(StrobeError::IncompleteHashValues);
+        }
+        Ok((fwd, rev))
+    }
+
+    /// Returns, per k-mer start of `seq`, whether the k-mer may be used for seeding.
+    ///
+    /// Iterators never start a seed at an invalid k-mer nor pick one as a
+    /// later strobe, and MinStrobes window minima skip it, e.g. for runs of
+    /// `N` or hard-masked sequence. The mask has one entry per hash of
+    /// [`hash_all`](Self::hash_all) on the same input. `None` (the default)
+    /// means every k-mer is valid.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerHasher, NtHash64, RandStrobes, Result};
+    ///
+    /// // Treats hard-masked `X` bases as invalid
+    /// struct HardMasked;
+    /// impl KmerHasher for HardMasked {
+    ///     fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+    ///         let unmasked: Vec<u8> =
+    ///             seq.iter().map(|&b| if b == b'X' { b'A' } else { b }).collect();
+    ///         NtHash64.hash_all(&unmasked, k)
+    ///     }
+    ///     fn validity(&self, seq: &[u8], k: usize) -> Option<Vec<bool>> {
+    ///         Some(seq.windows(k).map(|w| !w.contains(&b'X')).collect())
+    ///     }
+    /// }
+    ///
+    /// let seq = b"ACGATCTGGTXXXXXGGATTACACG";
+    /// let mut rs = RandStrobes::with_hasher(seq, 2, 3, 3, 5, &HardMasked).unwrap();
+    /// while rs.next().is_some() {
+    ///     let [m1, m2, _] = rs.indexes();
+    ///     assert!(!seq[m1..m1 + 3].contains(&b'X') && !seq[m2..m2 + 3].contains(&b'X'));
+    /// }
+    /// ```
+    fn validity(&self, _seq: &[u8], _k: usize) -> Option<Vec<bool>> {
+        None
+    }
+
+    /// Checks that `seq` consists of symbols this hasher accepts.
+    ///
+    /// Constructors taking a hasher call this instead of a fixed check, so
+    /// hashers over other alphabets (see [`AlphabetHasher`](crate::AlphabetHasher))
+    /// can admit arbitrary bytes or reject symbols of their own choosing.
+    /// The default accepts any ASCII sequence.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSequence` (default) if `seq` contains a non-ASCII byte.
+    fn validate_sequence(&self, seq: &[u8]) -> Result<()> {
+        match seq.is_ascii() {
+            true => Ok(()),
+            false => Err(StrobeError::InvalidSequence),
+        }
+    }
 }
 
+/// Canonical ntHash (the default hasher), as computed by nthash-rs.
+///
+/// Each k-mer hashes to the sum of its forward and reverse-complement ntHash
+/// values, so a k-mer and its reverse complement receive the same hash and
+/// `hash_all(seq)` equals `hash_all(revcomp(seq))` reversed.
 pub struct NtHash64;
 impl Default for NtHash64 {
     fn default() -> Self {
@@ -14,6 +147,12 @@ impl Default for NtHash64 {
 
 impl KmerHasher for NtHash64 {
     fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
         if !(1..=64).contains(&k) {
             return Err(StrobeError::StrobeLengthTooSmall);
         }
@@ -27,12 +166,311 @@ impl KmerHasher for NtHash64 {
             .finish()
             .map_err(StrobeError::from)?;
 
-        let mut out = Vec::with_capacity(seq.len() - k + 1);
+        out.clear();
+        out.reserve(seq.len() - k + 1);
         for (_, h) in it {
             out.push(h[0]);
         }
+        Ok(())
+    }
+
+    fn hash_all_multi(&self, seq: &[u8], k: usize, m: usize) -> Result<Vec<u64>> {
+        if !(1..=u8::MAX as usize).contains(&m) {
+            return Err(StrobeError::InvalidHashCount);
+        }
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+
+        let it = NtHashBuilder::new(seq)
+            .k(k as u16)
+            .num_hashes(m as u8)
+            .finish()
+            .map_err(StrobeError::from)?;
+
+        let mut out = Vec::with_capacity((seq.len() - k + 1) * m);
+        for (_, h) in it {
+            out.extend_from_slice(&h[..m]);
+        }
+        Ok(out)
+    }
+
+    /// ntHash is strand-symmetric, so the reverse strand is the forward result
+    /// reversed; the sequence is hashed once.
+    fn hash_both_strands(&self, seq: &[u8], k: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+        let fwd = self.hash_all(seq, k)?;
+        let rev = fwd.iter().rev().copied().collect();
+        Ok((fwd, rev))
+    }
+}
+
+/// Derives hash `j` of a k-mer from its base hash `h`, as ntHash does for its extra hashes.
+fn extra_hash(h: u64, k: usize, j: usize) -> u64 {
+    let t = h.wrapping_mul(j as u64 ^ (k as u64).wrapping_mul(MULTI_SEED));
+    t ^ (t >> MULTI_SHIFT)
+}
+
+/// Canonical ntHash under an explicit name: the values of [`NtHash64`].
+///
+/// [`NtHash64`] is already strand-symmetric, so this hasher delegates to it
+/// and hashes each strand once; taking `min(forward, reverse-complement)`
+/// on top would only double the work. For strand-neutral seeds from hashers
+/// that are not canonical, see
+/// [`RandStrobes::canonical_with_hasher`](crate::RandStrobes::canonical_with_hasher).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NtHashCanonical;
+
+impl KmerHasher for NtHashCanonical {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        NtHash64.hash_all(seq, k)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        NtHash64.hash_all_into(seq, k, out)
+    }
+
+    fn hash_all_multi(&self, seq: &[u8], k: usize, m: usize) -> Result<Vec<u64>> {
+        NtHash64.hash_all_multi(seq, k, m)
+    }
+
+    fn hash_both_strands(&self, seq: &[u8], k: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+        NtHash64.hash_both_strands(seq, k)
+    }
+}
+
+/// Exact 2-bit k-mer codes for `k ≤ 32`.
+///
+/// Each value is the k-mer packed two bits per base (see [`nt4`]: A=0, C=1,
+/// G=2, T/U=3), first base in the most significant position. Unlike a hash it
+/// is invertible with [`decode_kmer`], so seeds can be verified exactly. Codes
+/// are not uniformly distributed, which biases strobe selection towards
+/// A-rich k-mers compared to [`NtHash64`].
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerCodeHasher, KmerHasher, decode_kmer};
+/// let codes = KmerCodeHasher.hash_all(b"ACGTA", 3).unwrap();
+/// assert_eq!(codes[0], 0b00_01_10);
+/// assert_eq!(decode_kmer(codes[2], 3), b"GTA");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KmerCodeHasher;
+
+impl KmerHasher for KmerCodeHasher {
+    /// # Errors
+    ///
+    /// * `StrobeLengthTooSmall` if `k` is not in `[1, 32]`.
+    /// * `SequenceTooShort` if `seq` is shorter than `k`.
+    /// * `InvalidSequence` if `seq` contains a byte other than `ACGTU` (any case).
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        if !(1..=32).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+
+        let mask = if k == 32 { u64::MAX } else { (1u64 << (2 * k)) - 1 };
+        out.clear();
+        out.reserve(seq.len() - k + 1);
+        let mut code = 0u64;
+        for (i, &b) in seq.iter().enumerate() {
+            let c = nt4(b);
+            if c > 3 {
+                return Err(StrobeError::InvalidSequence);
+            }
+            code = ((code << 2) | c as u64) & mask;
+            if i + 1 >= k {
+                out.push(code);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adapter hashing each k-mer through a [`std::hash::Hasher`].
+///
+/// For every k-mer the prototype hasher is cloned, fed the k-mer bytes with
+/// [`Hasher::write`] and finished, so any ecosystem hasher with a `Clone`
+/// implementation (e.g. ahash, fxhash, or a seeded SipHash) can be plugged in
+/// without a custom [`KmerHasher`]. Bytes are hashed as given: lower- and
+/// uppercase bases differ. This is an O(n·k) scan; rolling hashers such as
+/// [`NtHash64`] are faster for long k-mers.
+///
+/// # Example
+/// ```
+/// use std::collections::hash_map::DefaultHasher;
+/// use strobemers_rs::{KmerHasher, RandStrobes, StdHasherKmer};
+/// let hasher = StdHasherKmer::new(DefaultHasher::new());
+/// let hashes = hasher.hash_all(b"ACGTACG", 3).unwrap();
+/// assert_eq!(hashes[0], hashes[4]);
+/// let rs = RandStrobes::with_hasher(b"ACGATCTGGTACCTAG", 2, 3, 3, 5, &hasher).unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdHasherKmer<H> {
+    prototype: H,
+}
+
+impl<H> StdHasherKmer<H>
+where
+    H: Hasher + Clone,
+{
+    /// Wraps `prototype`, whose state is cloned for each k-mer.
+    pub fn new(prototype: H) -> Self {
+        Self { prototype }
+    }
+}
+
+impl<H> KmerHasher for StdHasherKmer<H>
+where
+    H: Hasher + Clone,
+{
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+
+        out.clear();
+        out.extend(seq.windows(k).map(|kmer| {
+            let mut h = self.prototype.clone();
+            h.write(kmer);
+            h.finish()
+        }));
+        Ok(())
+    }
+}
+
+/// Adapter drawing the hashes of another hasher from a seeded hash universe.
+///
+/// Each hash of the inner hasher is remapped through a bijective mix keyed by
+/// `seed`, so equal k-mers still hash equally and no collisions are added,
+/// while runs with different seeds see independent-looking hash functions
+/// (e.g. for multi-probe sketching, or against inputs crafted for one fixed
+/// hash). Strobe selection depends on the hash values, so seeds differ in the
+/// positions they pick too. Validity and sequence checks are the inner hasher's.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerHasher, NtHash64, RandStrobes, SeededHasher};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let a = SeededHasher::new(NtHash64, 1).hash_all(seq, 5).unwrap();
+/// let b = SeededHasher::new(NtHash64, 2).hash_all(seq, 5).unwrap();
+/// assert_ne!(a, b);
+/// let rs = RandStrobes::with_hasher(seq, 2, 3, 3, 5, &SeededHasher::new(NtHash64, 1)).unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SeededHasher<H> {
+    inner: H,
+    key: u64, // Mixed from the seed, XORed into every hash before remixing
+}
+
+impl<H: KmerHasher> SeededHasher<H> {
+    /// Wraps `inner`, drawing its hashes from the universe selected by `seed`.
+    pub fn new(inner: H, seed: u64) -> Self {
+        Self { inner, key: seed_key(seed) }
+    }
+
+    /// Returns the wrapped hasher.
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    fn remap(&self, hashes: &mut [u64]) {
+        hashes.iter_mut().for_each(|h| *h = mix(*h ^ self.key));
+    }
+}
+
+impl<H: KmerHasher> KmerHasher for SeededHasher<H> {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
         Ok(out)
     }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        self.inner.hash_all_into(seq, k, out)?;
+        self.remap(out);
+        Ok(())
+    }
+
+    fn hash_both_strands(&self, seq: &[u8], k: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+        let (mut fwd, mut rev) = self.inner.hash_both_strands(seq, k)?;
+        self.remap(&mut fwd);
+        self.remap(&mut rev);
+        Ok((fwd, rev))
+    }
+
+    fn validity(&self, seq: &[u8], k: usize) -> Option<Vec<bool>> {
+        self.inner.validity(seq, k)
+    }
+
+    fn validate_sequence(&self, seq: &[u8]) -> Result<()> {
+        self.inner.validate_sequence(seq)
+    }
+}
+
+/// Derives the mixing key of a hash universe from a user seed.
+pub(crate) fn seed_key(seed: u64) -> u64 {
+    mix(seed.wrapping_add(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Recovers the k-mer (uppercase `ACGT`) from a [`KmerCodeHasher`] code; `k` must be ≤ 32.
+pub fn decode_kmer(code: u64, k: usize) -> Vec<u8> {
+    (0..k)
+        .rev()
+        .map(|i| b"ACGT"[((code >> (2 * i)) & 3) as usize])
+        .collect()
+}
+
+/// Hashes both strands of `seq` with `hasher` and keeps the smaller value per k-mer.
+///
+/// Position `i` on the forward strand pairs with position `len − k − i` on the
+/// reverse complement.
+pub(crate) fn canonical_hashes<H: KmerHasher + ?Sized>(
+    hasher: &H,
+    seq: &[u8],
+    k: usize,
+) -> Result<Vec<u64>> {
+    let (fwd, rev) = hasher.hash_both_strands(seq, k)?;
+    Ok(fwd.iter().zip(rev.iter().rev()).map(|(&f, &r)| f.min(r)).collect())
+}
+
+/// Returns, per k-mer of `seq`, whether its reverse complement is its canonical
+/// form: the smaller one comparing 2-bit codes (A < C < G < T) base by base.
+///
+/// Unlike a comparison of hashes this does not depend on the hasher, which
+/// may be strand-symmetric (e.g. [`NtHash64`]). Palindromes count as forward.
+pub(crate) fn reverse_is_canonical(seq: &[u8], k: usize) -> Vec<bool> {
+    let rc = |b: u8| match nt4(b) {
+        4 => 4,
+        c => 3 - c,
+    };
+    seq.windows(k)
+        .map(|kmer| {
+            let mut pairs = kmer.iter().zip(kmer.iter().rev()).map(|(&f, &r)| (nt4(f), rc(r)));
+            pairs.find(|(f, r)| f != r).is_some_and(|(f, r)| r < f)
+        })
+        .collect()
 }
 
 /// For a sliding window of width `w` over the given slice of hash values,
@@ -49,19 +487,121 @@ impl KmerHasher for NtHash64 {
 ///
 /// Only valid when `i ≥ w - 1`; for indices `< w - 1`, the values in `locs` and `mins` remain default (0 and `u64::MAX`).
 ///
+/// Ties resolve to the rightmost position, see [`compute_min_hashes_with`].
 pub fn compute_min_hashes(hashes: &[u64], w: usize) -> (Vec<usize>, Vec<u64>) {
+    compute_min_hashes_with(hashes, w, TieBreak::Rightmost)
+}
+
+/// [`compute_min_hashes`] with ties among equal minima resolved by `tie`.
+///
+/// Equal values share their secondary hash, so [`TieBreak::SecondaryHash`]
+/// selects the leftmost position, like [`TieBreak::Leftmost`].
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{TieBreak, compute_min_hashes_with};
+/// let (left, _) = compute_min_hashes_with(&[4, 1, 7, 1], 4, TieBreak::Leftmost);
+/// let (right, _) = compute_min_hashes_with(&[4, 1, 7, 1], 4, TieBreak::Rightmost);
+/// assert_eq!((left[3], right[3]), (1, 3));
+/// ```
+pub fn compute_min_hashes_with(
+    hashes: &[u64],
+    w: usize,
+    tie: TieBreak,
+) -> (Vec<usize>, Vec<u64>) {
+    let mut scratch = Scratch::default();
+    compute_min_hashes_into::<usize>(hashes, w, tie, &mut scratch);
+    (scratch.minloc, scratch.minval)
+}
+
+/// [`compute_min_hashes_with`] writing into the `minval` buffer of `scratch` and
+/// its location buffer of type `P`, reusing their capacity and that of the deque.
+pub(crate) fn compute_min_hashes_into<P: Position>(
+    hashes: &[u64],
+    w: usize,
+    tie: TieBreak,
+    scratch: &mut Scratch,
+) {
+    let mut locs = mem::take(P::locations(scratch));
+    slide_min(hashes, w, tie, &mut locs, scratch);
+    *P::locations(scratch) = locs;
+}
+
+/// Sliding-window minima of `hashes` into `locs` and the buffers of `scratch`.
+fn slide_min<P: Position>(
+    hashes: &[u64],
+    w: usize,
+    tie: TieBreak,
+    locs: &mut Vec<P>,
+    scratch: &mut Scratch,
+) {
     assert!(w >= 1, "window size must be ≥ 1");
     let n = hashes.len();
+    let Scratch { minval: mins, idx_q, val_q, .. } = scratch;
+    locs.clear();
+    mins.clear();
 
     if w == 1 {
-        return ((0..n).collect(), hashes.to_vec());
+        locs.extend((0..n).map(P::from_usize));
+        mins.extend_from_slice(hashes);
+        return;
     }
 
-    let mut locs = vec![0usize; n];
-    let mut mins = vec![u64::MAX; n];
+    locs.resize(n, P::default());
+    mins.resize(n, u64::MAX);
+    if w <= 64 {
+        slide_min_mask(hashes, w, tie, locs, mins);
+    } else {
+        slide_min_deque(hashes, w, tie, locs, mins, idx_q, val_q);
+    }
+}
+
+/// Monotonic-deque minima for windows of up to 64 k-mers, the deque being a
+/// bitmask over the window: bit `b` set while at k-mer `i` means k-mer
+/// `i − b` is queued. Advancing shifts the mask, expiry is a mask, the tail is
+/// the lowest set bit and the head the highest, so the loop needs no deque
+/// buffers or modulo arithmetic; values are read from `hashes` directly.
+fn slide_min_mask<P: Position>(
+    hashes: &[u64],
+    w: usize,
+    tie: TieBreak,
+    locs: &mut [P],
+    mins: &mut [u64],
+) {
+    let window = u64::MAX >> (64 - w);
+    let mut queued = 0u64;
+    for (i, &h) in hashes.iter().enumerate() {
+        queued = (queued << 1) & window;
 
-    let mut idx_q = vec![0usize; w];
-    let mut val_q = vec![0u64; w];
+        // Equal values queued before `h` lose to it only under `Rightmost`
+        let evicts = |q: u64| q > h || (q == h && tie == TieBreak::Rightmost);
+        while queued != 0 && evicts(hashes[i - queued.trailing_zeros() as usize]) {
+            queued &= queued - 1;
+        }
+        queued |= 1;
+
+        if i >= w - 1 {
+            let head = i - (63 - queued.leading_zeros() as usize);
+            locs[i] = P::from_usize(head);
+            mins[i] = hashes[head];
+        }
+    }
+}
+
+/// Monotonic-deque minima over ring buffers of `w` positions and values.
+fn slide_min_deque<P: Position>(
+    hashes: &[u64],
+    w: usize,
+    tie: TieBreak,
+    locs: &mut [P],
+    mins: &mut [u64],
+    idx_q: &mut Vec<usize>,
+    val_q: &mut Vec<u64>,
+) {
+    idx_q.clear();
+    idx_q.resize(w, 0);
+    val_q.clear();
+    val_q.resize(w, 0);
     let mut head = 0usize;
     let mut len = 0usize;
 
@@ -72,7 +612,9 @@ pub fn compute_min_hashes(hashes: &[u64], w: usize) -> (Vec<usize>, Vec<u64>) {
             len -= 1;
         }
 
-        while len > 0 && val_q[(head + len - 1) % w] >= h {
+        // Equal values queued before `h` lose to it only under `Rightmost`
+        let evicts = |queued: u64| queued > h || (queued == h && tie == TieBreak::Rightmost);
+        while len > 0 && evicts(val_q[(head + len - 1) % w]) {
             len -= 1;
         }
 
@@ -82,17 +624,37 @@ pub fn compute_min_hashes(hashes: &[u64], w: usize) -> (Vec<usize>, Vec<u64>) {
         len += 1;
 
         if i >= w - 1 {
-            locs[i] = idx_q[head];
+            locs[i] = P::from_usize(idx_q[head]);
             mins[i] = val_q[head];
         }
     }
-    (locs, mins)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn mask_minima_match_deque() {
+        let mut state = 11u64;
+        let hashes: Vec<u64> = (0..500)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 40) % 7
+            })
+            .collect();
+        for w in 2..=64 {
+            for tie in [TieBreak::Leftmost, TieBreak::Rightmost] {
+                let (mut locs, mut mins) = (vec![0usize; 500], vec![u64::MAX; 500]);
+                slide_min_mask(&hashes, w, tie, &mut locs, &mut mins);
+                let (mut q_locs, mut q_mins) = (vec![0usize; 500], vec![u64::MAX; 500]);
+                let (mut idx_q, mut val_q) = (Vec::new(), Vec::new());
+                slide_min_deque(&hashes, w, tie, &mut q_locs, &mut q_mins, &mut idx_q, &mut val_q);
+                assert_eq!((locs, mins), (q_locs, q_mins), "w={w} {tie:?}");
+            }
+        }
+    }
+
     #[test]
     fn slide_min_window_three() {
         // Test vector: [5, 3, 6, 1, 4]
@@ -106,4 +668,115 @@ mod tests {
         assert_eq!(&mins[2..], &[3, 1, 1]);
         assert_eq!(&locs[2..], &[1, 3, 3]);
     }
+
+    #[test]
+    fn canonical_is_strand_symmetric() {
+        let seq = b"ACGATCTGGTACCTAG";
+        let fwd = NtHashCanonical.hash_all(seq, 5).unwrap();
+        let mut rev = NtHashCanonical.hash_all(&reverse_complement(seq), 5).unwrap();
+        rev.reverse();
+        assert_eq!(fwd, rev);
+
+        assert_eq!(fwd, NtHash64.hash_all(seq, 5).unwrap());
+
+        let (both_fwd, both_rev) = NtHashCanonical.hash_both_strands(seq, 5).unwrap();
+        assert_eq!(both_fwd, fwd);
+        assert_eq!(both_rev, NtHashCanonical.hash_all(&reverse_complement(seq), 5).unwrap());
+    }
+
+    #[test]
+    fn nthash_both_strands_matches_hashing_each_strand() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGNNACGTTAGC";
+        for k in [1, 4, 7] {
+            let both = NtHash64.hash_both_strands(seq, k).unwrap();
+            let fwd = NtHash64.hash_all(seq, k).unwrap();
+            let rev = NtHash64.hash_all(&reverse_complement(seq), k).unwrap();
+            assert_eq!(both, (fwd, rev), "k={k}");
+        }
+    }
+
+    #[test]
+    fn orientation_follows_the_smaller_code() {
+        // ACG < CGT, so CGT reads canonically on the reverse strand, as does GTT
+        assert_eq!(reverse_is_canonical(b"ACGTT", 3), [false, true, true]);
+        // ACGT is its own reverse complement
+        assert_eq!(reverse_is_canonical(b"ACGT", 4), [false]);
+        let seq = b"ACGATCTGGTACCTAGGATTACACG";
+        let fwd = reverse_is_canonical(seq, 5);
+        let rev = reverse_is_canonical(&reverse_complement(seq), 5);
+        assert!(fwd.iter().zip(rev.iter().rev()).all(|(f, r)| f != r));
+    }
+
+    #[test]
+    fn kmer_codes_round_trip() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGAC";
+        for k in [1, 5, 32] {
+            let codes = KmerCodeHasher.hash_all(seq, k).unwrap();
+            assert_eq!(codes.len(), seq.len() - k + 1);
+            for (i, &c) in codes.iter().enumerate() {
+                assert_eq!(decode_kmer(c, k), &seq[i..i + k]);
+            }
+        }
+        assert!(KmerCodeHasher.hash_all(b"ACGNT", 2).is_err());
+        assert!(KmerCodeHasher.hash_all(seq, 33).is_err());
+    }
+
+    #[test]
+    fn hash_all_into_reuses_buffer() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let seq = b"ACGATCTGGTACCTAG";
+        let mut out = Vec::with_capacity(64);
+        let std_hasher = StdHasherKmer::new(DefaultHasher::new());
+        let hashers: [&dyn KmerHasher; 3] = [&NtHash64, &KmerCodeHasher, &std_hasher];
+        for hasher in hashers {
+            out.push(7);
+            hasher.hash_all_into(seq, 5, &mut out).unwrap();
+            assert_eq!(out, hasher.hash_all(seq, 5).unwrap());
+            assert!(out.capacity() >= 64);
+        }
+    }
+
+    #[test]
+    fn multi_hash_extends_single_hash() {
+        let seq = b"ACGATCTGGTACCTAG";
+        for hasher in [&NtHash64 as &dyn KmerHasher, &KmerCodeHasher] {
+            let single = hasher.hash_all(seq, 4).unwrap();
+            let multi = hasher.hash_all_multi(seq, 4, 4).unwrap();
+            assert_eq!(multi.len(), 4 * single.len());
+            for (hs, &h) in multi.chunks(4).zip(&single) {
+                assert_eq!(hs[0], h);
+                assert!(hs[1] != hs[2] && hs[2] != hs[3]);
+            }
+            assert_eq!(hasher.hash_all_multi(seq, 4, 1).unwrap(), single);
+            assert_eq!(hasher.hash_all_multi(seq, 4, 0), Err(StrobeError::InvalidHashCount));
+        }
+    }
+
+    #[test]
+    fn seeded_hasher_preserves_equality() {
+        let seq = b"ACGTACGTTTGCA";
+        let plain = NtHash64.hash_all(seq, 4).unwrap();
+        let seeded = SeededHasher::new(NtHash64, 42).hash_all(seq, 4).unwrap();
+        assert_eq!(seeded[0], seeded[4]);
+        assert!(plain.iter().zip(&seeded).all(|(p, s)| p != s));
+
+        let (fwd, rev) = SeededHasher::new(NtHashCanonical, 7).hash_both_strands(seq, 4).unwrap();
+        assert_eq!(fwd, rev.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn std_hasher_matches_manual_hashing() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let seq = b"ACGATCTGGTACCTAG";
+        let hashes = StdHasherKmer::new(DefaultHasher::new()).hash_all(seq, 4).unwrap();
+        assert_eq!(hashes.len(), seq.len() - 3);
+        for (i, &h) in hashes.iter().enumerate() {
+            let mut manual = DefaultHasher::new();
+            manual.write(&seq[i..i + 4]);
+            assert_eq!(h, manual.finish());
+        }
+        assert!(StdHasherKmer::<DefaultHasher>::default().hash_all(seq, 17).is_err());
+    }
 }
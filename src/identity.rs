@@ -0,0 +1,106 @@
+use crate::{Result, containment::sketch};
+
+/// Flags which sequence pairs reach an approximate identity of `threshold`.
+///
+/// Each sequence is reduced to a bottom-`sketch_size` sketch of its distinct
+/// RandStrobe hashes. The Jaccard index `J` of a pair is estimated from the
+/// bottom `sketch_size` hashes of the sketches' union, and converted to an
+/// identity estimate `(2J / (1 + J))^(1 / (n · k))`, treating the `n · k`
+/// strobe bases of a seed like a Mash k-mer. This is meant as a cheap
+/// pre-filter in all-vs-all pipelines before any alignment; sequences too
+/// short to produce a strobemer have an empty sketch and are always rejected.
+///
+/// # Arguments
+///
+/// * `pairs` – Sequence pairs to screen.
+/// * `threshold` – Minimum estimated identity in `[0, 1]` for a pair to pass.
+/// * `n`, `k`, `w_min`, `w_max` – Strobemer parameters, as for [`RandStrobes::new`](crate::RandStrobes::new).
+/// * `sketch_size` – Number of hashes kept per sequence; larger is more accurate.
+///
+/// # Returns
+///
+/// * `Ok(Vec<bool>)` – `true` for each pair that passes, in input order.
+/// * `Err(StrobeError)` if a sequence is invalid or the parameters are rejected.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::identity_filter;
+/// let a: &[u8] = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+/// let b: &[u8] = b"TTGCAGGCATTAGCCTAGACCTGATCCATGGACTTAGCAAGTC";
+/// let keep = identity_filter(&[(a, a), (a, b)], 0.9, 2, 3, 3, 5, 64).unwrap();
+/// assert_eq!(keep, vec![true, false]);
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn identity_filter<A, B>(
+    pairs: &[(A, B)],
+    threshold: f64,
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    sketch_size: usize,
+) -> Result<Vec<bool>>
+where
+    A: AsRef<[u8]>,
+    B: AsRef<[u8]>,
+{
+    let mut out = Vec::with_capacity(pairs.len());
+    for (a, b) in pairs {
+        let sa = bottom_k(a.as_ref(), n, k, w_min, w_max, sketch_size)?;
+        let sb = bottom_k(b.as_ref(), n, k, w_min, w_max, sketch_size)?;
+        let j = jaccard(&sa, &sb, sketch_size);
+        let identity = (2.0 * j / (1.0 + j)).powf(1.0 / (n as usize * k) as f64);
+        out.push(identity >= threshold);
+    }
+    Ok(out)
+}
+
+/// Returns the `size` smallest distinct RandStrobe hashes of `seq`, sorted.
+fn bottom_k(
+    seq: &[u8],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    size: usize,
+) -> Result<Vec<u64>> {
+    let mut hashes: Vec<u64> = sketch(&[seq], n, k, w_min, w_max)?.into_iter().collect();
+    hashes.sort_unstable();
+    hashes.truncate(size);
+    Ok(hashes)
+}
+
+/// Estimates the Jaccard index from two sorted bottom-k sketches.
+///
+/// Walks the merged union up to `size` hashes and counts those present in both.
+fn jaccard(a: &[u64], b: &[u64], size: usize) -> f64 {
+    let (mut i, mut j) = (0, 0);
+    let (mut seen, mut shared) = (0usize, 0usize);
+    while seen < size && (i < a.len() || j < b.len()) {
+        match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) if x == y => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+            (Some(x), Some(y)) if x < y => i += 1,
+            (Some(_), None) => i += 1,
+            _ => j += 1,
+        }
+        seen += 1;
+    }
+    if seen == 0 { 0.0 } else { shared as f64 / seen as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jaccard_of_sorted_sketches() {
+        assert_eq!(jaccard(&[1, 2, 3], &[1, 2, 3], 3), 1.0);
+        assert_eq!(jaccard(&[1, 3], &[2, 4], 4), 0.0);
+        assert_eq!(jaccard(&[1, 2, 5], &[1, 3, 5], 4), 0.5);
+        assert_eq!(jaccard(&[], &[], 4), 0.0);
+    }
+}
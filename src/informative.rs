@@ -0,0 +1,83 @@
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// Reference abundance of seed hashes, e.g. occurrence counts from an index.
+///
+/// Used by [`top_informative`] to rank the seeds of a read. Implement it for
+/// your own index type; a `HashMap` from hash to count works out of the box.
+pub trait AbundanceSource {
+    /// Returns how often `hash` occurs in the reference (0 if it does not).
+    fn abundance(&self, hash: u64) -> u64;
+}
+
+impl<C, S> AbundanceSource for HashMap<u64, C, S>
+where
+    C: Copy + TryInto<u64>,
+    S: BuildHasher,
+{
+    #[inline]
+    fn abundance(&self, hash: u64) -> u64 {
+        self.get(&hash).map_or(0, |&c| c.try_into().unwrap_or(u64::MAX))
+    }
+}
+
+/// Keeps the `budget` seeds with the lowest reference abundance.
+///
+/// Rare seeds are the most informative anchors, so a coarse mapper can look up
+/// only these instead of every seed of the read. Seeds absent from the
+/// reference (abundance 0) cannot anchor anything and are dropped; ties are
+/// broken by read order. The kept seeds are returned in read order.
+///
+/// # Arguments
+///
+/// * `seeds` – `(hash, [m1, m2, m3])` pairs, as produced by `with_positions`.
+/// * `budget` – Maximum number of seeds to keep.
+/// * `source` – Reference abundance of each hash.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use strobemers_rs::{RandStrobes, top_informative};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let seeds: Vec<_> = RandStrobes::new(seq, 2, 3, 3, 5).unwrap().with_positions().collect();
+/// // Earlier seeds are rarer in this made-up reference
+/// let counts: HashMap<u64, usize> =
+///     seeds.iter().enumerate().map(|(i, s)| (s.0, i + 1)).collect();
+/// let top = top_informative(seeds.iter().copied(), 2, &counts);
+/// assert_eq!(top, seeds[..2]);
+/// ```
+pub fn top_informative<I, A>(seeds: I, budget: usize, source: &A) -> Vec<(u64, [usize; 3])>
+where
+    I: IntoIterator<Item = (u64, [usize; 3])>,
+    A: AbundanceSource + ?Sized,
+{
+    // (abundance, read order, seed); the first two form a total order
+    let mut ranked: Vec<(u64, usize, (u64, [usize; 3]))> = seeds
+        .into_iter()
+        .enumerate()
+        .map(|(i, seed)| (source.abundance(seed.0), i, seed))
+        .filter(|&(a, _, _)| a > 0)
+        .collect();
+    if budget == 0 {
+        return Vec::new();
+    }
+    if ranked.len() > budget {
+        ranked.select_nth_unstable_by_key(budget - 1, |&(a, i, _)| (a, i));
+        ranked.truncate(budget);
+    }
+    ranked.sort_unstable_by_key(|&(_, i, _)| i);
+    ranked.into_iter().map(|(_, _, seed)| seed).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_rarest_in_read_order() {
+        let seeds = [(10, [0; 3]), (20, [1; 3]), (30, [2; 3]), (40, [3; 3])];
+        let counts: HashMap<u64, u64> = [(10, 5), (20, 1), (40, 1)].into_iter().collect();
+        let top = top_informative(seeds, 2, &counts);
+        assert_eq!(top, vec![(20, [1; 3]), (40, [3; 3])]);
+        assert!(top_informative(seeds, 0, &counts).is_empty());
+    }
+}
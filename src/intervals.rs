@@ -0,0 +1,52 @@
+use crate::{Result, StrobeError};
+
+/// Returns, per k-mer start, whether the k-mer intersects one of `intervals`.
+///
+/// Intervals are half-open `(start, end)` input coordinates; `offset` is the
+/// input position of k-mer 0. Intervals may come in any order and overlap.
+pub(crate) fn kmers_overlapping<I>(
+    intervals: I,
+    offset: usize,
+    n_kmers: usize,
+    k: usize,
+) -> Result<Vec<bool>>
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    // depth[i] - depth[i - 1] = intervals starting minus ending coverage at k-mer i
+    let mut delta = vec![0isize; n_kmers + 1];
+    for (start, end) in intervals {
+        if start > end {
+            return Err(StrobeError::InvalidRegion);
+        }
+        // K-mers at p intersect [start, end) iff start < p + k and p < end
+        let first = (start + 1).saturating_sub(k).saturating_sub(offset);
+        let last = end.saturating_sub(offset).min(n_kmers);
+        if start < end && first < last {
+            delta[first] += 1;
+            delta[last] -= 1;
+        }
+    }
+    let mut depth = 0;
+    Ok(delta[..n_kmers]
+        .iter()
+        .map(|&d| {
+            depth += d;
+            depth > 0
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_kmers() {
+        let hit = kmers_overlapping([(4, 6)], 0, 8, 3).unwrap();
+        assert_eq!(hit, [false, false, true, true, true, true, false, false]);
+        let hit = kmers_overlapping([(0, 1), (8, 20)], 2, 6, 2).unwrap();
+        assert_eq!(hit, [false, false, false, false, false, true]);
+        assert!(kmers_overlapping([(5, 4)], 0, 8, 3).is_err());
+    }
+}
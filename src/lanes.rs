@@ -0,0 +1,151 @@
+use crate::{
+    Result, StrobeError,
+    alphabet::{Alphabet, CLASS_SEEDS, class_validity, mix},
+    hashes::KmerHasher,
+};
+
+/// Number of independent rolling states advanced per iteration.
+const LANES: usize = 4;
+
+/// Lane-batched rolling k-mer hasher computing several hashes per iteration.
+///
+/// A different hash function from the default [`NtHash64`](crate::NtHash64),
+/// so seeds differ from those of the default constructors: it produces
+/// exactly the hashes of an [`AlphabetHasher`](crate::AlphabetHasher) over
+/// the same alphabet and is a faster drop-in for it. The sequence is split
+/// into four segments hashed side by side; each loop iteration advances four
+/// independent rolling states, which removes the loop-carried dependency of
+/// the scalar hash so that the lanes' updates overlap. Each lane still looks
+/// up two byte tables per base, so the gain comes from instruction-level
+/// parallelism rather than vector instructions (see the `hash_all` benches).
+/// Works on stable Rust and without `unsafe`.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{Alphabet, AlphabetHasher, KmerHasher, RandStrobes, LaneBatchedHasher};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCC";
+/// let scalar = AlphabetHasher::new(Alphabet::dna()).hash_all(seq, 5).unwrap();
+/// assert_eq!(LaneBatchedHasher::dna().hash_all(seq, 5).unwrap(), scalar);
+/// let rs = RandStrobes::with_hasher(seq, 2, 3, 3, 5, &LaneBatchedHasher::dna()).unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LaneBatchedHasher {
+    alphabet: Alphabet,
+    seeds: [u64; 256], // Rolling-hash value of every byte (by its code; unknown bytes as 0)
+}
+
+impl LaneBatchedHasher {
+    /// Hashes symbols of `alphabet`.
+    pub fn new(alphabet: Alphabet) -> Self {
+        let seeds =
+            std::array::from_fn(|b| CLASS_SEEDS[alphabet.code(b as u8).unwrap_or(0) as usize]);
+        Self { alphabet, seeds }
+    }
+
+    /// Hashes nucleotides, as [`Alphabet::dna`].
+    pub fn dna() -> Self {
+        Self::new(Alphabet::dna())
+    }
+
+    /// Returns the alphabet.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+}
+
+impl KmerHasher for LaneBatchedHasher {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+        lane_hashes(&self.seeds, seq, k, out);
+        Ok(())
+    }
+
+    fn hash_both_strands(&self, seq: &[u8], k: usize) -> Result<(Vec<u64>, Vec<u64>)> {
+        let rev = self.alphabet.reverse_complement(seq)?;
+        Ok((self.hash_all(seq, k)?, self.hash_all(&rev, k)?))
+    }
+
+    fn validity(&self, seq: &[u8], k: usize) -> Option<Vec<bool>> {
+        class_validity(seq, k, |b| self.alphabet.code(b))
+    }
+
+    fn validate_sequence(&self, seq: &[u8]) -> Result<()> {
+        self.alphabet.validate(seq)
+    }
+}
+
+/// Writes the rolling hashes of every k-mer of `seq` into `out`.
+///
+/// Lane `j` hashes k-mers `j·m .. (j + 1)·m` with `m = n / LANES`; the last
+/// lane's state then rolls on over the `n mod LANES` remaining k-mers.
+fn lane_hashes(seeds: &[u64; 256], seq: &[u8], k: usize, out: &mut Vec<u64>) {
+    let n = seq.len() - k + 1;
+    let leaving = seeds.map(|s| s.rotate_left(k as u32));
+    let init = |start: usize| {
+        seq[start..start + k].iter().fold(0u64, |h, &b| h.rotate_left(1) ^ seeds[b as usize])
+    };
+    let roll = |h: u64, i: usize| {
+        h.rotate_left(1) ^ leaving[seq[i - 1] as usize] ^ seeds[seq[i + k - 1] as usize]
+    };
+
+    out.clear();
+    out.resize(n, 0);
+    let m = n / LANES;
+    let mut h = [0u64; LANES];
+    if m > 0 {
+        for (j, state) in h.iter_mut().enumerate() {
+            *state = init(j * m);
+            out[j * m] = mix(*state);
+        }
+        for t in 1..m {
+            for (j, state) in h.iter_mut().enumerate() {
+                *state = roll(*state, j * m + t);
+                out[j * m + t] = mix(*state);
+            }
+        }
+    }
+
+    let (mut last, start) = match m {
+        0 => {
+            out[0] = mix(init(0));
+            (init(0), 1)
+        }
+        _ => (h[LANES - 1], LANES * m),
+    };
+    for (i, slot) in out.iter_mut().enumerate().skip(start) {
+        last = roll(last, i);
+        *slot = mix(last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlphabetHasher;
+
+    #[test]
+    fn matches_scalar_hasher() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCATTGCAGGCTAAGCTTGCAnacgu";
+        let (batched, scalar) = (LaneBatchedHasher::dna(), AlphabetHasher::new(Alphabet::dna()));
+        for len in [1, 3, 4, 5, 8, 9, 17, seq.len()] {
+            for k in [1, 2, 4, 15] {
+                let seq = &seq[..len];
+                assert_eq!(batched.hash_all(seq, k), scalar.hash_all(seq, k), "len={len} k={k}");
+            }
+        }
+        assert_eq!(batched.validity(b"ACNGT", 2), scalar.validity(b"ACNGT", 2));
+        assert_eq!(batched.validate_sequence(b"ACNGT"), Err(StrobeError::InvalidSymbol(2)));
+    }
+}
@@ -0,0 +1,180 @@
+use std::{fmt, iter::FusedIterator};
+
+use crate::{MinStrobes, RandStrobes, Result, StrobeError, hashes::KmerHasher};
+
+/// Strobemer iterator whose hashing is deferred until it is first used.
+///
+/// Built by [`RandStrobes::lazy`] and [`MinStrobes::lazy`], which only check
+/// the parameters against the sequence length. The sequence is validated and
+/// hashed by [`prepare`](Self::prepare) or the first [`next`](Iterator::next),
+/// so records a pipeline drops before iterating cost no hashing. Seeds are
+/// those of the eager `with_hasher` constructor.
+///
+/// An error of the deferred construction is returned by `prepare` (and
+/// again by later calls); `next` then yields nothing.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{NtHash64, RandStrobes};
+/// let reads = [&b"ACGATCTGGTACCTAG"[..], b"GGATTACACGATCGGATC"];
+/// let mut lazy: Vec<_> =
+///     reads.iter().map(|r| RandStrobes::lazy(r, 2, 3, 3, 5, &NtHash64).unwrap()).collect();
+/// // Only the kept read is hashed
+/// lazy.truncate(1);
+/// let seeds: Vec<u64> = lazy.pop().unwrap().collect();
+/// assert_eq!(seeds, RandStrobes::new(reads[0], 2, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+/// ```
+pub struct LazyStrobes<'a, I> {
+    build: Option<Box<dyn FnOnce() -> Result<I> + Send + 'a>>, // Deferred construction
+    iter: Option<I>,                                             // Built iterator
+    error: Option<StrobeError>,                                  // Construction error
+}
+
+impl<'a, I> LazyStrobes<'a, I> {
+    fn new(build: impl FnOnce() -> Result<I> + Send + 'a) -> Self {
+        Self { build: Some(Box::new(build)), iter: None, error: None }
+    }
+
+    /// Hashes the sequence if not done yet and returns the built iterator,
+    /// e.g. to change its settings before iterating.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&mut I)` – The iterator, in the state iteration has left it.
+    /// * `Err(StrobeError)` – The error of the deferred constructor.
+    pub fn prepare(&mut self) -> Result<&mut I> {
+        if let Some(build) = self.build.take() {
+            match build() {
+                Ok(iter) => self.iter = Some(iter),
+                Err(e) => self.error = Some(e),
+            }
+        }
+        match (&mut self.iter, &self.error) {
+            (Some(iter), _) => Ok(iter),
+            (None, Some(e)) => Err(e.clone()),
+            (None, None) => unreachable!("construction ran without a result"),
+        }
+    }
+
+    /// Returns whether the sequence has been hashed (or failed to).
+    pub fn is_prepared(&self) -> bool {
+        self.build.is_none()
+    }
+
+    /// Hashes the sequence if not done yet and returns the built iterator.
+    pub fn into_inner(mut self) -> Result<I> {
+        self.prepare()?;
+        Ok(self.iter.take().expect("prepared iterator"))
+    }
+}
+
+impl<I: Iterator> Iterator for LazyStrobes<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.prepare().ok()?.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (&self.iter, self.is_prepared()) {
+            (Some(iter), _) => iter.size_hint(),
+            (None, true) => (0, Some(0)),
+            (None, false) => (0, None),
+        }
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for LazyStrobes<'_, I> {}
+
+impl<I: fmt::Debug> fmt::Debug for LazyStrobes<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyStrobes")
+            .field("prepared", &self.is_prepared())
+            .field("iter", &self.iter)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl RandStrobes {
+    /// Constructs a [`RandStrobes`] iterator that hashes `seq` on first use.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LazyStrobes)` – If the parameters suit the sequence length.
+    /// * `Err(StrobeError)` – If `seq` is empty or too short, or the
+    ///   parameters are invalid. Other errors of [`RandStrobes::with_hasher`]
+    ///   are deferred; see [`LazyStrobes`].
+    pub fn lazy<'a, H>(
+        seq: &'a [u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &'a H,
+    ) -> Result<LazyStrobes<'a, Self>>
+    where
+        H: KmerHasher + Sync + ?Sized,
+    {
+        if seq.is_empty() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        validate_params!(@len seq.len(), n, k, w_min, w_max);
+        Ok(LazyStrobes::new(move || Self::with_hasher(seq, n, k, w_min, w_max, hasher)))
+    }
+}
+
+impl MinStrobes {
+    /// Constructs a [`MinStrobes`] iterator that hashes `seq` on first use.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LazyStrobes)` – If the parameters suit the sequence length.
+    /// * `Err(StrobeError)` – If `seq` is empty or too short, or the
+    ///   parameters are invalid. Other errors of [`MinStrobes::with_hasher`]
+    ///   are deferred; see [`LazyStrobes`].
+    pub fn lazy<'a, H>(
+        seq: &'a [u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &'a H,
+    ) -> Result<LazyStrobes<'a, Self>>
+    where
+        H: KmerHasher + Sync + ?Sized,
+    {
+        if seq.is_empty() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        validate_params!(@len seq.len(), n, k, w_min, w_max);
+        Ok(LazyStrobes::new(move || Self::with_hasher(seq, n, k, w_min, w_max, hasher)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KmerCodeHasher, NtHash64};
+
+    #[test]
+    fn defers_hashing_and_its_errors() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGG";
+        let mut lazy = MinStrobes::lazy(seq, 3, 3, 2, 5, &NtHash64).unwrap();
+        assert!(!lazy.is_prepared());
+        lazy.prepare().unwrap().set_stride(2).unwrap();
+        let mut eager = MinStrobes::new(seq, 3, 3, 2, 5).unwrap();
+        eager.set_stride(2).unwrap();
+        assert!(lazy.eq(eager));
+
+        let bad = b"ACGATCTGG-ACCTAGGATTACACGATCGG";
+        let mut lazy = RandStrobes::lazy(bad, 2, 3, 2, 5, &KmerCodeHasher).unwrap();
+        let err = RandStrobes::with_hasher(bad, 2, 3, 2, 5, &KmerCodeHasher).err();
+        assert_eq!(lazy.prepare().err(), err);
+        assert_eq!(lazy.next(), None);
+        assert_eq!(lazy.prepare().err(), err);
+
+        let short = RandStrobes::lazy(&seq[..5], 2, 3, 2, 5, &NtHash64).err();
+        assert_eq!(short, Some(StrobeError::SequenceTooShort));
+    }
+}
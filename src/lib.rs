@@ -1,15 +1,113 @@
+mod alphabet;
+mod ambiguity;
+mod batch;
+mod bdanchors;
+mod combine;
 mod constants;
+mod containment;
 #[macro_use]
 mod util;
+mod dualstrand;
+mod explain;
+mod extsort;
+#[cfg(feature = "fasta")]
+pub mod fasta;
+#[cfg(feature = "fastq")]
+pub mod fastq;
+mod fxhash;
+mod gcdensity;
+mod generator;
 mod hashes;
+mod identity;
+mod informative;
+mod intervals;
+mod lanes;
+mod lazy;
 mod minstrobes;
+mod mintable;
+mod modminimizers;
+mod paired;
+mod pangenome;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod position;
+mod precomputed;
+mod protein;
 mod randstrobes;
+mod repeats;
+mod ringstrobes;
+#[cfg(feature = "reference")]
+pub mod reference;
+mod scratch;
+mod selection;
+mod selfcheck;
+mod softmask;
+mod spaced;
+mod spec;
+mod strand;
+mod stream;
+mod streaming;
+mod strobemer;
+mod variants;
+#[cfg(feature = "wyhash")]
+mod wyhash;
+#[cfg(feature = "xxhash")]
+mod xxhash;
 
+pub use alphabet::{Alphabet, AlphabetHasher};
+pub use ambiguity::AmbiguityPolicy;
+pub use batch::MultiSeqStrobes;
+pub use bdanchors::BdAnchors;
+pub use combine::{
+    CombineScheme, LegacyCombiner, PackedCombiner, SeededCombiner, StrobeCombiner,
+    SymmetricCombiner,
+};
 pub use constants::*;
-pub use hashes::{KmerHasher, compute_min_hashes};
+pub use containment::{Containment, assembly_containment};
+pub use dualstrand::DualStrand;
+pub use explain::{SeedExplanation, SeedVerdict, explain};
+pub use extsort::{ExternalSeedSorter, SeedRecord, SortedSeeds};
+pub use fxhash::FxHashHasher;
+pub use generator::StrobeGenerator;
+pub use hashes::{
+    KmerCodeHasher, KmerHasher, NtHash64, NtHashCanonical, SeededHasher, StdHasherKmer,
+    compute_min_hashes, compute_min_hashes_with, decode_kmer,
+};
+pub use identity::identity_filter;
+pub use informative::{AbundanceSource, top_informative};
+pub use lanes::LaneBatchedHasher;
+pub use lazy::LazyStrobes;
 pub use minstrobes::MinStrobes;
+pub use mintable::MinTable;
+pub use modminimizers::ModMinimizers;
+pub use paired::{Mate, PairAnchor, PairOrientation, PairSeeds, PairedSeed, PairedStrobes};
+pub use pangenome::{AnnotatedSeed, annotate_genome_counts};
+#[cfg(feature = "rayon")]
+pub use parallel::par_for_each_sequence;
+pub use position::Position;
+pub use precomputed::PrecomputedHashes;
+pub use protein::{ProteinHasher, ReducedAlphabet};
 pub use randstrobes::RandStrobes;
+pub use repeats::{RepeatRegion, detect_tandem_repeats};
+pub use ringstrobes::StreamingRandStrobes;
+pub use scratch::Scratch;
+pub use selection::{
+    MaskedSum, MinAbsDiff, MinXor, PerformanceMode, SelectionStrategy, TieBreak, WindowAnchor,
+};
+pub use selfcheck::self_check;
+pub use softmask::SoftMask;
+pub use spaced::{SpacedSeed, parse_mask};
+pub use spec::{Combine, Protocol, Selection, StrobeSpec};
+pub use strand::Strand;
+pub use stream::ReaderStrobes;
+pub use streaming::{KmerCarry, KmerCodeState, StreamingHashes, StreamingKmerHasher};
+pub use strobemer::{SeedColumns, Strobemer, Strobemer128};
 pub use util::*;
+pub use variants::{CandidateRegion, screen_variants};
+#[cfg(feature = "wyhash")]
+pub use wyhash::WyHashHasher;
+#[cfg(feature = "xxhash")]
+pub use xxhash::XxHash64Hasher;
 
 use nthash_rs::NtHashError;
 
@@ -40,12 +138,16 @@ pub enum StrobeError {
     #[error("sequence too short for given parameters")]
     SequenceTooShort,
 
+    /// Thrown when a sequence is too long for the requested position type.
+    #[error("sequence too long for the position type")]
+    SequenceTooLong,
+
     /// Thrown when the strobe (k-mer) length `l` is less than 1 or greater than 64.
     #[error("strobe length (l) must be ≥ 1 and ≤ 64")]
     StrobeLengthTooSmall,
 
-    /// Thrown when window offsets are invalid (must be > 0 and `w_min ≤ w_max`).
-    #[error("window offsets must be > 0 and w_min ≤ w_max")]
+    /// Thrown when window offsets are invalid (`w_max` must be > 0 and `w_min ≤ w_max`).
+    #[error("window offsets must satisfy w_min ≤ w_max and w_max > 0")]
     InvalidWindowOffsets,
 
     /// Indicates that the precomputed k-mer hash values (via `nthash-rs`) were incomplete.
@@ -53,6 +155,80 @@ pub enum StrobeError {
     #[error("incomplete pre-computed hash values (nthash)")]
     IncompleteHashValues,
 
+    /// Thrown when a window is too short to contain a single k-mer.
+    #[error("window must contain at least one k-mer")]
+    InvalidWindowLength,
+
+    /// Thrown when a spaced-seed mask is malformed (must be 1–64 ASCII `1`/`0`
+    /// starting and ending with `1`) or masks do not match the strobemer order.
+    #[error("invalid spaced-seed mask")]
+    InvalidSpacedMask,
+
+    /// Thrown when a density fraction is outside `(0, 1]`.
+    #[error("density must be in (0, 1]")]
+    InvalidDensity,
+
+    /// Thrown when a histogram or bin count is zero.
+    #[error("bin count must be ≥ 1")]
+    InvalidBinCount,
+
+    /// Thrown when the number of hashes per k-mer is zero or exceeds the hasher's limit.
+    #[error("hash count must be ≥ 1 and within the hasher's limit")]
+    InvalidHashCount,
+
+    /// Thrown when the strobes of a seed do not fit a [`PackedCombiner`] word (`2·n·k > 64`).
+    #[error("packed strobes must fit in 64 bits (2·n·k ≤ 64)")]
+    PackedSeedTooWide,
+
+    /// Thrown when a maximum seed span leaves no room for the last strobe.
+    #[error("maximum span too small for the strobe length and window offsets")]
+    MaxSpanTooSmall,
+
+    /// Thrown when a minimum seed span exceeds what the windows or the maximum span allow.
+    #[error("minimum span too large for the window offsets or maximum span")]
+    MinSpanTooLarge,
+
+    /// Thrown when a seed stride is zero.
+    #[error("stride must be ≥ 1")]
+    InvalidStride,
+
+    /// Thrown under [`AmbiguityPolicy::Error`] for a base other than A/C/G/T/U;
+    /// holds its position.
+    #[error("ambiguous base at position {0}")]
+    AmbiguousBase(usize),
+
+    /// Thrown when a sequence region or masked interval is reversed, or a region
+    /// extends past the sequence end.
+    #[error("region out of bounds")]
+    InvalidRegion,
+
+    /// Thrown when a [`StrobeSpec`] string is malformed; holds the offending field.
+    #[error("invalid strobe spec: {0}")]
+    InvalidSpec(String),
+
+    /// Thrown when a file or reader fails; holds the I/O error message.
+    #[error("I/O error: {0}")]
+    Io(String),
+
+    /// Thrown when a sequence file is malformed; holds the 1-based line number.
+    #[error("malformed record at line {0}")]
+    MalformedRecord(usize),
+
+    /// Thrown when an [`Alphabet`] or [`ReducedAlphabet`] definition is empty,
+    /// malformed or overlapping, or a reverse complement is requested from an
+    /// alphabet without complement.
+    #[error("invalid alphabet")]
+    InvalidAlphabet,
+
+    /// Thrown when a sequence contains a byte outside the hasher's [`Alphabet`];
+    /// holds its position.
+    #[error("symbol outside the alphabet at position {0}")]
+    InvalidSymbol(usize),
+
+    /// Thrown by [`self_check`] when a known-answer test fails on the running target.
+    #[error("self-check failed: {0}")]
+    SelfCheckFailed(&'static str),
+
     /// Thrown when the provided prime number is too small (minimum allowed is 256).
     #[error("prime number too small (must be ≥ 256)")]
     PrimeNumberTooSmall,
@@ -1,7 +1,22 @@
+use std::{collections::HashMap, io::Read, iter::FusedIterator, ops::Range, sync::Arc};
+
 use crate::{
     Result, StrobeError,
+    ambiguity::{AmbiguityPolicy, hasher_validity, quality_validity},
+    combine::{CombineScheme, Combiner, StrobeCombiner, SymmetricCombiner},
     constants::DEFAULT_PRIME_NUMBER,
-    hashes::{KmerHasher, NtHash64, compute_min_hashes},
+    gcdensity::GcDensity,
+    hashes::{KmerHasher, NtHash64},
+    intervals::kmers_overlapping,
+    informative::{AbundanceSource, top_informative},
+    mintable::MinTable,
+    position::Position,
+    scratch::Scratch,
+    selection::{TieBreak, WindowAnchor, lane_min, secondary_key},
+    softmask::{SoftMask, soft_masked_kmers},
+    spec::Protocol,
+    stream::ReaderStrobes,
+    strobemer::{SeedColumns, Strobemer, Strobemer128, strobe_bytes},
     util::roundup64,
 };
 
@@ -12,34 +27,56 @@ use crate::{
 /// window minima to efficiently produce strobemer hash values.
 ///
 #[derive(Debug, Clone)]
-pub struct MinStrobes {
+pub struct MinStrobes<P: Position = usize> {
     // Parameters controlling strobemer generation
-    n: u8,        // Order of strobemer: 2 or 3
+    n: u8,        // Order of strobemer: 2 or 3 (1 for the k-mer fallback)
+    k: usize,     // k-mer length
     w_min: usize, // Minimum window offset
     w_max: usize, // Maximum window offset
 
     // Precomputed data
-    hashes: Vec<u64>,   // Hash values for each k-mer in the sequence
-    minloc: Vec<usize>, // Location of the minimum hash within each sliding window
-    minval: Vec<u64>,   // Minimum hash value within each sliding window
+    table: Arc<MinTable<P>>, // k-mer hashes and sliding-window minima, possibly shared
 
     // Iteration state
     idx: usize,      // Current index of the first k-mer (m1)
-    end_idx: usize,  // Last index at which a complete strobemer can start
     end_hash: usize, // Last index in `hashes` (i.e., sequence length minus k)
+    back: usize,     // One past the last m1 still available (consumed from the back)
+    from_back: bool, // Whether the current item came from `next_back`
 
     // Strobe indices for current item
     idx2: usize, // Index of second k-mer (m2)
     idx3: usize, // Index of third k-mer (m3) if order = 3
+    offset: usize, // Input position of the first hashed base (see `new_in_region`)
 
     // Prime number and shrink-window flag
-    prime: u64,   // Used for combining hash values in order 3
-    shrink: bool, // Whether to shrink windows near sequence end
+    prime: u64,            // Used for combining hash values in order 3
+    shrink: bool,          // Whether to shrink windows near sequence end
+    consistent: bool,      // Whether shrunk order-3 windows select m3 by plain minimum too
+    tie: Option<TieBreak>, // Tie rule for all windows; `None` keeps the legacy mix of rules
+    max_span: usize,       // Cap on the distance from m1's start to the last strobe's end
+    min_span: usize,       // Floor on that distance, raising the start of the last window
+    anchor: WindowAnchor,  // Whether m3's window is placed relative to m1 or to m2
 
     // Working registers for hash values
     h1: u64, // Hash of first k-mer (m1)
     h2: u64, // Combined hash after selecting m2
     h3: u64, // Combined hash after selecting m3 (order 3 only)
+
+    // Replaces the built-in combination of the emitted hash (see `set_combiner`)
+    combiner: Option<Combiner>,
+    scheme: CombineScheme, // Built-in combination used without a combiner
+
+    // Output thinning
+    first_mask: Option<Vec<bool>>, // Allowed m1 positions (see `set_first_strobe_positions`)
+    stride: usize,                 // Distance between consecutive m1 positions (see `set_stride`)
+    soft_mask: Option<Vec<bool>>,  // Entirely lowercase k-mers (see `set_soft_mask`)
+    soft_any: bool,                // Whether any soft-masked strobe drops the seed
+    excluded: Option<Vec<bool>>,   // k-mers intersecting masked intervals
+    threshold: u64,                // Only hashes ≤ threshold are emitted (density thinning)
+    gc_density: Option<GcDensity>, // Per-GC-bin thresholds replacing `threshold`
+
+    // Occurrences of each k-mer hash within the sequence (see `set_local_counts`)
+    local_counts: Option<HashMap<u64, u32>>,
 }
 
 impl MinStrobes {
@@ -71,6 +108,77 @@ impl MinStrobes {
         Self::with_hasher(seq, n, k, w_min, w_max, &NtHash64)
     }
 
+    /// Seeds a sequence read incrementally from `reader`.
+    ///
+    /// The sequence is consumed in chunks, so neither it nor its full hash
+    /// vector has to fit in memory; seeds and positions match [`MinStrobes::new`]
+    /// on the whole sequence. See [`ReaderStrobes`] for the input format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReaderStrobes)` – Iterator of `Result<Strobemer>`; read errors are
+    ///   yielded as [`StrobeError::Io`].
+    /// * `Err(StrobeError)` – If `n`, `k` or the window offsets are invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let seq = "ACGATCTGGT\nACCTAGGATT\nACACG\n";
+    /// let seeds: Vec<_> = MinStrobes::from_reader(seq.as_bytes(), 2, 3, 3, 5)
+    ///     .unwrap()
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// let whole = MinStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 3, 5).unwrap();
+    /// assert_eq!(seeds, whole.iter_seeds().collect::<Vec<_>>());
+    /// ```
+    pub fn from_reader<R: Read>(
+        reader: R,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<ReaderStrobes<R>> {
+        ReaderStrobes::new(reader, Protocol::Min, n, k, w_min, w_max)
+    }
+
+    /// Constructs a [`MinStrobes`] iterator over `seq[region]` that reports
+    /// positions in `seq`.
+    ///
+    /// Seeds are those of [`MinStrobes::new`] on the region alone: windows do
+    /// not reach past its end. All positions the iterator reports or accepts
+    /// ([`index`](Self::index), [`indexes`](Self::indexes), `seek`,
+    /// `set_first_strobe_positions`, `seed_bytes`) are coordinates in `seq`.
+    /// `set_gc_density` takes the region's bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator over the region.
+    /// * `Err(StrobeError::InvalidRegion)` – If `region` is reversed or exceeds `seq`.
+    /// * `Err(StrobeError)` – As for [`MinStrobes::new`] on the region.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let mut ms = MinStrobes::new_in_region(seq, 5..20, 2, 3, 3, 5).unwrap();
+    /// ms.next().unwrap();
+    /// assert_eq!(ms.index(), Some(5));
+    /// ```
+    pub fn new_in_region(
+        seq: &[u8],
+        region: Range<usize>,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        let start = region.start;
+        let sub = seq.get(region).ok_or(StrobeError::InvalidRegion)?;
+        let mut ms = Self::new(sub, n, k, w_min, w_max)?;
+        ms.offset = start;
+        Ok(ms)
+    }
+
     /// Constructs a new [`MinStrobes`] iterator with a user-defined hash function.
     ///
     /// This method accepts any implementation of the [`KmerHasher`] trait,
@@ -118,40 +226,404 @@ impl MinStrobes {
         hasher: &H,
     ) -> Result<Self>
     where
-        H: KmerHasher,
+        H: KmerHasher + ?Sized,
+    {
+        Self::with_scratch(seq, n, k, w_min, w_max, hasher, &mut Scratch::new())
+    }
+
+    /// Constructs a new [`MinStrobes`] iterator with a hasher chosen at runtime.
+    ///
+    /// Same as [`MinStrobes::with_hasher`], for a hasher behind a trait object, e.g. one
+    /// picked from a command-line flag.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerCodeHasher, KmerHasher, MinStrobes, NtHash64};
+    /// let name = "kmer-code";
+    /// let hasher: Box<dyn KmerHasher> = match name {
+    ///     "kmer-code" => Box::new(KmerCodeHasher),
+    ///     _ => Box::new(NtHash64),
+    /// };
+    /// let seeds = MinStrobes::with_dyn_hasher(b"ACGATCTGGTACCTAG", 2, 3, 3, 5, &*hasher)?;
+    /// assert!(seeds.count() > 0);
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn with_dyn_hasher(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &dyn KmerHasher,
+    ) -> Result<Self> {
+        Self::with_hasher(seq, n, k, w_min, w_max, hasher)
+    }
+
+    /// Constructs a new [`MinStrobes`] iterator over caller-supplied k-mer hashes.
+    ///
+    /// `hashes[i]` is the hash of the k-mer starting at `i`, e.g. from the
+    /// caller's own hashing pass or loaded from disk, so the sequence of
+    /// `hashes.len() + k − 1` bases is not hashed again. Seeds equal those of
+    /// [`MinStrobes::with_hasher`] with a hasher producing these hashes. The hashes
+    /// are copied.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `hashes` is empty.
+    /// * `Err(StrobeError)` – On invalid parameters or too few hashes for them.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerHasher, NtHash64, MinStrobes};
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let hashes = NtHash64.hash_all(seq, 3)?;
+    /// let seeds: Vec<u64> = MinStrobes::from_hashes(&hashes, 2, 3, 3, 5)?.collect();
+    /// assert_eq!(seeds, MinStrobes::new(seq, 2, 3, 3, 5)?.collect::<Vec<u64>>());
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn from_hashes(
+        hashes: &[u64],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        if hashes.is_empty() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        let seq_len = hashes.len() + k.saturating_sub(1);
+        validate_params!(@len seq_len, n, k, w_min, w_max);
+        let scratch = &mut Scratch::new();
+        let table = MinTable::from_hashes(seq_len, k, w_min, w_max, hashes.to_vec(), None, scratch);
+        Self::from_table(Arc::new(table), n)
+    }
+
+    /// Constructs a new [`MinStrobes`] iterator, taking its hash and window-minima buffers
+    /// from `scratch`.
+    ///
+    /// Behaves like [`MinStrobes::with_hasher`]; pass the buffers back with
+    /// [`recycle`](Self::recycle) to reuse them for the next sequence.
+    pub fn with_scratch<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+        scratch: &mut Scratch,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
     {
         // Check all preconditions
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+
+        // Compute k-mer hashes and sliding-window minima
+        let table = MinTable::with_scratch(seq, k, w_min, w_max, hasher, scratch)?;
+        Self::from_table(Arc::new(table), n)
+    }
+
+    /// Constructs a [`MinStrobes`] iterator handling ambiguous bases (e.g. `N`)
+    /// according to `policy`.
+    ///
+    /// With [`AmbiguityPolicy::Hasher`] this is [`MinStrobes::with_hasher`].
+    /// Under [`AmbiguityPolicy::SkipAffectedSeeds`] no seed starts at, or picks
+    /// as a later strobe, a k-mer overlapping an ambiguous base; such k-mers are
+    /// hashed as if the base were `A` so every position keeps a hash.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::AmbiguousBase)` – Under [`AmbiguityPolicy::Error`], for
+    ///   the first ambiguous base.
+    /// * `Err(StrobeError)` – As for [`MinStrobes::with_hasher`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{AmbiguityPolicy, MinStrobes, NtHash64};
+    /// let seq = b"ACGATCTGGTNCCTAGGATTACACG";
+    /// let policy = AmbiguityPolicy::SkipAffectedSeeds;
+    /// let mut it =
+    ///     MinStrobes::with_ambiguity_policy(seq, 2, 3, 3, 5, &NtHash64, policy).unwrap();
+    /// while it.next().is_some() {
+    ///     let [m1, m2, _] = it.indexes();
+    ///     assert!(!seq[m1..m1 + 3].contains(&b'N') && !seq[m2..m2 + 3].contains(&b'N'));
+    /// }
+    /// ```
+    pub fn with_ambiguity_policy<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+        policy: AmbiguityPolicy,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        validate_params!(seq, n, k, w_min, w_max);
+
+        let scratch = &mut Scratch::new();
+        let table = MinTable::build(seq, k, w_min, w_max, hasher, scratch, policy, None)?;
+        Self::from_table(Arc::new(table), n)
+    }
+
+    /// Constructs a [`MinStrobes`] iterator that skips low-quality bases.
+    ///
+    /// `qual` holds one Phred+33 quality character per base of `seq`, as in a
+    /// FASTQ record. K-mers containing a base below `min_qual` are treated
+    /// like ambiguous k-mers under [`AmbiguityPolicy::SkipAffectedSeeds`]: no
+    /// seed starts at them and window selection avoids them. The caller's
+    /// buffers are left untouched.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `qual` and `seq` differ in length.
+    /// * `Err(StrobeError)` – As for [`MinStrobes::new`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let qual = b"IIIIIIIIII#IIIIIIIIIIIIII";
+    /// let mut it = MinStrobes::with_quality_mask(seq, qual, 20, 2, 3, 3, 5).unwrap();
+    /// while it.next().is_some() {
+    ///     let [m1, m2, _] = it.indexes();
+    ///     assert!(!(m1..m1 + 3).contains(&10) && !(m2..m2 + 3).contains(&10));
+    /// }
+    /// ```
+    pub fn with_quality_mask(
+        seq: &[u8],
+        qual: &[u8],
+        min_qual: u8,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
         validate_params!(seq, n, k, w_min, w_max);
+        if qual.len() != seq.len() {
+            return Err(StrobeError::InvalidSequence);
+        }
 
-        // Compute k-mer hash values via user-supplied hasher
-        let hashes = hasher.hash_all(seq, k)?;
+        let valid = Some(quality_validity(qual, k, min_qual));
+        let scratch = &mut Scratch::new();
+        let policy = AmbiguityPolicy::Hasher;
+        let table = MinTable::build(seq, k, w_min, w_max, &NtHash64, scratch, policy, valid)?;
+        Self::from_table(Arc::new(table), n)
+    }
 
-        // Precompute min-hash locations and values within each sliding window
-        let (minloc, minval) = compute_min_hashes(&hashes, w_max - w_min + 1);
+    /// Constructs a [`MinStrobes`] iterator that falls back to plain k-mers on
+    /// sequences too short for order `n`.
+    ///
+    /// Where [`MinStrobes::with_hasher`] fails with
+    /// [`StrobeError::SequenceTooShort`], the iterator instead yields one seed
+    /// per k-mer: its hash is the k-mer hash and its
+    /// [`order`](crate::Strobemer::order) is 1. Short reads thus still get
+    /// seeds, at k-mer rather than strobemer sensitivity. K-mers the hasher
+    /// marks invalid are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::SequenceTooShort)` – If the sequence is shorter than `k`.
+    /// * `Err(StrobeError)` – Any other error of [`MinStrobes::with_hasher`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{MinStrobes, NtHash64};
+    /// let seq = b"ACGATCTG";
+    /// assert!(MinStrobes::new(seq, 3, 3, 1, 2).is_err());
+    /// let seeds: Vec<_> =
+    ///     MinStrobes::with_kmer_fallback(seq, 3, 3, 1, 2, &NtHash64)?.iter_seeds().collect();
+    /// assert_eq!(seeds.len(), seq.len() - 3 + 1);
+    /// assert!(seeds.iter().all(|s| s.order == 1 && s.span == 3));
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn with_kmer_fallback<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        match Self::with_hasher(seq, n, k, w_min, w_max, hasher) {
+            Err(StrobeError::SequenceTooShort) => {}
+            built => return built,
+        }
+        let mut hashes = hasher.hash_all(seq, k)?;
+        let valid = hasher_validity(hasher, seq, k, hashes.len())?;
+        if let Some(valid) = &valid {
+            for (h, _) in hashes.iter_mut().zip(valid).filter(|&(_, &ok)| !ok) {
+                *h = u64::MAX;
+            }
+        }
+        let table = MinTable {
+            seq_len: seq.len(),
+            k,
+            w_min,
+            w_max,
+            hashes,
+            minloc: Vec::new(),
+            minval: Vec::new(),
+            valid,
+        };
+        Ok(Self::assemble(Arc::new(table), 1))
+    }
 
+    /// Constructs a [`MinStrobes`] iterator whose table stores window-minimum
+    /// locations as `u32`, halving their memory; see [`Position`].
+    ///
+    /// Seeds and positions are those of [`MinStrobes::with_hasher`], and
+    /// [`collect_soa`](Self::collect_soa) cannot fail.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes<u32>)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::SequenceTooLong)` – If `seq` is longer than `u32::MAX` bases.
+    /// * `Err(StrobeError)` – Any other error of [`MinStrobes::with_hasher`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{MinStrobes, NtHash64};
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let compact = MinStrobes::with_u32_positions(seq, 3, 3, 3, 5, &NtHash64).unwrap();
+    /// let seeds: Vec<_> = compact.iter_seeds().collect();
+    /// let expected: Vec<_> = MinStrobes::new(seq, 3, 3, 3, 5).unwrap().iter_seeds().collect();
+    /// assert_eq!(seeds, expected);
+    /// ```
+    pub fn with_u32_positions<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<MinStrobes<u32>>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+        let table = MinTable::with_u32_positions(seq, k, w_min, w_max, hasher)?;
+        MinStrobes::from_table(Arc::new(table), n)
+    }
+}
+
+impl<P: Position> MinStrobes<P> {
+    /// Constructs a new [`MinStrobes`] iterator of order `n` from a precomputed table.
+    ///
+    /// The table is shared, not copied, so iterators of both orders (or
+    /// several iterators with different settings) can be built from one
+    /// hashing and window-minima pass; see [`MinTable`] for an example.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator over the table's sequence.
+    /// * `Err(StrobeError::OrderNotSupported)` – If `n` is not 2 or 3.
+    /// * `Err(StrobeError::SequenceTooShort)` – If the sequence is too short for order `n`.
+    pub fn from_table(table: Arc<MinTable<P>>, n: u8) -> Result<Self> {
+        if !matches!(n, 2 | 3) {
+            return Err(StrobeError::OrderNotSupported);
+        }
+        if table.seq_len < (n as usize - 1) * (table.w_max + 1)
+            || table.seq_len < n as usize * table.k
+        {
+            return Err(StrobeError::SequenceTooShort);
+        }
+        Ok(Self::assemble(table, n))
+    }
+
+    /// Builds the iterator of order `n` over a validated table; order 1
+    /// yields plain k-mers (see [`MinStrobes::with_kmer_fallback`]).
+    fn assemble(table: Arc<MinTable<P>>, n: u8) -> Self {
         // Define range bounds for m1 (starting point of each strobemer)
-        let seq_len = seq.len();
+        let (seq_len, k) = (table.seq_len, table.k);
         let end_hash = seq_len - k;
         let end_idx = seq_len - k - (n as usize - 1) * k;
 
-        Ok(Self {
+        Self {
             n,
-            w_min,
-            w_max,
-            hashes,
-            minloc,
-            minval,
+            k,
+            w_min: table.w_min,
+            w_max: table.w_max,
+            table,
             idx: 0,
             end_hash,
-            end_idx,
+            back: end_idx + 1,
+            from_back: false,
             idx2: 0,
             idx3: 0,
+            offset: 0,
             prime: DEFAULT_PRIME_NUMBER,
             shrink: true,
+            consistent: false,
+            tie: None,
+            max_span: usize::MAX,
+            min_span: 0,
+            anchor: WindowAnchor::FromFirstStrobe,
             h1: 0,
             h2: 0,
             h3: 0,
-        })
+            combiner: None,
+            scheme: CombineScheme::default(),
+            first_mask: None,
+            stride: 1,
+            soft_mask: None,
+            soft_any: false,
+            excluded: None,
+            threshold: u64::MAX,
+            gc_density: None,
+            local_counts: None,
+        }
+    }
+
+    /// Returns the hash and window-minima buffers to `scratch` for reuse by the next
+    /// construction.
+    ///
+    /// Does nothing if the table is still shared with another iterator.
+    pub fn recycle(self, scratch: &mut Scratch) {
+        if let Ok(table) = Arc::try_unwrap(self.table) {
+            table.recycle(scratch);
+        }
+    }
+
+    /// Returns the precomputed table, e.g. to build an iterator of the other order.
+    pub fn table(&self) -> &Arc<MinTable<P>> {
+        &self.table
+    }
+
+    /// Returns the hash of every k-mer, by start position; k-mers with
+    /// ambiguous or invalid bases hold `u64::MAX`.
+    ///
+    /// Shorthand for [`table`](Self::table)`().`[`hashes`](MinTable::hashes)`()`.
+    pub fn hashes(&self) -> &[u64] {
+        &self.table.hashes
+    }
+
+    /// Returns the precomputed sliding-window minima as `(locations, values)`:
+    /// entry `i` describes the window of `w_max − w_min + 1` k-mers ending at
+    /// k-mer `i` (valid for `i ≥ w_max − w_min`). Both are empty for the
+    /// k-mer fallback of [`with_kmer_fallback`](MinStrobes::with_kmer_fallback).
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let ms = MinStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// let (locs, mins) = ms.window_minima();
+    /// for i in 2..locs.len() {
+    ///     assert!((i - 2..=i).contains(&locs[i]));
+    ///     assert_eq!(mins[i], *ms.hashes()[i - 2..=i].iter().min().unwrap());
+    /// }
+    /// ```
+    pub fn window_minima(&self) -> (&[P], &[u64]) {
+        (&self.table.minloc, &self.table.minval)
     }
 
     /// Sets a new prime number for combining hash values in order-3 strobes.
@@ -184,33 +656,766 @@ impl MinStrobes {
         self.shrink = s;
     }
 
+    /// Selects m3 of order-3 seeds by the same criterion in every window.
+    ///
+    /// By default a full third window picks the k-mer with the smallest hash,
+    /// but a window shrunk at the sequence end picks the smallest
+    /// `(h2 + hash) & prime`, as in the original implementation, so seeds
+    /// near the end follow a different rule. When enabled, shrunk windows
+    /// also pick the smallest hash, with ties resolved to the rightmost
+    /// position like full windows. Seeds away from the sequence end are
+    /// unchanged. Disabled by default to keep seeds identical to the
+    /// original implementation.
+    pub fn set_consistent_selection(&mut self, enable: bool) {
+        self.consistent = enable;
+    }
+
+    /// Sets which of several equal minima is selected, in every window.
+    ///
+    /// By default full windows use the precomputed minima, which resolve ties
+    /// to the rightmost position, while windows shrunk at the sequence end
+    /// resolve them to the leftmost (except under
+    /// [`set_consistent_selection`](Self::set_consistent_selection)). Setting
+    /// a [`TieBreak`] applies it to both, recomputing the window minima; the
+    /// table is copied first if it is shared with other iterators.
+    pub fn set_tie_break(&mut self, tie: TieBreak) {
+        Arc::make_mut(&mut self.table).set_tie_break(tie);
+        self.tie = Some(tie);
+    }
+
+    /// Caps the span of every seed, from the start of m1 to the end of the
+    /// last strobe, at `max_span` bases.
+    ///
+    /// The last strobe's search window is cut short where a strobe would end
+    /// past the cap and is then scanned like a window shrunk at the sequence
+    /// end, so seeds keep their anchors but never span more than `max_span`,
+    /// whatever `w_max`. Banded aligners can size their bands from it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If every last window keeps at least one candidate.
+    /// * `Err(StrobeError::MaxSpanTooSmall)` – If `max_span` is below
+    ///   `k + w_min` (order 2) or `k + w_max + w_min` (order 3), or below
+    ///   the [`min_span`](Self::set_min_span) floor.
+    pub fn set_max_span(&mut self, max_span: usize) -> Result<()> {
+        if max_span < self.k + (self.n as usize).saturating_sub(2) * self.w_max + self.w_min
+            || max_span < self.min_span
+        {
+            return Err(StrobeError::MaxSpanTooSmall);
+        }
+        self.max_span = max_span;
+        Ok(())
+    }
+
+    /// Guarantees every seed a span, from the start of m1 to the end of the
+    /// last strobe, of at least `min_span` bases.
+    ///
+    /// The last strobe's search window starts no earlier than where a strobe
+    /// would end at `min_span`; a raised window is scanned like one shrunk at
+    /// the sequence end, and anchors whose raised window no longer fits the
+    /// sequence yield no seed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If every last window keeps at least one candidate.
+    /// * `Err(StrobeError::MinSpanTooLarge)` – If `min_span` exceeds
+    ///   `k + (n − 1)·w_max` or the [`max_span`](Self::set_max_span) cap.
+    pub fn set_min_span(&mut self, min_span: usize) -> Result<()> {
+        let longest = self.k + (self.n as usize - 1) * self.w_max;
+        if min_span > longest.min(self.max_span) {
+            return Err(StrobeError::MinSpanTooLarge);
+        }
+        self.min_span = min_span;
+        Ok(())
+    }
+
+    /// Sets where the search window of m3 is anchored, see [`WindowAnchor`].
+    ///
+    /// Under [`WindowAnchor::FromPreviousStrobe`] the window follows m2, cut
+    /// and raised by the sequence end and the span bounds like the default
+    /// one, and still uses the precomputed minima when it is whole. Anchors
+    /// producing seeds are the same under either rule.
+    pub fn set_window_anchor(&mut self, anchor: WindowAnchor) {
+        self.anchor = anchor;
+    }
+
+    /// Restricts the first strobe (m1) to the given start positions.
+    ///
+    /// Start positions not listed are skipped without doing any selection work,
+    /// which thins the output, e.g. to mod-minimizer positions from
+    /// [`ModMinimizers`](crate::ModMinimizers). Positions beyond the sequence are
+    /// ignored.
+    pub fn set_first_strobe_positions<I>(&mut self, positions: I)
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut mask = vec![false; self.table.hashes.len()];
+        for p in positions {
+            if let Some(m) = p.checked_sub(self.offset).and_then(|p| mask.get_mut(p)) {
+                *m = true;
+            }
+        }
+        self.first_mask = Some(mask);
+    }
+
+    /// Emits seeds only at every `s`-th first-strobe position.
+    ///
+    /// Positions between the grid points are skipped inside `next()` without
+    /// any selection work, so this is the cheapest way to shrink an index by
+    /// roughly a factor `s`. The grid starts at the first anchor (position 0). Combined
+    /// with [`set_first_strobe_positions`](Self::set_first_strobe_positions),
+    /// only listed positions on the grid are kept. `s = 1` (the default)
+    /// seeds every position.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `s ≥ 1`.
+    /// * `Err(StrobeError::InvalidStride)` – If `s` is 0.
+    pub fn set_stride(&mut self, s: usize) -> Result<()> {
+        if s == 0 {
+            return Err(StrobeError::InvalidStride);
+        }
+        self.stride = s;
+        Ok(())
+    }
+
+    /// Drops seeds in soft-masked (lowercase) sequence, see [`SoftMask`].
+    ///
+    /// Seeds whose first strobe is masked are skipped before any selection
+    /// work; with [`SoftMask::SkipAnyStrobe`], seeds are also dropped after
+    /// selection if m2 (or m3) is masked. Strobe selection itself is unchanged,
+    /// so the remaining seeds are identical to those without the option.
+    /// `seq` must be the sequence the iterator hashes (the region's bytes for
+    /// [`new_in_region`](Self::new_in_region)).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `seq` matches the iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `seq` does not match the iterator's length.
+    pub fn set_soft_mask(&mut self, seq: &[u8], mode: SoftMask) -> Result<()> {
+        let masked = soft_masked_kmers(seq, self.k);
+        if masked.len() != self.table.hashes.len() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        self.soft_mask = match mode {
+            SoftMask::Ignore => None,
+            _ => Some(masked),
+        };
+        self.soft_any = mode == SoftMask::SkipAnyStrobe;
+        Ok(())
+    }
+
+    /// Skips every seed with a strobe intersecting one of the masked `intervals`.
+    ///
+    /// Intervals are half-open `(start, end)` ranges of input positions, e.g.
+    /// repeat or low-quality annotations, and may be given in any order. Seeds
+    /// whose first strobe intersects an interval are skipped before any
+    /// selection work; the others are dropped after selection if m2 (or m3)
+    /// intersects one. Strobe selection itself is unchanged, so the remaining
+    /// seeds are identical to those without masking. Replaces intervals set
+    /// before.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – On success.
+    /// * `Err(StrobeError::InvalidRegion)` – If an interval has `start > end`.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let mut it = MinStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 3, 5).unwrap();
+    /// it.set_masked_intervals([(10, 14)]).unwrap();
+    /// while it.next().is_some() {
+    ///     assert!(it.indexes()[..2].iter().all(|&p| p + 3 <= 10 || p >= 14));
+    /// }
+    /// ```
+    pub fn set_masked_intervals<I>(&mut self, intervals: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let n_kmers = self.table.hashes.len();
+        self.excluded = Some(kmers_overlapping(intervals, self.offset, n_kmers, self.k)?);
+        Ok(())
+    }
+
+    /// Sets the combiner producing the emitted hash from the strobe hashes.
+    ///
+    /// By default strobe hashes are combined with a built-in
+    /// [`CombineScheme`] ([`Mix`](CombineScheme::Mix) unless changed with
+    /// [`set_combine_scheme`](Self::set_combine_scheme)). Any
+    /// [`StrobeCombiner`], including a closure `Fn(&[u64]) -> u64`, can replace
+    /// it. Strobe selection is unaffected; only the emitted hash changes.
+    pub fn set_combiner<C: StrobeCombiner>(&mut self, combiner: C) {
+        self.combiner = Some(Combiner::new(combiner));
+    }
+
+    /// Enables or disables order-independent combination of strobe hashes.
+    ///
+    /// With the built-in schemes a seed and the same strobes visited in
+    /// reverse order hash differently. Enabling this is shorthand for
+    /// `set_combiner(SymmetricCombiner)`; disabling restores the built-in
+    /// scheme (and drops any combiner set before).
+    pub fn set_symmetric(&mut self, enable: bool) {
+        self.combiner = enable.then(|| Combiner::new(SymmetricCombiner));
+    }
+
+    /// Selects the built-in combination of strobe hashes (default
+    /// [`CombineScheme::Mix`]) and drops any combiner set before.
+    ///
+    /// [`CombineScheme::Legacy`] reproduces the seeds of the original
+    /// implementation and of indexes built before `Mix` became the default.
+    /// Strobe selection is unaffected.
+    pub fn set_combine_scheme(&mut self, scheme: CombineScheme) {
+        self.combiner = None;
+        self.scheme = scheme;
+    }
+
+    /// Keeps only strobemers whose combined hash is at most `f × u64::MAX`.
+    ///
+    /// This deterministically thins the output to roughly a fraction `f` of the
+    /// seeds, consistently across sequences. Rejected seeds never leave the
+    /// iterator. The rate is exactly `f` only if combined hashes are uniform
+    /// over `u64`, as with the default [`CombineScheme::Mix`]; the legacy
+    /// formulas use part of the range, so their realized rate is lower.
+    /// Replaces any density set with [`set_gc_density`](Self::set_gc_density).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `0 < f ≤ 1`.
+    /// * `Err(StrobeError::InvalidDensity)` – Otherwise (including NaN).
+    pub fn set_density(&mut self, f: f64) -> Result<()> {
+        if !(f > 0.0 && f <= 1.0) {
+            return Err(StrobeError::InvalidDensity);
+        }
+        self.threshold = (f * u64::MAX as f64) as u64;
+        self.gc_density = None;
+        Ok(())
+    }
+
+    /// Thins the output to a fraction `f` of the seeds within every GC-content bin.
+    ///
+    /// Hash-based thinning as in [`set_density`](Self::set_density) keeps more
+    /// or fewer seeds depending on base composition, because k-mer hashes are
+    /// not independent of GC content. Here each first-strobe position is put
+    /// into one of `bins` equal-width bins by the GC fraction of the `window`
+    /// bases centered on its k-mer, and each bin gets its own threshold keeping
+    /// `f` of that bin's seeds, so seed density is uniform across AT- and
+    /// GC-rich regions. Thresholds are calibrated on this sequence by running
+    /// the remaining iteration once in advance, which roughly doubles the cost.
+    /// Since they depend on the sequence, the kept seeds are not consistent
+    /// across sequences as with `set_density`; use this for density and
+    /// coverage estimates rather than for seed matching.
+    ///
+    /// `seq` must be the sequence the iterator was built from. Replaces any
+    /// density set before, and is replaced by a later `set_density`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If the parameters are valid.
+    /// * `Err(StrobeError::InvalidDensity)` – If `f` is not in `(0, 1]`.
+    /// * `Err(StrobeError::InvalidWindowLength)` – If `window` is 0.
+    /// * `Err(StrobeError::InvalidBinCount)` – If `bins` is 0.
+    /// * `Err(StrobeError::InvalidSequence)` – If `seq` does not match the iterator's length.
+    pub fn set_gc_density(&mut self, seq: &[u8], f: f64, window: usize, bins: u8) -> Result<()> {
+        if !(f > 0.0 && f <= 1.0) {
+            return Err(StrobeError::InvalidDensity);
+        }
+        let mut gc = GcDensity::new(seq, self.k, self.table.hashes.len(), window, bins)?;
+
+        // Calibrate on the seeds the unthinned iterator would emit
+        let mut probe = self.clone();
+        probe.threshold = u64::MAX;
+        probe.gc_density = None;
+        let samples = std::iter::from_fn(|| {
+            let h = probe.next()?;
+            Some((probe.first_local()?, h))
+        });
+        gc.calibrate(samples, f);
+
+        self.threshold = u64::MAX;
+        self.gc_density = Some(gc);
+        Ok(())
+    }
+
+    /// Moves the iterator so the next seed has its first strobe at `i` or later.
+    ///
+    /// Nothing between the current position and `i` is computed, and seeking
+    /// backwards revisits seeds already returned, e.g. to re-seed a region of
+    /// interest. Seeds already taken with `next_back` are not revisited;
+    /// seeking past them leaves the iterator exhausted.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let mut ms = MinStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// ms.seek(4);
+    /// ms.next().unwrap();
+    /// assert_eq!(ms.index(), Some(4));
+    /// ```
+    pub fn seek(&mut self, i: usize) {
+        self.idx = i.saturating_sub(self.offset).min(self.back);
+        self.from_back = false;
+    }
+
+    /// Returns the number of remaining anchors, a cheap upper bound on the
+    /// number of seeds still to come.
+    ///
+    /// The bound is exact unless seeds may be dropped after their anchor is
+    /// visited: by first-strobe positions, a density or GC density, a soft
+    /// mask, masked intervals, or ambiguous k-mers (skipped under
+    /// [`AmbiguityPolicy::SkipAffectedSeeds`](crate::AmbiguityPolicy::SkipAffectedSeeds)
+    /// or flagged by the hasher).
+    pub fn remaining_upper_bound(&self) -> usize {
+        self.remaining_anchors()
+    }
+
+    /// Generates seeds for at most `max_bases` first-strobe positions and returns.
+    ///
+    /// Emitted hashes are appended to `out`. Positions skipped by
+    /// [`set_first_strobe_positions`](Self::set_first_strobe_positions) or a
+    /// mask count towards the budget but cost no selection work; so do
+    /// anchors whose seed a density or mask drops. A budget of 0 is treated
+    /// as 1 so every call makes progress. Intended for event-loop integrations
+    /// that must regain control at a bounded interval.
+    ///
+    /// # Returns
+    ///
+    /// * `true` – Once the iterator is exhausted.
+    /// * `false` – If the budget ran out and more seeds may follow.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let mut it = MinStrobes::new(b"ACGATCTGGTACCTAGGATTACA", 2, 3, 3, 5).unwrap();
+    /// let mut out = Vec::new();
+    /// while !it.poll_seeds(4, &mut out) {
+    ///     // yield to the event loop here
+    /// }
+    /// assert!(!out.is_empty());
+    /// ```
+    pub fn poll_seeds(&mut self, max_bases: usize, out: &mut Vec<u64>) -> bool {
+        let limit = self.idx.saturating_add(max_bases.max(1));
+        loop {
+            match self.next_until(limit) {
+                Some(Some(h)) => out.push(h),
+                Some(None) => return false,
+                None => return true,
+            }
+        }
+    }
+
+    /// Advances to the next emitted seed, visiting no anchor at or past `limit`.
+    ///
+    /// Returns `None` once exhausted and `Some(None)` if `limit` is reached first.
+    fn next_until(&mut self, limit: usize) -> Option<Option<u64>> {
+        loop {
+            self.idx = self.on_stride(self.idx);
+            // Skip start positions excluded by `set_first_strobe_positions` or ambiguous bases
+            while self.idx < self.back.min(limit) && self.skips_anchor(self.idx) {
+                self.idx = self.idx.saturating_add(self.stride);
+            }
+            if self.idx >= self.back {
+                return None;
+            }
+            if self.idx >= limit {
+                return Some(None);
+            }
+            let h = self.anchor_seed(self.idx)?;
+            self.idx += 1;
+            self.from_back = false;
+            if let Some(h) = self.emit(h) {
+                return Some(Some(h));
+            }
+        }
+    }
+
+    /// Writes the next seeds into `out` and returns how many were written.
+    ///
+    /// Fewer than `out.len()` seeds are written only once the iterator is
+    /// exhausted. Unless first-strobe positions, a mask, a density or a
+    /// combiner is set, every remaining anchor yields one seed, and the seeds
+    /// are computed in one loop over the anchors instead of one
+    /// [`next`](Iterator::next) call each. Either way the seeds, and the state
+    /// left behind (e.g. [`indexes`](Self::indexes)), are those of `next`.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACA";
+    /// let mut it = MinStrobes::new(seq, 2, 3, 3, 5).unwrap();
+    /// let mut buf = [0u64; 4];
+    /// let mut seeds = Vec::new();
+    /// loop {
+    ///     let n = it.fill_slice(&mut buf);
+    ///     seeds.extend_from_slice(&buf[..n]);
+    ///     if n < buf.len() {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(seeds, MinStrobes::new(seq, 2, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+    /// ```
+    pub fn fill_slice(&mut self, out: &mut [u64]) -> usize {
+        if self.is_filtered() || self.combiner.is_some() {
+            return out.iter_mut().zip(self.by_ref()).map(|(slot, h)| *slot = h).count();
+        }
+        let last = self.last_complete_anchor();
+        let mut i = self.on_stride(self.idx);
+        let mut written = 0;
+        while written < out.len() && last.is_some_and(|last| i <= last) {
+            let Some(h) = self.anchor_seed(i) else { break };
+            self.idx = i + 1;
+            self.from_back = false;
+            out[written] = self.builtin(h);
+            written += 1;
+            i += self.stride;
+        }
+        if written < out.len() {
+            // Leave the state of the `next` call that finds the end
+            let rest = self.next();
+            debug_assert!(rest.is_none());
+        }
+        written
+    }
+
+    /// Collects the remaining seeds into a vector sized once from the size
+    /// hint, using [`fill_slice`](Self::fill_slice).
+    pub fn into_vec(mut self) -> Vec<u64> {
+        let mut seeds = vec![0; self.size_hint().1.unwrap_or(0)];
+        let n = self.fill_slice(&mut seeds);
+        seeds.truncate(n);
+        seeds
+    }
+
+    /// Enables or disables counting of k-mer hash occurrences within the sequence.
+    ///
+    /// When enabled, a table of how often each k-mer hash occurs in this
+    /// sequence is built from the already computed hashes, and
+    /// [`first_strobe_count`](Self::first_strobe_count) reports the value for
+    /// each seed. High counts mark self-repetitive regions that downstream
+    /// code may want to down-weight without consulting a reference index.
+    pub fn set_local_counts(&mut self, enable: bool) {
+        self.local_counts = enable.then(|| {
+            let mut counts = HashMap::new();
+            for &h in &self.table.hashes {
+                // Saturate instead of wrapping on pathological repeats (> u32::MAX copies)
+                let c = counts.entry(h).or_insert(0u32);
+                *c = c.saturating_add(1);
+            }
+            counts
+        });
+    }
+
+    /// Returns how many times the most recent first strobe's hash occurs in the sequence.
+    ///
+    /// `None` if counting is disabled or no strobemer has been generated yet.
+    pub fn first_strobe_count(&self) -> Option<usize> {
+        let counts = self.local_counts.as_ref()?;
+        let h = self.table.hashes[self.first_local()?];
+        counts.get(&h).map(|&c| c as usize)
+    }
+
+    /// Recombines the strobes of the current seed with `combiner`.
+    fn recombine(&self, combiner: &Combiner) -> u64 {
+        let n = self.n as usize;
+        combiner.combine(&self.current_strobes()[..n], &self.local_indexes()[..n])
+    }
+
+    /// Applies the built-in scheme to the current seed, whose hash under the
+    /// legacy formula (computed during selection) is `h`. Order-1 seeds keep
+    /// their k-mer hash.
+    #[inline(always)]
+    fn builtin(&self, h: u64) -> u64 {
+        match self.scheme {
+            _ if self.n == 1 => h,
+            CombineScheme::Legacy => h,
+            scheme => scheme.combine(&self.current_strobes()[..self.n as usize]),
+        }
+    }
+
+    /// Returns the hashes of the current seed's strobes; a strobe from an empty
+    /// shrunk window (past the last k-mer) counts as `u64::MAX`, as in selection.
+    #[inline(always)]
+    fn current_strobes(&self) -> [u64; 3] {
+        self.local_indexes().map(|m| self.table.hashes.get(m).copied().unwrap_or(u64::MAX))
+    }
+
+    /// Advances the iterator, returning the next strobemer with its positions.
+    ///
+    /// Equivalent to calling `next()` followed by [`indexes`](Self::indexes).
+    pub fn next_seed(&mut self) -> Option<Strobemer> {
+        let hash = self.next()?;
+        Some(Strobemer::new(hash, self.indexes(), self.n, self.k))
+    }
+
+    /// Turns the iterator into one yielding [`Strobemer`] items instead of bare hashes.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let ms = MinStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// let seeds: Vec<_> = ms.iter_seeds().collect();
+    /// assert!(seeds.iter().all(|s| s.positions[0] < s.positions[1] && s.span <= 16));
+    /// ```
+    pub fn iter_seeds(mut self) -> impl Iterator<Item = Strobemer> {
+        std::iter::from_fn(move || self.next_seed())
+    }
+
+    /// Turns the iterator into one yielding seeds with 128-bit hashes.
+    ///
+    /// Positions and the high 64 bits are those of
+    /// [`iter_seeds`](Self::iter_seeds); the low 64 bits hash the strobe bases
+    /// (see [`Strobemer128`]). `seq` must be the sequence the iterator was built
+    /// from, as for [`seed_bytes`](Self::seed_bytes).
+    pub fn iter_seeds128(mut self, seq: &[u8]) -> impl Iterator<Item = Strobemer128> {
+        std::iter::from_fn(move || {
+            let seed = self.next_seed()?;
+            Some(Strobemer128::new(seed, &self.seed_bytes(seq)))
+        })
+    }
+
+    /// Turns the iterator into one yielding `(hash, [m1, m2, m3])` pairs.
+    ///
+    /// A lighter alternative to [`iter_seeds`](Self::iter_seeds) that works
+    /// with `filter`, `zip` and friends without calling `indexes()` after each
+    /// `next()`. `m3` is 0 for order 2.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let ms = MinStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// let near: Vec<u64> = ms
+    ///     .with_positions()
+    ///     .filter(|&(_, [m1, m2, _])| m2 - m1 <= 4)
+    ///     .map(|(h, _)| h)
+    ///     .collect();
+    /// ```
+    pub fn with_positions(mut self) -> impl Iterator<Item = (u64, [usize; 3])> {
+        std::iter::from_fn(move || self.next().map(|h| (h, self.indexes())))
+    }
+
+    /// Collects the remaining seeds column-wise: `(hashes, m1, m2, m3)`.
+    ///
+    /// One pass, with the columns pre-sized from the size hint, for index
+    /// builders that sort or serialize each column separately. `m3` is all
+    /// zeros for order 2.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((hashes, m1, m2, m3))` – The columns.
+    /// * `Err(StrobeError::SequenceTooLong)` – If a position could exceed
+    ///   `u32::MAX`, i.e. the last k-mer starts past it; checked before any
+    ///   seed is computed.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let (hashes, m1, m2, _) = MinStrobes::new(seq, 2, 3, 3, 5).unwrap().collect_soa().unwrap();
+    /// assert_eq!(hashes.len(), m1.len());
+    /// assert!(m1.iter().zip(&m2).all(|(a, b)| a < b));
+    /// ```
+    pub fn collect_soa(mut self) -> Result<SeedColumns> {
+        // The start of the last k-mer bounds every position
+        if self.offset + self.table.hashes.len() - 1 > u32::MAX as usize {
+            return Err(StrobeError::SequenceTooLong);
+        }
+        let cap = self.size_hint().1.unwrap_or(0);
+        let mut hashes = Vec::with_capacity(cap);
+        let mut cols = [(); 3].map(|_| Vec::with_capacity(cap));
+        while let Some(h) = self.next() {
+            hashes.push(h);
+            for (col, p) in cols.iter_mut().zip(self.indexes()) {
+                col.push(p as u32);
+            }
+        }
+        let [m1, m2, m3] = cols;
+        Ok((hashes, m1, m2, m3))
+    }
+
+    /// Returns the `budget` remaining seeds with the lowest reference abundance.
+    ///
+    /// Shorthand for [`top_informative`](crate::top_informative) over
+    /// [`with_positions`](Self::with_positions); see there for tie-breaking and
+    /// seeds absent from the reference.
+    pub fn top_informative<A>(self, budget: usize, source: &A) -> Vec<(u64, [usize; 3])>
+    where
+        A: AbundanceSource + ?Sized,
+    {
+        top_informative(self.with_positions(), budget, source)
+    }
+
+    /// Returns the concatenated strobe bases of the most recent strobemer.
+    ///
+    /// The iterator does not keep the sequence, so `seq` must be the one it
+    /// was built from; panics if it is shorter. Strobes are concatenated in
+    /// order m1, m2(, m3). Returns an empty vector before the first strobemer.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::MinStrobes;
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let mut ms = MinStrobes::new(seq, 2, 3, 3, 5).unwrap();
+    /// ms.next().unwrap();
+    /// let [m1, m2, _] = ms.indexes();
+    /// assert_eq!(ms.seed_bytes(seq), [&seq[m1..m1 + 3], &seq[m2..m2 + 3]].concat());
+    /// ```
+    pub fn seed_bytes(&self, seq: &[u8]) -> Vec<u8> {
+        if self.index().is_none() {
+            return Vec::new();
+        }
+        strobe_bytes(seq, &self.indexes()[..self.n as usize], self.k, false)
+    }
+
     /// Returns the index of the last returned first-strobe (m1).
     ///
     /// If no strobe has been generated yet, returns `None`.
     pub fn index(&self) -> Option<usize> {
-        self.idx.checked_sub(1)
+        self.first_local().map(|i| i + self.offset)
     }
 
     /// Returns the indices of the most recently generated strobes: [m1, m2, (m3)].
     ///
     /// If no strobe has been generated yet, returns `[0, 0, 0]`.
     pub fn indexes(&self) -> [usize; 3] {
-        [self.index().unwrap_or(0), self.idx2, self.idx3]
+        let [m1, m2, m3] = self.local_indexes();
+        if self.offset == 0 || self.first_local().is_none() {
+            return [m1, m2, m3];
+        }
+        [m1 + self.offset, m2 + self.offset, if self.n == 3 { m3 + self.offset } else { 0 }]
+    }
+
+    /// Index into `hashes` of the current item's m1, `None` before the first item.
+    #[inline(always)]
+    fn first_local(&self) -> Option<usize> {
+        if self.from_back { Some(self.back) } else { self.idx.checked_sub(1) }
+    }
+
+    /// Strobe indices `[m1, m2, m3]` into `hashes`, without the region offset.
+    #[inline(always)]
+    fn local_indexes(&self) -> [usize; 3] {
+        [self.first_local().unwrap_or(0), self.idx2, self.idx3]
+    }
+
+    /// Returns the last m1 in `idx..back` whose windows fit the sequence, if any.
+    fn last_complete_anchor(&self) -> Option<usize> {
+        let links = self.n as usize - 1;
+        // Offset of the farthest window bound that must lie within the sequence;
+        // shrinking order-2 windows may become empty but still yield a seed
+        let reach = match (self.n, self.shrink) {
+            (1, _) => 0,
+            (2, true) if self.min_span == 0 => 0,
+            (_, true) => self.last_window_start(0),
+            (_, false) => links * self.w_max,
+        };
+        let last = self.end_hash.checked_sub(reach)?.min(self.back.checked_sub(1)?);
+        let last = last / self.stride * self.stride;
+        (last >= self.idx).then_some(last)
+    }
+
+    /// Rounds `i` up to the next first-strobe position allowed by the stride.
+    #[inline(always)]
+    fn on_stride(&self, i: usize) -> usize {
+        i.next_multiple_of(self.stride)
+    }
+
+    /// Returns how many anchors from the current one on produce a seed, before
+    /// any filtering.
+    fn remaining_anchors(&self) -> usize {
+        self.last_complete_anchor()
+            .map_or(0, |l| (l - self.on_stride(self.idx)) / self.stride + 1)
+    }
+
+    /// Computes the seed anchored at `i`, or `None` if its windows do not fit.
+    #[inline(always)]
+    fn anchor_seed(&mut self, i: usize) -> Option<u64> {
+        match self.n {
+            1 => Some(self.kmer_at(i)),
+            2 => self.order2_at(i),
+            3 => self.order3_at(i),
+            _ => None, // Should not occur due to prior validation
+        }
     }
 
-    /// Computes the next hash value for an order-2 MinStrobe.
-    fn next_order2(&mut self) -> Option<u64> {
-        // Stop if no more valid starting positions for m1
-        if self.idx > self.end_idx {
+    /// Applies the combiner and the density threshold to the current seed.
+    #[inline(always)]
+    fn emit(&self, h: u64) -> Option<u64> {
+        let h = match &self.combiner {
+            Some(c) => self.recombine(c),
+            None => self.builtin(h),
+        };
+        if self.drops_strobes() {
             return None;
         }
+        // Drop seeds above the density threshold
+        let threshold = match &self.gc_density {
+            Some(gc) => gc.threshold(self.first_local().unwrap_or(0)),
+            None => self.threshold,
+        };
+        (h <= threshold).then_some(h)
+    }
+
+    /// Returns whether no seed may start at `i`: it is excluded by
+    /// `set_first_strobe_positions`, overlaps an ambiguous base, is soft-masked,
+    /// or intersects a masked interval.
+    #[inline(always)]
+    fn skips_anchor(&self, i: usize) -> bool {
+        self.first_mask.as_ref().is_some_and(|mask| !mask[i])
+            || self.table.valid.as_ref().is_some_and(|valid| !valid[i])
+            || self.soft_mask.as_ref().is_some_and(|masked| masked[i])
+            || self.excluded.as_ref().is_some_and(|excluded| excluded[i])
+    }
+
+    /// Returns whether the current seed is dropped for its linked strobes: a
+    /// window held only k-mers with ambiguous bases, a strobe is soft-masked
+    /// under [`SoftMask::SkipAnyStrobe`], or a strobe intersects a masked interval.
+    #[inline(always)]
+    fn drops_strobes(&self) -> bool {
+        let [_, m2, m3] = self.local_indexes();
+        let links = [m2, m3];
+        let links = &links[..self.n as usize - 1];
+        self.table.valid.as_ref().is_some_and(|valid| links.iter().any(|&m| !valid[m]))
+            || self.soft_any
+                && self.soft_mask.as_ref().is_some_and(|masked| links.iter().any(|&m| masked[m]))
+            || self.excluded.as_ref().is_some_and(|excluded| links.iter().any(|&m| excluded[m]))
+    }
+
+    /// Returns whether seeds may be dropped after their anchor was visited.
+    fn is_filtered(&self) -> bool {
+        self.first_mask.is_some()
+            || self.table.valid.is_some()
+            || self.soft_mask.is_some()
+            || self.excluded.is_some()
+            || self.threshold < u64::MAX
+            || self.gc_density.is_some()
+    }
 
+    /// Returns the start of anchor `i`'s last search window, raised so that a
+    /// strobe in it ends no earlier than the span floor.
+    #[inline(always)]
+    fn last_window_start(&self, i: usize) -> usize {
+        let links = self.n as usize - 1;
+        i + ((links - 1) * self.w_max + self.w_min).max(self.min_span.saturating_sub(self.k))
+    }
+
+    /// Returns the plain k-mer at `i`, the seed of the order-1 fallback.
+    fn kmer_at(&mut self, i: usize) -> u64 {
+        self.h1 = self.table.hashes[i];
+        self.h1
+    }
+
+    /// Computes the order-2 MinStrobe anchored at `i`.
+    fn order2_at(&mut self, i: usize) -> Option<u64> {
         // Define the search window range for m2
-        let w_start = self.idx + self.w_min;
-        let mut w_end = self.idx + self.w_max;
+        let w_start = self.last_window_start(i);
+        let mut w_end = i + self.w_max;
+
+        // Under a span floor the window must keep a candidate
+        if self.min_span > 0 && w_start > self.end_hash {
+            return None;
+        }
 
         // Hash of the first k-mer (m1)
-        self.h1 = self.hashes[self.idx];
+        self.h1 = self.table.hashes[i];
 
         // If window extends past last hash index, adjust or stop
         if w_end > self.end_hash {
@@ -219,48 +1424,39 @@ impl MinStrobes {
             }
             w_end = self.end_hash;
         }
+        // Cut the window at the span cap; a cut window is scanned like a shrunk one
+        let w_end = w_end.min(i.saturating_add(self.max_span - self.k));
 
         // If full window fits, use precomputed minimum
-        if w_end == self.idx + self.w_max {
-            self.idx2 = self.minloc[w_end];
+        if w_start == i + self.w_min && w_end == i + self.w_max {
+            self.idx2 = self.table.minloc[w_end].to_usize();
             // Combine h1 and precomputed minimum hash
-            self.h2 = (self.h1 >> 1) + self.minval[w_end] / 3;
+            self.h2 = (self.h1 >> 1) + self.table.minval[w_end] / 3;
         } else {
-            // Partial window: manually scan to find minimum
-            let (mut best_hash, mut best_pos) = (u64::MAX, w_start);
-            for pos in w_start..=w_end {
-                let cand = self.hashes[pos];
-                if cand < best_hash {
-                    best_hash = cand;
-                    best_pos = pos;
-                }
-            }
-            self.idx2 = best_pos;
+            // Partial window: scan for the minimum (empty windows keep `w_start`).
+            // Equal hashes tie under SecondaryHash too, leaving the leftmost
+            let rightmost = self.tie == Some(TieBreak::Rightmost);
+            let window = self.table.hashes.get(w_start..=w_end).unwrap_or_default();
+            let (at, best_hash) = lane_min(window, rightmost, |h| h);
+            self.idx2 = w_start + at;
             self.h2 = self.h1 / 2 + best_hash / 3;
         }
 
-        // Advance to next starting index for m1
-        self.idx += 1;
         Some(self.h2)
     }
 
-    /// Computes the next hash value for an order-3 MinStrobe.
+    /// Computes the order-3 MinStrobe anchored at `i`.
     ///
     /// # Returns
     /// - `Some(u64)` – Combined hash value of m1, m2, and m3, if available.
-    /// - `None` – When no further strobes can be formed.
+    /// - `None` – When no third strobe fits after `i`.
     ///
-    fn next_order3(&mut self) -> Option<u64> {
-        // Stop if no more valid starting positions for m1
-        if self.idx > self.end_idx {
-            return None;
-        }
-
+    fn order3_at(&mut self, i: usize) -> Option<u64> {
         // Window range for selecting m2
-        let w_end = self.idx + self.w_max;
+        let w_end = i + self.w_max;
         // Window range for selecting m3 (after m2 block)
-        let w2_start = self.idx + self.w_max + self.w_min;
-        let mut w2_end = self.idx + (self.w_max << 1);
+        let w2_start = self.last_window_start(i);
+        let mut w2_end = i + (self.w_max << 1);
 
         // If there's no room for a third k-mer, stop
         if w2_start > self.end_hash {
@@ -273,59 +1469,144 @@ impl MinStrobes {
             }
             w2_end = self.end_hash;
         }
+        // Cut the window at the span cap; a cut window is scanned like a shrunk one
+        let w2_end = w2_end.min(i.saturating_add(self.max_span - self.k));
 
         // Compute m1 (first k-mer)
-        self.h1 = self.hashes[self.idx];
+        self.h1 = self.table.hashes[i];
         // Select m2 using precomputed minima at window end
-        self.idx2 = self.minloc[w_end];
-        self.h2 = self.h1 / 3 + (self.minval[w_end] >> 2);
+        self.idx2 = self.table.minloc[w_end].to_usize();
+        self.h2 = self.h1 / 3 + (self.table.minval[w_end] >> 2);
+
+        // Full m3 window, relative to m1 or to the selected m2
+        let full_start = match self.anchor {
+            WindowAnchor::FromFirstStrobe => i + self.w_max,
+            WindowAnchor::FromPreviousStrobe => self.idx2,
+        } + self.w_min;
+        let full_end = full_start - self.w_min + self.w_max;
+        let (w2_start, w2_end) = match self.anchor {
+            WindowAnchor::FromFirstStrobe => (w2_start, w2_end),
+            WindowAnchor::FromPreviousStrobe => {
+                // Keep the far bound and the span floor of the window placed from m1
+                let start = full_start.max(i + self.min_span.saturating_sub(self.k));
+                (start, full_end.min(w2_end).max(start))
+            }
+        };
 
         // Select m3
-        if w2_end == self.idx + (self.w_max << 1) {
+        if w2_start == full_start && w2_end == full_end {
             // Full second window fits: use precomputed minima
-            self.idx3 = self.minloc[w2_end];
-            self.h3 = self.h2 + self.minval[w2_end] / 5;
+            self.idx3 = self.table.minloc[w2_end].to_usize();
+            self.h3 = self.h2 + self.table.minval[w2_end] / 5;
         } else {
-            // Partial second window near the end: manual scan
-            let (mut best_hash, mut best_pos) = (u64::MAX, w2_start);
-            for pos in w2_start..=w2_end {
-                // Combine current h2 with candidate hash, then mask with prime
-                let cand = (self.h2 + self.hashes[pos]) & self.prime;
-                if cand < best_hash {
-                    best_hash = cand;
-                    best_pos = pos;
-                }
-            }
-            self.idx3 = best_pos;
-            self.h3 = self.h2 + self.hashes[self.idx3] / 5;
+            // Partial second window near the end: scan it
+            let window = self.table.hashes.get(w2_start..=w2_end).unwrap_or_default();
+            // Combine current h2 with candidate hash, then mask with prime
+            let combined = |h: u64| self.h2.wrapping_add(h) & self.prime;
+            self.idx3 = w2_start
+                + match (self.consistent, self.tie) {
+                    // Plain minimum, ties to the rightmost as in the precomputed minima;
+                    // equal hashes tie under SecondaryHash too, leaving the leftmost
+                    (true, tie) => {
+                        let rightmost = matches!(tie, None | Some(TieBreak::Rightmost));
+                        lane_min(window, rightmost, |h| h).0
+                    }
+                    (false, Some(TieBreak::SecondaryHash)) => {
+                        lane_min(window, false, |h| secondary_key(combined(h), h)).0
+                    }
+                    (false, tie) => lane_min(window, tie == Some(TieBreak::Rightmost), combined).0,
+                };
+            self.h3 = self.h2 + self.table.hashes[self.idx3] / 5;
         }
 
-        // Advance to next starting index for m1
-        self.idx += 1;
         Some(self.h3)
     }
 }
 
-impl Iterator for MinStrobes {
+impl<P: Position> Iterator for MinStrobes<P> {
     type Item = u64;
 
     /// Advances the iterator, returning the next strobemer hash value.
     ///
-    /// Dispatches to `next_order2` or `next_order3` based on `self.n`.
+    /// Dispatches to `order2_at` or `order3_at` based on `self.n`.
     /// If `n` is not 2 or 3, returns `None`.
     fn next(&mut self) -> Option<Self::Item> {
-        match self.n {
-            2 => self.next_order2(),
-            3 => self.next_order3(),
-            _ => None, // Should not occur due to prior validation
+        self.next_until(usize::MAX).flatten()
+    }
+
+    /// Skips `n` seeds; without first-strobe positions or a density set,
+    /// every remaining anchor yields one seed, so skipped seeds are not computed.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.is_filtered() {
+            for _ in 0..n {
+                self.next()?;
+            }
+        } else if n < self.remaining_anchors() {
+            self.idx = self.on_stride(self.idx) + n * self.stride;
+        } else {
+            self.idx = self.back;
+            return None;
+        }
+        self.next()
+    }
+
+    /// Returns exact bounds unless seeds may be dropped after their anchor is
+    /// visited (see [`remaining_upper_bound`](Self::remaining_upper_bound)),
+    /// in which case the lower bound is 0.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = self.remaining_anchors();
+        (if self.is_filtered() { 0 } else { upper }, Some(upper))
+    }
+}
+
+impl<P: Position> DoubleEndedIterator for MinStrobes<P> {
+    /// Returns the remaining seed with the rightmost first strobe.
+    ///
+    /// Seeds are identical to those of forward iteration, which stops where
+    /// the two ends meet. After a call, [`indexes`](MinStrobes::indexes)
+    /// reports the strobes of the seed just returned.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut i = self.last_complete_anchor()?;
+            // Skip start positions excluded by `set_first_strobe_positions` or ambiguous bases
+            while self.skips_anchor(i) {
+                i = i.checked_sub(self.stride).filter(|&i| i >= self.idx)?;
+            }
+            let h = self.anchor_seed(i)?;
+            self.back = i;
+            self.from_back = true;
+            if let Some(h) = self.emit(h) {
+                return Some(h);
+            }
         }
     }
 }
 
+impl<P: Position> FusedIterator for MinStrobes<P> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn poll_seeds_bounds_skipped_and_dropped_anchors() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC".repeat(20);
+        let mut ms = MinStrobes::new(&seq, 2, 3, 2, 5).unwrap();
+        ms.set_first_strobe_positions([700]);
+        let mut out = Vec::new();
+        assert!(!ms.poll_seeds(16, &mut out));
+        assert!(out.is_empty() && ms.idx <= 16);
+
+        let mut ms = MinStrobes::new(&seq, 2, 3, 2, 5).unwrap();
+        ms.set_density(1e-9).unwrap();
+        assert!(!ms.poll_seeds(16, &mut out));
+        assert!(ms.idx <= 16);
+        while !ms.poll_seeds(16, &mut out) {}
+        let mut all = MinStrobes::new(&seq, 2, 3, 2, 5).unwrap();
+        all.set_density(1e-9).unwrap();
+        assert_eq!(out, all.collect::<Vec<_>>());
+    }
+
     #[test]
     fn order2_basic() {
         // Basic smoke test: sequence "ACGTACGTACGT", order=2, k=3, w_min=1, w_max=4
@@ -0,0 +1,239 @@
+use std::mem;
+
+use crate::{
+    Result, StrobeError,
+    ambiguity::{AmbiguityPolicy, hasher_validity, merge_validity},
+    hashes::{KmerHasher, NtHash64, compute_min_hashes_into},
+    position::Position,
+    scratch::Scratch,
+    selection::TieBreak,
+};
+
+/// Precomputed k-mer hashes and sliding-window minima of one sequence.
+///
+/// This is everything [`MinStrobes`](crate::MinStrobes) derives from the
+/// sequence; only the strobemer order is left open. Build the table once and
+/// hand it to [`MinStrobes::from_table`](crate::MinStrobes::from_table) for
+/// each order, instead of hashing the sequence and computing the minima once
+/// per order. The window width is `w_max − w_min + 1`, and
+/// [`min_locations`](Self::min_locations)`[i]` /
+/// [`min_values`](Self::min_values)`[i]` describe the window ending at k-mer
+/// `i` (valid for `i ≥ w_max − w_min`). Locations are stored as `P`; see
+/// [`Position`] for `u32` tables.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use strobemers_rs::{MinStrobes, MinTable};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let table = Arc::new(MinTable::new(seq, 3, 3, 5).unwrap());
+/// let order2: Vec<u64> = MinStrobes::from_table(Arc::clone(&table), 2).unwrap().collect();
+/// let order3: Vec<u64> = MinStrobes::from_table(table, 3).unwrap().collect();
+/// assert_eq!(order2, MinStrobes::new(seq, 2, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+/// assert_eq!(order3, MinStrobes::new(seq, 3, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinTable<P: Position = usize> {
+    pub(crate) seq_len: usize,           // Length of the hashed sequence
+    pub(crate) k: usize,                 // k-mer length
+    pub(crate) w_min: usize,             // Minimum window offset
+    pub(crate) w_max: usize,             // Maximum window offset
+    pub(crate) hashes: Vec<u64>,         // Hash values for each k-mer in the sequence
+    pub(crate) minloc: Vec<P>,           // Location of the minimum hash within each window
+    pub(crate) minval: Vec<u64>,         // Minimum hash value within each sliding window
+    pub(crate) valid: Option<Vec<bool>>, // k-mers free of ambiguous bases, if any are not
+}
+
+impl MinTable {
+    /// Builds the table using the default hash function (`NtHash64`).
+    ///
+    /// See [`MinTable::with_scratch`] for details.
+    pub fn new(seq: &[u8], k: usize, w_min: usize, w_max: usize) -> Result<Self> {
+        Self::with_hasher(seq, k, w_min, w_max, &NtHash64)
+    }
+
+    /// Builds the table with a user-defined hash function.
+    ///
+    /// See [`MinTable::with_scratch`] for details.
+    pub fn with_hasher<H>(
+        seq: &[u8],
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        Self::with_scratch(seq, k, w_min, w_max, hasher, &mut Scratch::new())
+    }
+
+    /// Builds the table, taking its hash and window-minima buffers from `scratch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq`, `k`, `w_min`, `w_max` – As for [`MinStrobes::new`](crate::MinStrobes::new).
+    /// * `hasher` – Hasher for the k-mer hashes.
+    /// * `scratch` – Buffers to reuse; see [`Scratch`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinTable)` – If the parameters are valid for at least order 2.
+    /// * `Err(StrobeError)` – On invalid parameters, hashing errors, or a sequence
+    ///   too short for order 2.
+    pub fn with_scratch<H>(
+        seq: &[u8],
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+        scratch: &mut Scratch,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        Self::build(seq, k, w_min, w_max, hasher, scratch, AmbiguityPolicy::Hasher, None)
+    }
+
+    /// Builds a table storing window-minimum locations as `u32`, half the
+    /// memory of the default `usize`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinTable<u32>)` – As [`MinTable::with_hasher`] would build it.
+    /// * `Err(StrobeError::SequenceTooLong)` – If `seq` is longer than `u32::MAX` bases.
+    /// * `Err(StrobeError)` – Any other error of [`MinTable::with_hasher`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{MinTable, NtHash64};
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let compact = MinTable::with_u32_positions(seq, 3, 3, 5, &NtHash64).unwrap();
+    /// let table = MinTable::new(seq, 3, 3, 5).unwrap();
+    /// assert!(compact.min_locations().iter().zip(table.min_locations()).all(|(&a, &b)| {
+    ///     a as usize == b
+    /// }));
+    /// ```
+    pub fn with_u32_positions<H>(
+        seq: &[u8],
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<MinTable<u32>>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        let scratch = &mut Scratch::new();
+        MinTable::build(seq, k, w_min, w_max, hasher, scratch, AmbiguityPolicy::Hasher, None)
+    }
+}
+
+impl<P: Position> MinTable<P> {
+    /// Builds the table, handling ambiguous bases according to `policy`.
+    ///
+    /// K-mers invalid under [`AmbiguityPolicy::SkipAffectedSeeds`], by the
+    /// hasher's [`validity`](KmerHasher::validity) or by the caller's `valid`
+    /// mask get the hash `u64::MAX`, so window minima avoid them whenever possible.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn build<H>(
+        seq: &[u8],
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+        scratch: &mut Scratch,
+        policy: AmbiguityPolicy,
+        valid: Option<Vec<bool>>,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        // Order 2 has the weakest length requirement; order 3 is checked on use
+        validate_params!(seq, 2, k, w_min, w_max, hasher);
+        if seq.len() > P::MAX_SEQ_LEN {
+            return Err(StrobeError::SequenceTooLong);
+        }
+
+        let (seq, ambiguous) = policy.apply(seq, k)?;
+        let valid = merge_validity(valid, ambiguous);
+        let mut hashes = mem::take(&mut scratch.hashes);
+        hasher.hash_all_into(&seq, k, &mut hashes)?;
+        let valid = merge_validity(valid, hasher_validity(hasher, &seq, k, hashes.len())?);
+        Ok(Self::from_hashes(seq.len(), k, w_min, w_max, hashes, valid, scratch))
+    }
+
+    /// Builds the table from the k-mer hashes of a validated sequence of
+    /// `seq_len` bases; k-mers invalid under `valid` get the hash `u64::MAX`.
+    pub(crate) fn from_hashes(
+        seq_len: usize,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        mut hashes: Vec<u64>,
+        valid: Option<Vec<bool>>,
+        scratch: &mut Scratch,
+    ) -> Self {
+        if let Some(valid) = &valid {
+            for (h, _) in hashes.iter_mut().zip(valid).filter(|&(_, &ok)| !ok) {
+                *h = u64::MAX;
+            }
+        }
+        compute_min_hashes_into::<P>(&hashes, w_max - w_min + 1, TieBreak::Rightmost, scratch);
+
+        Self {
+            seq_len,
+            k,
+            w_min,
+            w_max,
+            hashes,
+            minloc: mem::take(P::locations(scratch)),
+            minval: mem::take(&mut scratch.minval),
+            valid,
+        }
+    }
+
+    /// Returns the hash and window-minima buffers to `scratch` for reuse by the next construction.
+    pub fn recycle(self, scratch: &mut Scratch) {
+        scratch.hashes = self.hashes;
+        *P::locations(scratch) = self.minloc;
+        scratch.minval = self.minval;
+    }
+
+    /// Recomputes the window minima with ties among equal minima resolved by `tie`.
+    ///
+    /// Tables are built with [`TieBreak::Rightmost`]; see
+    /// [`compute_min_hashes_with`](crate::compute_min_hashes_with).
+    pub fn set_tie_break(&mut self, tie: TieBreak) {
+        let mut scratch = Scratch { minval: mem::take(&mut self.minval), ..Scratch::default() };
+        *P::locations(&mut scratch) = mem::take(&mut self.minloc);
+        compute_min_hashes_into::<P>(&self.hashes, self.w_max - self.w_min + 1, tie, &mut scratch);
+        self.minloc = mem::take(P::locations(&mut scratch));
+        self.minval = scratch.minval;
+    }
+
+    /// Returns the k-mer length.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the window offsets `(w_min, w_max)`.
+    pub fn window(&self) -> (usize, usize) {
+        (self.w_min, self.w_max)
+    }
+
+    /// Returns the hash of every k-mer, by start position.
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// Returns, per window end, the position of the window's minimum hash.
+    pub fn min_locations(&self) -> &[P] {
+        &self.minloc
+    }
+
+    /// Returns, per window end, the window's minimum hash.
+    pub fn min_values(&self) -> &[u64] {
+        &self.minval
+    }
+}
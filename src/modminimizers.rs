@@ -0,0 +1,158 @@
+use std::mem;
+
+use crate::{
+    Result, StrobeError,
+    hashes::{KmerHasher, NtHash64, compute_min_hashes_into},
+    scratch::Scratch,
+    selection::TieBreak,
+};
+
+/// Iterator over mod-minimizer positions of a DNA/RNA sequence.
+///
+/// For a window of `w` consecutive k-mers, the mod-minimizer scheme finds the
+/// position `x` of the smallest `t`-mer inside the window, with
+/// `t = r + ((k − r) mod w)`, and selects the k-mer starting at `x mod w`.
+/// Compared to plain minimizers this lowers the density for large `k`
+/// (approaching the `1/w` lower bound).
+///
+/// Positions are k-mer start indices, yielded once each in increasing order.
+/// They can be fed to `set_first_strobe_positions` on [`MinStrobes`](crate::MinStrobes)
+/// or [`RandStrobes`](crate::RandStrobes) to thin the output.
+///
+#[derive(Debug, Clone)]
+pub struct ModMinimizers {
+    minloc: Vec<usize>,  // Location of the minimum t-mer hash within each window
+    w: usize,            // Window size in k-mers
+    span: usize,         // Number of t-mers per window
+    idx: usize,          // Start of the next window to visit
+    end: usize,          // Last valid window start
+    last: Option<usize>, // Most recently emitted position
+}
+
+impl ModMinimizers {
+    /// Constructs a new [`ModMinimizers`] iterator using the default hash function (`NtHash64`).
+    ///
+    /// # Arguments
+    ///
+    /// * `seq` – Input nucleotide sequence as a byte slice (ASCII only).
+    /// * `k` – k-mer length; must be in `[1, 64]`.
+    /// * `w` – Window size in k-mers; must be ≥ 1.
+    /// * `r` – Lower bound of the t-mer length (4 is the customary choice).
+    ///   Clamped to `[1, k]`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ModMinimizers)` on success.
+    /// * `Err(StrobeError)` if parameters are invalid or the sequence holds fewer than `w` k-mers.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::ModMinimizers;
+    /// let pos: Vec<usize> = ModMinimizers::new(b"ACGATCTGGTACCTAGGATTACA", 7, 4, 4).unwrap().collect();
+    /// assert!(pos.windows(2).all(|p| p[0] < p[1]));
+    /// ```
+    pub fn new(seq: &[u8], k: usize, w: usize, r: usize) -> Result<Self> {
+        Self::with_hasher(seq, k, w, r, &NtHash64)
+    }
+
+    /// Constructs a new [`ModMinimizers`] iterator with a user-defined hash function,
+    /// which is applied to the t-mers.
+    ///
+    /// See [`ModMinimizers::new`] for the parameters.
+    pub fn with_hasher<H>(seq: &[u8], k: usize, w: usize, r: usize, hasher: &H) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        Self::with_scratch(seq, k, w, r, hasher, &mut Scratch::new())
+    }
+
+    /// Constructs a new [`ModMinimizers`] iterator, taking its window-minima buffer from `scratch`.
+    ///
+    /// Behaves like [`ModMinimizers::with_hasher`]; pass the buffer back with
+    /// [`recycle`](Self::recycle) to reuse it for the next sequence.
+    pub fn with_scratch<H>(
+        seq: &[u8],
+        k: usize,
+        w: usize,
+        r: usize,
+        hasher: &H,
+        scratch: &mut Scratch,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        if seq.is_empty() || !seq.is_ascii() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if w == 0 {
+            return Err(StrobeError::InvalidWindowLength);
+        }
+        // A window of w k-mers spans w + k − 1 bases
+        if seq.len() < w + k - 1 {
+            return Err(StrobeError::SequenceTooShort);
+        }
+
+        let r = r.clamp(1, k);
+        let t = r + (k - r) % w;
+        let span = w + k - t;
+
+        let mut hashes = mem::take(&mut scratch.hashes);
+        hasher.hash_all_into(seq, t, &mut hashes)?;
+        compute_min_hashes_into::<usize>(&hashes, span, TieBreak::Rightmost, scratch);
+        scratch.hashes = hashes;
+        let minloc = mem::take(&mut scratch.minloc);
+
+        Ok(Self {
+            minloc,
+            w,
+            span,
+            idx: 0,
+            end: seq.len() - (w + k - 1),
+            last: None,
+        })
+    }
+
+    /// Returns the window-minima buffer to `scratch` for reuse by the next construction.
+    pub fn recycle(self, scratch: &mut Scratch) {
+        scratch.minloc = self.minloc;
+    }
+}
+
+impl Iterator for ModMinimizers {
+    type Item = usize;
+
+    /// Returns the next distinct mod-minimizer position.
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx <= self.end {
+            let start = self.idx;
+            self.idx += 1;
+            // Smallest t-mer of the window, mapped back to a k-mer by `mod w`
+            let x = self.minloc[start + self.span - 1] - start;
+            let pos = start + x % self.w;
+            if self.last != Some(pos) {
+                self.last = Some(pos);
+                return Some(pos);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_window_has_a_position() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGT";
+        let (k, w) = (7, 5);
+        let pos: Vec<usize> = ModMinimizers::new(seq, k, w, 4).unwrap().collect();
+        assert!(pos.windows(2).all(|p| p[0] < p[1]));
+        for start in 0..=seq.len() - (w + k - 1) {
+            assert!(pos.iter().any(|&p| (start..start + w).contains(&p)));
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{
+    Result, StrobeSpec, Strobemer,
+    combine::{LegacyCombiner, StrobeCombiner},
+    strand::Strand,
+    util::reverse_complement,
+};
+
+/// Read of a pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mate {
+    /// Read 1.
+    First,
+    /// Read 2.
+    Second,
+}
+
+/// Relative orientation of the two reads of a pair with respect to the fragment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PairOrientation {
+    /// Read 1 on the fragment's forward strand, read 2 on its reverse (Illumina paired-end).
+    #[default]
+    Fr,
+    /// Read 1 on the reverse strand, read 2 on the forward (mate-pair libraries).
+    Rf,
+    /// Both reads on the forward strand.
+    Ff,
+}
+
+impl PairOrientation {
+    /// Returns the fragment strand each mate was sequenced from, read 1 first.
+    pub fn strands(self) -> [Strand; 2] {
+        match self {
+            Self::Fr => [Strand::Forward, Strand::Reverse],
+            Self::Rf => [Strand::Reverse, Strand::Forward],
+            Self::Ff => [Strand::Forward, Strand::Forward],
+        }
+    }
+}
+
+/// Seed of one read of a pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PairedSeed {
+    /// Read the seed comes from.
+    pub mate: Mate,
+    /// Strand the read was seeded on: [`Strand::Reverse`] if its reverse
+    /// complement was seeded to bring it into fragment orientation.
+    pub strand: Strand,
+    /// The seed, with positions in the read as given.
+    pub seed: Strobemer,
+}
+
+/// Seed combining a read-1 seed with the anchor seed of read 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PairAnchor {
+    /// Combined hash of both seeds (read-1 seed first).
+    pub hash: u64,
+    /// Seed of read 1.
+    pub first: Strobemer,
+    /// Lowest-hash seed of read 2.
+    pub second: Strobemer,
+}
+
+/// Seeds of a read pair, as returned by [`PairedStrobes::seed`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PairSeeds {
+    /// Seeds of read 1, then of read 2.
+    pub mates: Vec<PairedSeed>,
+    /// Pair-anchored seeds; empty unless enabled with
+    /// [`with_pair_anchors`](PairedStrobes::with_pair_anchors).
+    pub anchors: Vec<PairAnchor>,
+}
+
+/// Joint seeding of paired-end reads.
+///
+/// Seeds both reads of a pair with the iterator described by a
+/// [`StrobeSpec`] and tags every seed with its [`Mate`] and strand. A read
+/// sequenced from the fragment's reverse strand (per the [`PairOrientation`],
+/// by default [`Fr`](PairOrientation::Fr)) is seeded on its reverse
+/// complement, so seeds of both mates share the orientation of the fragment;
+/// positions are always reported in the read as given.
+///
+/// Optionally, pair-anchored seeds are emitted too: every read-1 seed
+/// combined with the lowest-hash seed of read 2, so a hit identifies the
+/// pair rather than a single read.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{Mate, PairedStrobes, StrobeSpec, Strand};
+/// let spec: StrobeSpec = "rand2-k3-w3:5".parse().unwrap();
+/// let pair = PairedStrobes::new(&spec)
+///     .seed(b"ACGATCTGGTACCTAG", b"CTAGGTACCAGATCGT")
+///     .unwrap();
+/// let second = pair.mates.iter().find(|s| s.mate == Mate::Second).unwrap();
+/// assert_eq!(second.strand, Strand::Reverse);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairedStrobes {
+    spec: StrobeSpec,
+    orientation: PairOrientation,
+    anchors: bool, // Whether to emit pair-anchored seeds
+}
+
+impl PairedStrobes {
+    /// Creates a pair seeder for `spec`, expecting [`PairOrientation::Fr`] pairs.
+    pub fn new(spec: &StrobeSpec) -> Self {
+        Self {
+            spec: *spec,
+            orientation: PairOrientation::default(),
+            anchors: false,
+        }
+    }
+
+    /// Sets the library orientation of the pairs.
+    pub fn with_orientation(mut self, orientation: PairOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Enables or disables pair-anchored seeds (disabled by default).
+    pub fn with_pair_anchors(mut self, enable: bool) -> Self {
+        self.anchors = enable;
+        self
+    }
+
+    /// Seeds the pair `(read1, read2)`.
+    ///
+    /// A read too short for a single seed contributes none (and no anchors).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PairSeeds)` – Tagged seeds of both reads and, if enabled, pair anchors.
+    /// * `Err(StrobeError)` – If the spec's iterator rejects a read for another reason.
+    pub fn seed(&self, read1: &[u8], read2: &[u8]) -> Result<PairSeeds> {
+        let [s1, s2] = self.orientation.strands();
+        let first = self.seed_mate(read1, s1)?;
+        let second = self.seed_mate(read2, s2)?;
+
+        let mut anchors = Vec::new();
+        if let Some(anchor) = second.iter().min_by_key(|s| s.hash).filter(|_| self.anchors) {
+            anchors = first
+                .iter()
+                .map(|&s| PairAnchor {
+                    hash: LegacyCombiner.combine(&[s.hash, anchor.hash]),
+                    first: s,
+                    second: *anchor,
+                })
+                .collect();
+        }
+
+        let tag = |mate, strand| move |seed| PairedSeed { mate, strand, seed };
+        let mut mates: Vec<PairedSeed> = first.into_iter().map(tag(Mate::First, s1)).collect();
+        mates.extend(second.into_iter().map(tag(Mate::Second, s2)));
+        Ok(PairSeeds { mates, anchors })
+    }
+
+    /// Seeds `read` on `strand`, with positions in `read`.
+    fn seed_mate(&self, read: &[u8], strand: Strand) -> Result<Vec<Strobemer>> {
+        if strand == Strand::Forward {
+            return self.spec.seeds(read);
+        }
+        let mut seeds = self.spec.seeds(&reverse_complement(read))?;
+        let last = read.len().saturating_sub(self.spec.k);
+        for s in &mut seeds {
+            let order = s.order as usize;
+            s.positions[..order].iter_mut().for_each(|p| *p = last - *p);
+        }
+        Ok(seeds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAGMENT: &[u8] = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCATTGCAGGCTAAGCTTGCA";
+
+    #[test]
+    fn reverse_mate_matches_fragment_strand() {
+        let spec: StrobeSpec = "rand2-k3-w3:5".parse().unwrap();
+        let (read1, tail) = (&FRAGMENT[..30], &FRAGMENT[30..]);
+        let read2 = reverse_complement(tail);
+        let pair = PairedStrobes::new(&spec).seed(read1, &read2).unwrap();
+
+        let (first, second): (Vec<&PairedSeed>, Vec<_>) =
+            pair.mates.iter().partition(|s| s.mate == Mate::First);
+        let forward = spec.seeds(read1).unwrap();
+        assert_eq!(first.iter().map(|s| s.seed).collect::<Vec<_>>(), forward);
+        assert!(first.iter().all(|s| s.strand == Strand::Forward));
+
+        let fragment = spec.seeds(tail).unwrap();
+        assert_eq!(second.len(), fragment.len());
+        for (s, f) in second.iter().zip(&fragment) {
+            assert_eq!((s.strand, s.seed.hash), (Strand::Reverse, f.hash));
+            let last = tail.len() - 3;
+            assert_eq!(s.seed.strobes(), [last - f.positions[0], last - f.positions[1]]);
+        }
+        assert!(pair.anchors.is_empty());
+    }
+
+    #[test]
+    fn anchors_pair_each_first_seed_with_min_second_seed() {
+        let spec: StrobeSpec = "min2-k3-w3:5".parse().unwrap();
+        let seeder = PairedStrobes::new(&spec)
+            .with_orientation(PairOrientation::Ff)
+            .with_pair_anchors(true);
+        let pair = seeder.seed(&FRAGMENT[..30], &FRAGMENT[30..]).unwrap();
+        let first = spec.seeds(&FRAGMENT[..30]).unwrap();
+        let min = spec.seeds(&FRAGMENT[30..]).unwrap().into_iter().min_by_key(|s| s.hash);
+        let min = min.unwrap();
+        assert_eq!(pair.anchors.len(), first.len());
+        for (a, s) in pair.anchors.iter().zip(&first) {
+            assert_eq!((a.first, a.second), (*s, min));
+            assert_eq!(a.hash, LegacyCombiner.combine(&[s.hash, min.hash]));
+        }
+        assert!(seeder.seed(&FRAGMENT[..30], b"AC").unwrap().anchors.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::{Result, StrobeError, containment::sketch, randstrobes::RandStrobes};
+
+/// A seed of the target genome annotated with the number of genomes containing it.
+///
+/// Produced by [`annotate_genome_counts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnnotatedSeed {
+    /// Index of the target contig the seed was drawn from.
+    pub contig: usize,
+    /// Strobe start positions `[m1, m2, m3]` within the contig (`m3` is 0 for order 2).
+    pub positions: [usize; 3],
+    /// Strobemer hash.
+    pub hash: u64,
+    /// Number of genomes in which the same hash occurs.
+    pub genomes: usize,
+}
+
+impl AnnotatedSeed {
+    /// Returns `true` if the seed occurs in all `total` genomes (core genome).
+    pub fn is_core(&self, total: usize) -> bool {
+        self.genomes == total
+    }
+}
+
+/// Annotates every RandStrobe seed of `target` with the number of `genomes` containing it.
+///
+/// Each genome is a set of contigs and counts at most once per seed, so a seed
+/// with `genomes == genomes.len()` is core and anything less is accessory.
+/// Include the target in `genomes` if it should count towards the total.
+/// Contigs too short to produce a strobemer are skipped.
+///
+/// # Arguments
+///
+/// * `target` – Contigs of the genome to annotate.
+/// * `genomes` – Genomes to count occurrences in, each given as its contigs.
+/// * `n`, `k`, `w_min`, `w_max` – Strobemer parameters, as for [`RandStrobes::new`].
+///
+/// # Returns
+///
+/// * `Ok(Vec<AnnotatedSeed>)` – One entry per target seed, in contig then position order.
+/// * `Err(StrobeError)` if a record is invalid or the parameters are rejected.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::annotate_genome_counts;
+/// let a: &[u8] = b"ACGATCTGGTACCTAGGATTACACGATCGGATC";
+/// let b: &[u8] = b"ACGATCTGGTACCTAGGTTTTTTTTTTTTTTTT";
+/// let seeds = annotate_genome_counts(&[a], &[vec![a], vec![b]], 2, 3, 3, 5).unwrap();
+/// assert!(seeds.iter().any(|s| s.is_core(2)));
+/// assert!(seeds.iter().any(|s| !s.is_core(2)));
+/// ```
+pub fn annotate_genome_counts<T, G, S>(
+    target: &[T],
+    genomes: &[G],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+) -> Result<Vec<AnnotatedSeed>>
+where
+    T: AsRef<[u8]>,
+    G: AsRef<[S]>,
+    S: AsRef<[u8]>,
+{
+    // Number of genomes per distinct seed hash
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for genome in genomes {
+        for h in sketch(genome.as_ref(), n, k, w_min, w_max)? {
+            *counts.entry(h).or_default() += 1;
+        }
+    }
+
+    let mut out = Vec::new();
+    for (contig, seq) in target.iter().enumerate() {
+        let mut rs = match RandStrobes::new(seq.as_ref(), n, k, w_min, w_max) {
+            Ok(rs) => rs,
+            Err(StrobeError::SequenceTooShort) => continue,
+            Err(e) => return Err(e),
+        };
+        while let Some(hash) = rs.next() {
+            out.push(AnnotatedSeed {
+                contig,
+                positions: rs.indexes(),
+                hash,
+                genomes: counts.get(&hash).copied().unwrap_or(0),
+            });
+        }
+    }
+    Ok(out)
+}
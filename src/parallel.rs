@@ -0,0 +1,180 @@
+//! Rayon-parallel seeding (feature `rayon`).
+
+use rayon::prelude::*;
+
+use crate::{Protocol, Result, Scratch, StrobeError, StrobeSpec, Strobemer};
+
+impl StrobeSpec {
+    /// Collects the seeds of one long sequence, e.g. a chromosome, seeding
+    /// chunks of it in parallel on the rayon thread pool.
+    ///
+    /// First-strobe positions are split into chunks of about `chunk_len`
+    /// (rounded up to a multiple of the stride). Each chunk is seeded over a
+    /// slice extended by the reach of its windows, and keeps the seeds whose
+    /// first strobe lies in the chunk, so the result equals
+    /// [`seeds`](Self::seeds) on the whole sequence: same seeds, same order,
+    /// positions in `seq`. A tail too short to seed on its own is merged into
+    /// the chunk before it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Strobemer>)` – Seeds in iteration order.
+    /// * `Err(StrobeError)` – If the constructor rejects the sequence or spec.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::StrobeSpec;
+    /// let spec: StrobeSpec = "rand3-k5-w4:8".parse().unwrap();
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCGTAGCATGCAAGT".repeat(20);
+    /// assert_eq!(spec.par_seeds(&seq, 100).unwrap(), spec.seeds(&seq).unwrap());
+    /// ```
+    pub fn par_seeds(&self, seq: &[u8], chunk_len: usize) -> Result<Vec<Strobemer>> {
+        let links = self.order.saturating_sub(1) as usize;
+        // Bases a chunk's slice extends before its first and after its last anchor
+        let before = if self.canonical { links * self.w_max } else { 0 };
+        let after = (self.order as usize * self.k).max(self.k + links * self.w_max);
+        let shortest = (before + after).max(links * (self.w_max + 1));
+
+        let chunk = chunk_len.max(1).next_multiple_of(self.stride.max(1));
+        let mut starts: Vec<usize> = (before..seq.len()).step_by(chunk).collect();
+        while starts.len() > 1 && seq.len() - (starts[starts.len() - 1] - before) < shortest {
+            starts.pop();
+        }
+
+        let chunks: Vec<Vec<Strobemer>> = starts
+            .par_iter()
+            .enumerate()
+            .map(|(j, &lo)| {
+                let hi = starts.get(j + 1).copied().unwrap_or(seq.len());
+                let from = lo - before;
+                let to = if hi == seq.len() { hi } else { (hi + after).min(seq.len()) };
+                self.chunk_seeds(&seq[from..to], lo - from, hi - from, from)
+            })
+            .collect::<Result<_>>()?;
+        Ok(chunks.concat())
+    }
+
+    /// Returns the seeds of `slice` whose first strobe lies in `lo..hi`,
+    /// shifting positions by `from`, the slice's start in the sequence.
+    fn chunk_seeds(
+        &self,
+        slice: &[u8],
+        lo: usize,
+        hi: usize,
+        from: usize,
+    ) -> Result<Vec<Strobemer>> {
+        let seeds = match self.protocol {
+            Protocol::Rand => self.rand_strobes(slice).map(|mut rs| {
+                rs.seek(lo);
+                rs.iter_seeds().take_while(|s| s.positions[0] < hi).collect::<Vec<_>>()
+            }),
+            Protocol::Min => self.min_strobes(slice).map(|mut ms| {
+                ms.seek(lo);
+                ms.iter_seeds().take_while(|s| s.positions[0] < hi).collect::<Vec<_>>()
+            }),
+        };
+        let mut seeds = match seeds {
+            Err(StrobeError::SequenceTooShort) => Vec::new(),
+            seeds => seeds?,
+        };
+        for s in &mut seeds {
+            s.positions[..s.order as usize].iter_mut().for_each(|p| *p += from);
+        }
+        Ok(seeds)
+    }
+}
+
+/// Seeds many sequences in parallel on the rayon thread pool, calling
+/// `f(id, seed)` for every seed of every `(id, seq)` record.
+///
+/// Seeds are those of [`StrobeSpec::seeds`] on each record, positions being
+/// relative to the record; records too short for a single seed (or empty)
+/// get none. Each worker thread keeps a [`Scratch`], so construction stops
+/// allocating once its buffers have grown to the longest record. `f` sees
+/// the seeds of one record in order, but records interleave across threads.
+///
+/// # Returns
+///
+/// * `Ok(())` – Once every record has been seeded.
+/// * `Err(StrobeError)` – The error of a record the spec's iterator rejects
+///   (such as one with non-ASCII bytes); other records may still be seeded.
+///
+/// # Example
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use strobemers_rs::{StrobeSpec, par_for_each_sequence};
+/// let spec: StrobeSpec = "rand2-k3-w3:5".parse().unwrap();
+/// let records: &[(&str, &[u8])] = &[("r1", b"ACGATCTGGTACCTAG"), ("r2", b"ACG")];
+/// let count = AtomicUsize::new(0);
+/// par_for_each_sequence(records, &spec, |id, _seed| {
+///     assert_eq!(id, "r1");
+///     count.fetch_add(1, Ordering::Relaxed);
+/// })
+/// .unwrap();
+/// assert_eq!(count.into_inner(), spec.seeds(records[0].1).unwrap().len());
+/// ```
+pub fn par_for_each_sequence<'a, F>(
+    records: &[(&'a str, &[u8])],
+    spec: &StrobeSpec,
+    f: F,
+) -> Result<()>
+where
+    F: Fn(&'a str, Strobemer) + Sync,
+{
+    records.par_iter().try_for_each_init(Scratch::new, |scratch, &(id, seq)| {
+        spec.for_each_seed(seq, scratch, |seed| f(id, seed))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn chunks_stitch_to_whole_sequence_seeds() {
+        let mut state = 7u64;
+        let seq: Vec<u8> = (0..3000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 62) as usize]
+            })
+            .collect();
+        let specs =
+            ["rand2-k5-w3:9", "rand3-k4-w2:6-canon", "min3-k5-w4:8-step3", "rand3-k7-w1:3-xor"];
+        for spec in specs {
+            let spec: StrobeSpec = spec.parse().unwrap();
+            let whole = spec.seeds(&seq).unwrap();
+            for chunk in [1, 5, 64, 999, 5000] {
+                assert_eq!(spec.par_seeds(&seq, chunk).unwrap(), whole, "{spec} chunk={chunk}");
+                let short = &seq[..40];
+                assert_eq!(spec.par_seeds(short, chunk).unwrap(), spec.seeds(short).unwrap());
+            }
+        }
+    }
+    #[test]
+    fn visits_every_record_seed() {
+        let spec: StrobeSpec = "min3-k4-w2:6".parse().unwrap();
+        let records: Vec<(String, Vec<u8>)> = (0..40)
+            .map(|i| (format!("r{i}"), b"ACGATCTGGTACCTAGGATTACACG".repeat(i % 5)))
+            .collect();
+        let records: Vec<(&str, &[u8])> =
+            records.iter().map(|(id, seq)| (id.as_str(), seq.as_slice())).collect();
+        let seen = Mutex::new(Vec::new());
+        par_for_each_sequence(&records, &spec, |id, seed| seen.lock().unwrap().push((id, seed)))
+            .unwrap();
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort_by_key(|&(id, s)| (id, s.positions));
+        let mut expected: Vec<_> = records
+            .iter()
+            .flat_map(|&(id, seq)| spec.seeds(seq).unwrap().into_iter().map(move |s| (id, s)))
+            .collect();
+        expected.sort_by_key(|&(id, s)| (id, s.positions));
+        assert_eq!(seen, expected);
+
+        let bad: &[(&str, &[u8])] =
+            &[("ok", b"ACGATCTGGTACCTAG"), ("bad", "ACGTÄCGTACGTACGT".as_bytes())];
+        assert!(par_for_each_sequence(bad, &spec, |_, _| {}).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::scratch::Scratch;
+
+/// Integer type of the window-minimum locations stored by a
+/// [`MinTable`](crate::MinTable) and read by [`MinStrobes`](crate::MinStrobes).
+///
+/// `usize`, the default, fits any sequence. `u32` halves the memory of the
+/// locations (4 instead of 8 bytes per k-mer) for sequences of at most
+/// `u32::MAX` bases, i.e. any chromosome; build such tables with
+/// [`MinTable::with_u32_positions`](crate::MinTable::with_u32_positions) or
+/// [`MinStrobes::with_u32_positions`](crate::MinStrobes::with_u32_positions),
+/// which check the sequence length. Seeds and positions do not depend on the
+/// type.
+///
+/// The locations are the only per-k-mer position vector the iterators keep:
+/// [`RandStrobes`](crate::RandStrobes) selects from the hashes alone, and
+/// `indexes()` and [`Strobemer`](crate::Strobemer) hold the positions of one
+/// seed. Collected positions are narrowed to `u32` by `collect_soa`, which
+/// returns [`StrobeError::SequenceTooLong`](crate::StrobeError::SequenceTooLong)
+/// for sequences they would not fit.
+pub trait Position:
+    sealed::Sealed + Copy + Default + Eq + Ord + Hash + Debug + Send + Sync + 'static
+{
+    /// Length of the longest sequence whose positions fit.
+    const MAX_SEQ_LEN: usize;
+
+    /// Widens the position to `usize`.
+    fn to_usize(self) -> usize;
+}
+
+impl Position for usize {
+    const MAX_SEQ_LEN: usize = usize::MAX;
+
+    #[inline(always)]
+    fn to_usize(self) -> usize {
+        self
+    }
+}
+
+impl Position for u32 {
+    const MAX_SEQ_LEN: usize = u32::MAX as usize;
+
+    #[inline(always)]
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
+
+pub(crate) mod sealed {
+    use crate::scratch::Scratch;
+
+    /// Crate-internal half of [`Position`](super::Position).
+    pub trait Sealed: Sized {
+        /// Narrows `p`, which the table's length check guarantees to fit.
+        fn from_usize(p: usize) -> Self;
+
+        /// Returns the buffer of `scratch` holding locations of this type.
+        fn locations(scratch: &mut Scratch) -> &mut Vec<Self>;
+    }
+}
+
+impl sealed::Sealed for usize {
+    #[inline(always)]
+    fn from_usize(p: usize) -> Self {
+        p
+    }
+
+    fn locations(scratch: &mut Scratch) -> &mut Vec<Self> {
+        &mut scratch.minloc
+    }
+}
+
+impl sealed::Sealed for u32 {
+    #[inline(always)]
+    fn from_usize(p: usize) -> Self {
+        debug_assert!(p <= u32::MAX as usize);
+        p as u32
+    }
+
+    fn locations(scratch: &mut Scratch) -> &mut Vec<Self> {
+        &mut scratch.minloc32
+    }
+}
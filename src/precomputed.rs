@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use crate::{
+    MinStrobes, RandStrobes, Result, StrobeError,
+    ambiguity::hasher_validity,
+    hashes::{KmerHasher, NtHash64},
+    mintable::MinTable,
+    scratch::Scratch,
+};
+
+/// K-mer hashes of one sequence, computed once and shared by the iterators
+/// of every protocol built over it.
+///
+/// Comparing protocols or parameters on a sequence otherwise re-hashes it
+/// for each iterator. Build the hashes once, optionally with the window
+/// minima of one window range, then construct [`RandStrobes`] and
+/// [`MinStrobes`] of any order and windows from them. Seeds equal those of
+/// the `with_hasher` constructors with the same hasher; the
+/// [`validity`](KmerHasher::validity) mask of the hasher is kept as well.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{MinStrobes, PrecomputedHashes, RandStrobes};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let pre = PrecomputedHashes::new(seq, 3).unwrap().with_window_minima(3, 5).unwrap();
+/// let rand: Vec<u64> = pre.rand_strobes(2, 3, 5).unwrap().collect();
+/// let min: Vec<u64> = pre.min_strobes(3, 3, 5).unwrap().collect();
+/// assert_eq!(rand, RandStrobes::new(seq, 2, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+/// assert_eq!(min, MinStrobes::new(seq, 3, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrecomputedHashes {
+    seq_len: usize,                // Length of the hashed sequence
+    k: usize,                      // k-mer length
+    hashes: Vec<u64>,              // Hash of each k-mer, by start position
+    valid: Option<Vec<bool>>,      // The hasher's validity mask, if any k-mer is invalid
+    minima: Option<Arc<MinTable>>, // Window minima, if computed
+}
+
+impl PrecomputedHashes {
+    /// Hashes `seq` using the default hash function (`NtHash64`).
+    ///
+    /// See [`PrecomputedHashes::with_hasher`] for details.
+    pub fn new(seq: &[u8], k: usize) -> Result<Self> {
+        Self::with_hasher(seq, k, &NtHash64)
+    }
+
+    /// Hashes every k-mer of `seq` with a user-defined hash function.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PrecomputedHashes)` – The hashes of the `seq.len() − k + 1` k-mers.
+    /// * `Err(StrobeError::InvalidSequence)` – If `seq` is empty.
+    /// * `Err(StrobeError::StrobeLengthTooSmall)` – If `k` is not in `1..=64`.
+    /// * `Err(StrobeError::SequenceTooShort)` – If `seq` is shorter than `k`.
+    /// * `Err(StrobeError)` – If the hasher rejects `seq` or fails.
+    pub fn with_hasher<H>(seq: &[u8], k: usize, hasher: &H) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        if seq.is_empty() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        hasher.validate_sequence(seq)?;
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+        let hashes = hasher.hash_all(seq, k)?;
+        let valid = hasher_validity(hasher, seq, k, hashes.len())?;
+        Ok(Self { seq_len: seq.len(), k, hashes, valid, minima: None })
+    }
+
+    /// Computes the sliding-window minima for windows `w_min..=w_max`, which
+    /// every [`min_strobes`](Self::min_strobes) with these offsets then shares.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PrecomputedHashes)` – With the minima; earlier minima are replaced.
+    /// * `Err(StrobeError)` – If the offsets or the sequence length do not
+    ///   suit order 2, as for [`MinTable::with_hasher`].
+    pub fn with_window_minima(mut self, w_min: usize, w_max: usize) -> Result<Self> {
+        validate_params!(@len self.seq_len, 2, self.k, w_min, w_max);
+        self.minima = Some(Arc::new(self.min_table(w_min, w_max)));
+        Ok(self)
+    }
+
+    /// Returns the k-mer length.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the length of the hashed sequence.
+    pub fn seq_len(&self) -> usize {
+        self.seq_len
+    }
+
+    /// Returns the hash of every k-mer, by start position.
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// Returns the window minima, if computed by
+    /// [`with_window_minima`](Self::with_window_minima).
+    pub fn window_minima(&self) -> Option<&Arc<MinTable>> {
+        self.minima.as_ref()
+    }
+
+    /// Builds a [`RandStrobes`] iterator over the hashes, as
+    /// [`RandStrobes::with_hasher`] would on the sequence.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – Ready-to-use iterator; it owns a copy of the hashes.
+    /// * `Err(StrobeError)` – On invalid parameters or a sequence too short for them.
+    pub fn rand_strobes(&self, n: u8, w_min: usize, w_max: usize) -> Result<RandStrobes> {
+        validate_params!(@len self.seq_len, n, self.k, w_min, w_max);
+        let (hashes, valid) = (self.hashes.clone(), self.valid.clone());
+        let (seq_len, k) = (self.seq_len, self.k);
+        Ok(RandStrobes::from_strand_hashes(seq_len, n, k, w_min, w_max, hashes, valid, false))
+    }
+
+    /// Builds a [`MinStrobes`] iterator over the hashes, as
+    /// [`MinStrobes::with_hasher`] would on the sequence.
+    ///
+    /// Shares the window minima if they were computed for `w_min..=w_max`,
+    /// and computes them otherwise.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError)` – On invalid parameters or a sequence too short for them.
+    pub fn min_strobes(&self, n: u8, w_min: usize, w_max: usize) -> Result<MinStrobes> {
+        validate_params!(@len self.seq_len, n, self.k, w_min, w_max);
+        let table = match &self.minima {
+            Some(table) if table.window() == (w_min, w_max) => Arc::clone(table),
+            _ => Arc::new(self.min_table(w_min, w_max)),
+        };
+        MinStrobes::from_table(table, n)
+    }
+
+    /// Computes the window minima for validated offsets.
+    fn min_table(&self, w_min: usize, w_max: usize) -> MinTable {
+        let (hashes, valid) = (self.hashes.clone(), self.valid.clone());
+        let scratch = &mut Scratch::new();
+        MinTable::from_hashes(self.seq_len, self.k, w_min, w_max, hashes, valid, scratch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KmerCodeHasher;
+
+    #[test]
+    fn iterators_match_hashing_constructors() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC".repeat(4);
+        let pre = PrecomputedHashes::with_hasher(&seq, 4, &KmerCodeHasher).unwrap();
+        let pre = pre.with_window_minima(2, 7).unwrap();
+        for n in [2, 3] {
+            for (w_min, w_max) in [(2, 7), (0, 12)] {
+                let rand = RandStrobes::with_hasher(&seq, n, 4, w_min, w_max, &KmerCodeHasher);
+                let rand: Vec<_> = rand.unwrap().iter_seeds().collect();
+                let pre_rand = pre.rand_strobes(n, w_min, w_max).unwrap();
+                assert_eq!(pre_rand.iter_seeds().collect::<Vec<_>>(), rand);
+                let min = MinStrobes::with_hasher(&seq, n, 4, w_min, w_max, &KmerCodeHasher);
+                let min: Vec<_> = min.unwrap().iter_seeds().collect();
+                let pre_min = pre.min_strobes(n, w_min, w_max).unwrap();
+                let shared = Arc::ptr_eq(pre_min.table(), pre.window_minima().unwrap());
+                assert_eq!(shared, (w_min, w_max) == (2, 7));
+                assert_eq!(pre_min.iter_seeds().collect::<Vec<_>>(), min);
+            }
+        }
+        assert_eq!(pre.rand_strobes(4, 2, 7).err(), Some(StrobeError::OrderNotSupported));
+        assert_eq!(pre.min_strobes(2, 2, 200).err(), Some(StrobeError::SequenceTooShort));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    Result, StrobeError,
+    alphabet::{UNKNOWN, class_hashes, class_validity},
+    hashes::KmerHasher,
+};
+
+/// The 20 standard amino acids.
+const AMINO_ACIDS: &str = "ACDEFGHIKLMNPQRSTVWY";
+
+/// Grouping of amino acids into classes that hash identically.
+///
+/// Reduced alphabets merge residues with similar physico-chemical properties,
+/// so k-mers differing only by conservative substitutions share a hash,
+/// which helps seeding remote protein homologs. Matching is case-insensitive;
+/// bytes in no group (e.g. `X`, `*`) are unknown.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerHasher, ProteinHasher, ReducedAlphabet};
+/// let hasher = ProteinHasher::reduced(ReducedAlphabet::dayhoff());
+/// // A, G, P, S and T form one Dayhoff class
+/// let hashes = hasher.hash_all(b"AGWTSW", 2).unwrap();
+/// assert_eq!(hashes[0], hashes[3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReducedAlphabet {
+    classes: [u8; 256],
+}
+
+impl ReducedAlphabet {
+    /// The 20 standard amino acids, each its own class.
+    pub fn amino_acids() -> Self {
+        let groups: Vec<String> = AMINO_ACIDS.chars().map(String::from).collect();
+        Self::from_groups(&groups).expect("valid built-in alphabet")
+    }
+
+    /// Dayhoff's 6 classes: `AGPST`, `C`, `DENQ`, `FWY`, `HKR`, `ILMV`.
+    pub fn dayhoff() -> Self {
+        Self::from_groups(&["AGPST", "C", "DENQ", "FWY", "HKR", "ILMV"])
+            .expect("valid built-in alphabet")
+    }
+
+    /// Murphy et al.'s 10 classes: `A`, `C`, `DENQ`, `FWY`, `G`, `H`, `ILMV`,
+    /// `KR`, `P`, `ST`.
+    pub fn murphy10() -> Self {
+        let groups = ["A", "C", "DENQ", "FWY", "G", "H", "ILMV", "KR", "P", "ST"];
+        Self::from_groups(&groups).expect("valid built-in alphabet")
+    }
+
+    /// Builds an alphabet from groups of residues, one class per group.
+    ///
+    /// Any ASCII letters may be grouped, so other published alphabets (e.g.
+    /// HSDM) can be supplied as their group lists.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReducedAlphabet)` – On success.
+    /// * `Err(StrobeError::InvalidAlphabet)` – If there are no groups or more than
+    ///   255, a group is empty, a residue is not an ASCII letter, or a residue
+    ///   appears twice.
+    pub fn from_groups<S: AsRef<str>>(groups: &[S]) -> Result<Self> {
+        if groups.is_empty() || groups.len() >= UNKNOWN as usize {
+            return Err(StrobeError::InvalidAlphabet);
+        }
+        let mut classes = [UNKNOWN; 256];
+        for (class, group) in groups.iter().enumerate() {
+            let group = group.as_ref().as_bytes();
+            if group.is_empty() {
+                return Err(StrobeError::InvalidAlphabet);
+            }
+            for &b in group {
+                let upper = b.to_ascii_uppercase();
+                if !b.is_ascii_alphabetic() || classes[upper as usize] != UNKNOWN {
+                    return Err(StrobeError::InvalidAlphabet);
+                }
+                classes[upper as usize] = class as u8;
+                classes[upper.to_ascii_lowercase() as usize] = class as u8;
+            }
+        }
+        Ok(Self { classes })
+    }
+
+    /// Returns the class of `b`, or `None` if `b` is in no group.
+    pub fn class(&self, b: u8) -> Option<u8> {
+        let class = self.classes[b as usize];
+        (class != UNKNOWN).then_some(class)
+    }
+}
+
+impl Default for ReducedAlphabet {
+    fn default() -> Self {
+        Self::amino_acids()
+    }
+}
+
+/// Rolling k-mer hasher for amino-acid sequences.
+///
+/// Maps each residue to its class in a [`ReducedAlphabet`] (by default the
+/// 20 standard amino acids), then hashes k-mers of classes with a cyclic
+/// polynomial rolling hash and a final mixing step, in O(n) for any `k`.
+/// K-mers containing a residue outside the alphabet are reported invalid
+/// through [`validity`](KmerHasher::validity), so iterators never seed them.
+///
+/// Protein sequences have no reverse complement; use the forward-strand
+/// constructors (`with_hasher`) only.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{ProteinHasher, RandStrobes};
+/// let seq = b"MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQAPILSRVGDGTQDNLSGAEKAVQVKVKALPDAQ";
+/// let rs = RandStrobes::with_hasher(seq, 2, 4, 4, 8, &ProteinHasher::new()).unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProteinHasher {
+    alphabet: ReducedAlphabet,
+}
+
+impl ProteinHasher {
+    /// Hashes the 20 standard amino acids, each distinct.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hashes residues through `alphabet`, so residues of a class are interchangeable.
+    pub fn reduced(alphabet: ReducedAlphabet) -> Self {
+        Self { alphabet }
+    }
+}
+
+impl KmerHasher for ProteinHasher {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        class_hashes(seq, k, |b| self.alphabet.class(b))
+    }
+
+    fn validity(&self, seq: &[u8], k: usize) -> Option<Vec<bool>> {
+        class_validity(seq, k, |b| self.alphabet.class(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_matches_direct_hashing() {
+        let seq = b"MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQAPILSRVGDGTQDNLSGAEKAVQVKVKALPDAQ";
+        let hasher = ProteinHasher::new();
+        for k in [1, 5, 64] {
+            let hashes = hasher.hash_all(seq, k).unwrap();
+            for (i, &h) in hashes.iter().enumerate() {
+                assert_eq!(h, hasher.hash_all(&seq[i..i + k], k).unwrap()[0]);
+            }
+        }
+        let lower = hasher.hash_all(b"mktay", 3).unwrap();
+        assert_eq!(lower, hasher.hash_all(b"MKTAY", 3).unwrap());
+    }
+
+    #[test]
+    fn unknown_residues_invalidate_kmers() {
+        let hasher = ProteinHasher::new();
+        assert_eq!(hasher.validity(b"MKTAY", 2), None);
+        assert_eq!(hasher.validity(b"MKXAY*", 2), Some(vec![true, false, false, true, false]));
+    }
+
+    #[test]
+    fn rejects_malformed_groups() {
+        let err = Err(StrobeError::InvalidAlphabet);
+        assert_eq!(ReducedAlphabet::from_groups(&["AG", "ga"]), err);
+        assert_eq!(ReducedAlphabet::from_groups(&["AG", ""]), err);
+        assert_eq!(ReducedAlphabet::from_groups(&["A*"]), err);
+        assert_eq!(ReducedAlphabet::from_groups::<&str>(&[]), err);
+    }
+}
@@ -1,8 +1,31 @@
+use std::{collections::HashMap, io::Read, iter::FusedIterator, mem, ops::Range, sync::Arc};
+
 use crate::{
     Result, StrobeError,
+    ambiguity::{
+        AmbiguityPolicy, hasher_validity, merge_validity, quality_penalties, quality_validity,
+    },
+    combine::{CombineScheme, Combiner, StrobeCombiner, SymmetricCombiner},
     constants::DEFAULT_PRIME_NUMBER,
-    hashes::{KmerHasher, NtHash64},
-    util::roundup64,
+    explain::{SeedExplanation, SeedVerdict},
+    gcdensity::GcDensity,
+    hashes::{KmerHasher, NtHash64, canonical_hashes, reverse_is_canonical},
+    intervals::kmers_overlapping,
+    informative::{AbundanceSource, top_informative},
+    ringstrobes::StreamingRandStrobes,
+    scratch::Scratch,
+    selection::{
+        MaskedSum, PerformanceMode, SelectionStrategy, TieBreak, WindowAnchor, lane_min,
+        secondary_key,
+    },
+    softmask::{SoftMask, soft_masked_kmers},
+    spaced::{hash_all_spaced, parse_mask},
+    spec::Protocol,
+    strand::Strand,
+    stream::ReaderStrobes,
+    streaming::StreamingKmerHasher,
+    strobemer::{SeedColumns, Strobemer, Strobemer128, strobe_bytes},
+    util::{reverse_complement, roundup64},
 };
 
 /// Iterator for generating RandStrobes of order 2 or 3 from a DNA/RNA sequence.
@@ -11,21 +34,28 @@ use crate::{
 /// position that minimizes `(base_hash + candidate_hash) & prime`. This approach
 /// provides a pseudo-random yet deterministic selection of k-mers within sliding windows.
 ///
+/// The scoring rule is pluggable through the `S` parameter (see [`SelectionStrategy`]);
+/// the default [`MaskedSum`] reproduces the original behavior.
+///
 #[derive(Debug, Clone)]
-pub struct RandStrobes {
+pub struct RandStrobes<S = MaskedSum> {
     // Parameters controlling strobemer generation
     n: u8,        // Order of strobemer: 2 or 3
-    _k: usize,    // k-mer length (only needed during construction)
+    k: usize,     // k-mer length
     w_min: usize, // Minimum window offset
     w_max: usize, // Maximum window offset
 
     // Precomputed data
-    hashes: Vec<u64>, // Hash values for each k-mer in the sequence
+    hashes: Vec<u64>,           // Hash values for each k-mer in the sequence
+    link_hashes: Vec<Vec<u64>>, // Separate hashes for m2 (and m3) when strobes use their own masks
+    seq: Option<Arc<[u8]>>,     // Input sequence, kept only by `from_owned`
 
     // Iteration state
     idx: usize,      // Current index of the first k-mer (m1)
     end_idx: usize,  // Last index at which a complete strobemer can start
     end_hash: usize, // Last index in `hashes` (i.e., sequence length minus k)
+    back: usize,     // One past the last m1 not yet consumed by `next_back`
+    from_back: bool, // Whether the current item came from `next_back`
 
     // Strobe indices for current item
     idx2: usize, // Index of second k-mer (m2)
@@ -34,11 +64,51 @@ pub struct RandStrobes {
     // Prime number and shrink-window flag
     prime: u64, // Used for mask-based combination: `(base_hash + candidate_hash) & prime`
     shrink: bool, // Whether to shrink windows near the end if the full window does not fit
+    max_span: usize, // Cap on the distance from m1's start to the last strobe's end
+    min_span: usize, // Floor on that distance, raising the start of the last window
+    anchor: WindowAnchor, // Whether m3's window is placed relative to m1 or to m2
 
     // Working registers for hash values
     h1: u64, // Hash of first k-mer (m1)
     h2: u64, // Combined hash after selecting m2
     h3: u64, // Combined hash after selecting m3 (order 3 only)
+
+    // Scoring rule used by `choose_min`
+    strategy: S,
+    distance_penalty: u64, // Added to a candidate's score per base of offset inside the window
+    step: usize,           // Distance between scored candidates (see `set_performance_mode`)
+    qual_penalty: Option<Vec<u64>>, // Added to a k-mer's score (see `set_quality_weights`)
+    tie: TieBreak,                  // Which of equally scored candidates wins
+
+    // Ambiguity flagging
+    margin: Option<u64>, // Winner/runner-up score gap at or below which a seed is unstable
+    unstable: bool,      // Whether the current item was flagged unstable
+
+    // Strand-neutral mode: each seed is the smaller of the downstream and mirrored upstream seed
+    canonical: bool,
+    kmer_rev: Vec<bool>, // Whether each k-mer's canonical form is its reverse complement
+    strand: Option<Strand>, // Orientation of the current canonical seed
+
+    // Seeds the reverse complement; reported positions are mapped back to the input
+    revcomp: bool,
+    offset: usize, // Input position of the first hashed base (see `new_in_region`)
+
+    // Replaces the built-in combination of the emitted hash (see `set_combiner`)
+    combiner: Option<Combiner>,
+    scheme: CombineScheme, // Built-in combination used without a combiner
+
+    // Output thinning
+    first_mask: Option<Vec<bool>>, // Allowed m1 positions (see `set_first_strobe_positions`)
+    valid: Option<Vec<bool>>,      // k-mers free of ambiguous bases (see `with_ambiguity_policy`)
+    soft_mask: Option<Vec<bool>>,  // Entirely lowercase k-mers (see `set_soft_mask`)
+    soft_any: bool,                // Whether any soft-masked strobe drops the seed
+    excluded: Option<Vec<bool>>,   // k-mers intersecting masked intervals
+    stride: usize,                 // Distance between consecutive m1 positions (see `set_stride`)
+    threshold: u64,                // Only hashes ≤ threshold are emitted (density thinning)
+    gc_density: Option<GcDensity>, // Per-GC-bin thresholds replacing `threshold`
+
+    // Occurrences of each k-mer hash within the sequence (see `set_local_counts`)
+    local_counts: Option<HashMap<u64, u32>>,
 }
 
 impl RandStrobes {
@@ -76,6 +146,244 @@ impl RandStrobes {
         Self::with_hasher(seq, n, k, w_min, w_max, &NtHash64)
     }
 
+    /// Seeds a sequence read incrementally from `reader`.
+    ///
+    /// The sequence is consumed in chunks, so neither it nor its full hash
+    /// vector has to fit in memory; seeds and positions match [`RandStrobes::new`]
+    /// on the whole sequence. See [`ReaderStrobes`] for the input format.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ReaderStrobes)` – Iterator of `Result<Strobemer>`; read errors are
+    ///   yielded as [`StrobeError::Io`].
+    /// * `Err(StrobeError)` – If `n`, `k` or the window offsets are invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = "ACGATCTGGT\nACCTAGGATT\nACACG\n";
+    /// let seeds: Vec<_> = RandStrobes::from_reader(seq.as_bytes(), 2, 3, 3, 5)
+    ///     .unwrap()
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// let whole = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 3, 5).unwrap();
+    /// assert_eq!(seeds, whole.iter_seeds().collect::<Vec<_>>());
+    /// ```
+    pub fn from_reader<R: Read>(
+        reader: R,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<ReaderStrobes<R>> {
+        ReaderStrobes::new(reader, Protocol::Rand, n, k, w_min, w_max)
+    }
+
+    /// Seeds `seq` while holding only the k-mer hashes its windows still need.
+    ///
+    /// Hashes are streamed from `hasher` into a ring buffer of O(w_max)
+    /// values instead of a vector of one hash per base; seeds and positions
+    /// match [`RandStrobes::with_hasher`] with default settings. See
+    /// [`StreamingRandStrobes`] for the details.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StreamingRandStrobes)` – Iterator of `Result<Strobemer>`.
+    /// * `Err(StrobeError)` – As for [`RandStrobes::with_hasher`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{NtHash64, RandStrobes};
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let seeds: Vec<_> = RandStrobes::streaming(seq, 3, 3, 2, 4, &NtHash64)?
+    ///     .collect::<Result<_, _>>()?;
+    /// let whole = RandStrobes::new(seq, 3, 3, 2, 4)?;
+    /// assert_eq!(seeds, whole.iter_seeds().collect::<Vec<_>>());
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn streaming<'a, H>(
+        seq: &'a [u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &'a H,
+    ) -> Result<StreamingRandStrobes<'a, H>>
+    where
+        H: KmerHasher + StreamingKmerHasher,
+    {
+        StreamingRandStrobes::new(seq, n, k, w_min, w_max, hasher)
+    }
+
+    /// Constructs a [`RandStrobes`] iterator that keeps its own copy of `seq`.
+    ///
+    /// Seeds are identical to [`RandStrobes::new`]. The sequence stays
+    /// available through [`sequence`](Self::sequence), so strobe bases can be
+    /// extracted with [`seed_bytes`](Self::seed_bytes) after the iterator has
+    /// been moved to another thread or task. Passing an `Arc<[u8]>` shares the
+    /// sequence without copying it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – Iterator owning the sequence.
+    /// * `Err(StrobeError)` – Returned if parameters are invalid or the sequence is too short.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let mut rs = RandStrobes::from_owned(b"ACGATCTGGTACCTAG".to_vec(), 2, 3, 3, 5).unwrap();
+    /// let bases = std::thread::spawn(move || {
+    ///     rs.next().unwrap();
+    ///     rs.seed_bytes(rs.sequence().unwrap())
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// assert_eq!(bases.len(), 6);
+    /// ```
+    pub fn from_owned<T: Into<Arc<[u8]>>>(
+        seq: T,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        let seq = seq.into();
+        let mut rs = Self::new(&seq, n, k, w_min, w_max)?;
+        rs.seq = Some(seq);
+        Ok(rs)
+    }
+
+    /// Constructs a [`RandStrobes`] iterator over `seq[region]` that reports
+    /// positions in `seq`.
+    ///
+    /// Seeds are those of [`RandStrobes::new`] on the region alone: windows do
+    /// not reach past its end. All positions the iterator reports or accepts
+    /// ([`index`](Self::index), [`indexes`](Self::indexes), `seek`,
+    /// `set_first_strobe_positions`, `explain`, `seed_bytes`) are coordinates
+    /// in `seq`, so seeds of adjacent regions processed in parallel can be
+    /// merged directly. `set_gc_density` takes the region's bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – Ready-to-use iterator over the region.
+    /// * `Err(StrobeError::InvalidRegion)` – If `region` is reversed or exceeds `seq`.
+    /// * `Err(StrobeError)` – As for [`RandStrobes::new`] on the region.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let mut rs = RandStrobes::new_in_region(seq, 5..20, 2, 3, 3, 5).unwrap();
+    /// rs.next().unwrap();
+    /// assert_eq!(rs.index(), Some(5));
+    /// ```
+    pub fn new_in_region(
+        seq: &[u8],
+        region: Range<usize>,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        let start = region.start;
+        let sub = seq.get(region).ok_or(StrobeError::InvalidRegion)?;
+        let mut rs = Self::new(sub, n, k, w_min, w_max)?;
+        rs.offset = start;
+        Ok(rs)
+    }
+
+    /// Constructs a [`RandStrobes`] iterator handling ambiguous bases (e.g. `N`)
+    /// according to `policy`.
+    ///
+    /// With [`AmbiguityPolicy::Hasher`] this is [`RandStrobes::with_hasher`].
+    /// Under [`AmbiguityPolicy::SkipAffectedSeeds`] no seed starts at, or picks
+    /// as a later strobe, a k-mer overlapping an ambiguous base; such k-mers are
+    /// hashed as if the base were `A` so every position keeps a hash.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::AmbiguousBase)` – Under [`AmbiguityPolicy::Error`], for
+    ///   the first ambiguous base.
+    /// * `Err(StrobeError)` – As for [`RandStrobes::with_hasher`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{AmbiguityPolicy, RandStrobes, NtHash64};
+    /// let seq = b"ACGATCTGGTNCCTAGGATTACACG";
+    /// let policy = AmbiguityPolicy::SkipAffectedSeeds;
+    /// let mut it =
+    ///     RandStrobes::with_ambiguity_policy(seq, 2, 3, 3, 5, &NtHash64, policy).unwrap();
+    /// while it.next().is_some() {
+    ///     let [m1, m2, _] = it.indexes();
+    ///     assert!(!seq[m1..m1 + 3].contains(&b'N') && !seq[m2..m2 + 3].contains(&b'N'));
+    /// }
+    /// ```
+    pub fn with_ambiguity_policy<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+        policy: AmbiguityPolicy,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        validate_params!(seq, n, k, w_min, w_max);
+
+        let (seq, valid) = policy.apply(seq, k)?;
+        let mut rs = Self::with_hasher(&seq, n, k, w_min, w_max, hasher)?;
+        rs.valid = merge_validity(rs.valid.take(), valid);
+        Ok(rs)
+    }
+
+    /// Constructs a [`RandStrobes`] iterator that skips low-quality bases.
+    ///
+    /// `qual` holds one Phred+33 quality character per base of `seq`, as in a
+    /// FASTQ record. K-mers containing a base below `min_qual` are treated
+    /// like ambiguous k-mers under [`AmbiguityPolicy::SkipAffectedSeeds`]: no
+    /// seed starts at them and window selection never picks them. The
+    /// caller's buffers are left untouched.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `qual` and `seq` differ in length.
+    /// * `Err(StrobeError)` – As for [`RandStrobes::new`].
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let qual = b"IIIIIIIIII#IIIIIIIIIIIIII";
+    /// let mut it = RandStrobes::with_quality_mask(seq, qual, 20, 2, 3, 3, 5).unwrap();
+    /// while it.next().is_some() {
+    ///     let [m1, m2, _] = it.indexes();
+    ///     assert!(!(m1..m1 + 3).contains(&10) && !(m2..m2 + 3).contains(&10));
+    /// }
+    /// ```
+    pub fn with_quality_mask(
+        seq: &[u8],
+        qual: &[u8],
+        min_qual: u8,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        validate_params!(seq, n, k, w_min, w_max);
+        if qual.len() != seq.len() {
+            return Err(StrobeError::InvalidSequence);
+        }
+
+        let mut rs = Self::with_hasher(seq, n, k, w_min, w_max, &NtHash64)?;
+        let valid = Some(quality_validity(qual, k, min_qual));
+        rs.valid = merge_validity(rs.valid.take(), valid);
+        Ok(rs)
+    }
+
     /// Constructs a new [`RandStrobes`] iterator using a user-defined k-mer hash function.
     ///
     /// This method enables **dependency injection** of the hashing algorithm via the [`KmerHasher`] trait.
@@ -92,7 +400,7 @@ impl RandStrobes {
     /// * `seq` – Input DNA/RNA sequence as ASCII bytes.
     /// * `n` – Order of the strobemer (must be 2 or 3).
     /// * `k` – Length of each strobe (k-mer), within the inclusive range [1, 64].
-    /// * `w_min` – Minimum offset for the search window (must be ≥ 1).
+    /// * `w_min` – Minimum offset for the search window (0 allows m2 to overlap m1).
     /// * `w_max` – Maximum offset (inclusive); must satisfy `w_min ≤ w_max`.
     /// * `hasher` – Reference to a [`KmerHasher`] implementation for computing all k-mer hashes.
     ///
@@ -126,212 +434,1965 @@ impl RandStrobes {
         hasher: &H,
     ) -> Result<Self>
     where
-        H: KmerHasher,
+        H: KmerHasher + ?Sized,
     {
-        // Ensure all parameters are valid before proceeding
-        validate_params!(seq, n, k, w_min, w_max);
-
-        // Precompute hash values for all valid k-mers
-        let hashes = hasher.hash_all(seq, k)?;
-
-        // Calculate the valid iteration bounds
-        let end_hash = seq.len().saturating_sub(k); // maximum hash index
-        let end_idx = seq.len().saturating_sub(k + (n as usize - 1) * k); // max starting index for m₁
-
-        Ok(Self {
-            n,
-            _k: k,
-            w_min,
-            w_max,
-            hashes,
-            idx: 0,
-            end_idx,
-            end_hash,
-            idx2: 0,
-            idx3: 0,
-            prime: DEFAULT_PRIME_NUMBER,
-            shrink: true,
-            h1: 0,
-            h2: 0,
-            h3: 0,
-        })
+        Self::with_scratch(seq, n, k, w_min, w_max, hasher, &mut Scratch::new())
     }
 
-    /// Sets a new prime number for combining hash values.
+    /// Constructs a new [`RandStrobes`] iterator with a hasher chosen at runtime.
     ///
-    /// The formula used is `(base_hash + candidate_hash) & prime`. The provided `q` must
-    /// be at least 256. Internally, `q` is rounded up to the next power of two and then
-    /// decremented by one to form a Mersenne prime.
+    /// Same as [`RandStrobes::with_hasher`], for a hasher behind a trait object, e.g. one
+    /// picked from a command-line flag.
     ///
-    /// # Arguments
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerCodeHasher, KmerHasher, RandStrobes, NtHash64};
+    /// let name = "kmer-code";
+    /// let hasher: Box<dyn KmerHasher> = match name {
+    ///     "kmer-code" => Box::new(KmerCodeHasher),
+    ///     _ => Box::new(NtHash64),
+    /// };
+    /// let seeds = RandStrobes::with_dyn_hasher(b"ACGATCTGGTACCTAG", 2, 3, 3, 5, &*hasher)?;
+    /// assert!(seeds.count() > 0);
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn with_dyn_hasher(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &dyn KmerHasher,
+    ) -> Result<Self> {
+        Self::with_hasher(seq, n, k, w_min, w_max, hasher)
+    }
+
+    /// Constructs a new [`RandStrobes`] iterator over caller-supplied k-mer hashes.
     ///
-    /// * `q` – Candidate prime (will be rounded to Mersenne form: `2^k − 1`).
+    /// `hashes[i]` is the hash of the k-mer starting at `i`, e.g. from the
+    /// caller's own hashing pass or loaded from disk, so the sequence of
+    /// `hashes.len() + k − 1` bases is not hashed again. Seeds equal those of
+    /// [`RandStrobes::with_hasher`] with a hasher producing these hashes. The hashes
+    /// are copied.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` – If `q` ≥ 256, updates `self.prime`.
-    /// * `Err(StrobeError::PrimeNumberTooSmall)` – If `q` < 256.
-    pub fn set_prime(&mut self, q: u64) -> Result<()> {
-        if q < 256 {
-            return Err(StrobeError::PrimeNumberTooSmall);
+    /// * `Ok(RandStrobes)` – Ready-to-use iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `hashes` is empty.
+    /// * `Err(StrobeError)` – On invalid parameters or too few hashes for them.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerHasher, NtHash64, RandStrobes};
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let hashes = NtHash64.hash_all(seq, 3)?;
+    /// let seeds: Vec<u64> = RandStrobes::from_hashes(&hashes, 2, 3, 3, 5)?.collect();
+    /// assert_eq!(seeds, RandStrobes::new(seq, 2, 3, 3, 5)?.collect::<Vec<u64>>());
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn from_hashes(
+        hashes: &[u64],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        if hashes.is_empty() {
+            return Err(StrobeError::InvalidSequence);
         }
-        // Round up to next power of two, subtract one → Mersenne prime form
-        self.prime = roundup64(q) - 1;
-        Ok(())
+        let seq_len = hashes.len() + k.saturating_sub(1);
+        validate_params!(@len seq_len, n, k, w_min, w_max);
+        Ok(Self::from_strand_hashes(seq_len, n, k, w_min, w_max, hashes.to_vec(), None, false))
     }
 
-    /// Enables or disables window shrinking at the sequence end.
+    /// Constructs a new [`RandStrobes`] iterator, taking its hash buffer from `scratch`.
     ///
-    /// When `shrink = true`, terminal windows may be smaller than `w_max`.
-    /// When `shrink = false`, iteration stops if a full window cannot be formed.
-    pub fn set_window_shrink(&mut self, s: bool) {
-        self.shrink = s;
-    }
-
-    /// Returns the index of the last returned first-strobe (m1).
+    /// Behaves like [`RandStrobes::with_hasher`]; pass the buffer back with
+    /// [`recycle`](Self::recycle) to reuse it for the next sequence, so
+    /// seeding many short reads does not allocate a hash vector per read.
     ///
-    /// If no strobe has been generated yet, returns `None`.
-    pub fn index(&self) -> Option<usize> {
-        self.idx.checked_sub(1)
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{NtHash64, RandStrobes, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// for read in [&b"ACGATCTGGTACCTAG"[..], b"GGATTACACGATCGGATC"] {
+    ///     let mut rs = RandStrobes::with_scratch(read, 2, 3, 3, 5, &NtHash64, &mut scratch)?;
+    ///     let seeds: Vec<u64> = rs.by_ref().collect();
+    ///     assert!(!seeds.is_empty());
+    ///     rs.recycle(&mut scratch);
+    /// }
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn with_scratch<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+        scratch: &mut Scratch,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        // Ensure all parameters are valid before proceeding
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+
+        // Precompute hash values for all valid k-mers
+        let mut hashes = mem::take(&mut scratch.hashes);
+        hasher.hash_all_into(seq, k, &mut hashes)?;
+        let valid = hasher_validity(hasher, seq, k, hashes.len())?;
+
+        Ok(Self::from_strand_hashes(seq.len(), n, k, w_min, w_max, hashes, valid, false))
     }
 
-    /// Returns the indices of the most recently generated strobes: [m1, m2, (m3)].
+    /// Constructs a new **canonical** (strand-neutral) [`RandStrobes`] iterator
+    /// using the default hash function (`NtHash64`).
     ///
-    /// If no strobe has been generated yet, returns `[0, 0, 0]`.
-    pub fn indexes(&self) -> [usize; 3] {
-        [self.index().unwrap_or(0), self.idx2, self.idx3]
+    /// See [`RandStrobes::canonical_with_hasher`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let fwd = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let rev = b"CGTGTAATCCTAGGTACCAGATCGT";
+    /// let mut a: Vec<u64> = RandStrobes::new_canonical(fwd, 2, 3, 3, 5).unwrap().collect();
+    /// let mut b: Vec<u64> = RandStrobes::new_canonical(rev, 2, 3, 3, 5).unwrap().collect();
+    /// a.sort();
+    /// b.sort();
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn new_canonical(seq: &[u8], n: u8, k: usize, w_min: usize, w_max: usize) -> Result<Self> {
+        Self::canonical_with_hasher(seq, n, k, w_min, w_max, &NtHash64)
     }
 
-    /// Chooses the position within `range` that minimizes `(base_hash + hashes[pos]) & prime`.
+    /// Constructs a new **canonical** (strand-neutral) [`RandStrobes`] iterator
+    /// with a user-defined hash function.
     ///
-    /// # Arguments
+    /// The emitted seeds are identical for a sequence and its reverse complement
+    /// (as a multiset, in mirrored order):
+    /// - Per-strobe hashes are canonical: `min(forward, reverse-complement)`,
+    ///   obtained by running `hasher` over both strands.
+    /// - At each anchor m1 the downstream seed (windows `m1 + w_min ..= m1 + w_max`, …)
+    ///   and the mirrored upstream seed (windows `m1 − w_max ..= m1 − w_min`, …,
+    ///   scanned outward from m1) are both formed, and the one with the smaller
+    ///   combined hash is emitted. The upstream seed is exactly the downstream
+    ///   seed of the same anchor on the reverse strand.
     ///
-    /// * `base_hash` – The hash value of the previous strobe (m1 or m2).
-    /// * `range` – Inclusive range of indices to consider for the next strobe.
+    /// Only anchors with complete windows on both sides produce seeds, so window
+    /// shrinking does not apply. Reported strobe positions (see
+    /// [`indexes`](RandStrobes::indexes)) may lie upstream of m1; use
+    /// [`strand`](RandStrobes::strand) and
+    /// [`strobe_strands`](RandStrobes::strobe_strands) for orientation.
     ///
     /// # Returns
     ///
-    /// *(best_pos, best_val)* – Index of the chosen k-mer and the resulting combined hash value.
-    ///
-    #[inline(always)]
-    fn choose_min(&self, base: u64, start: usize, end: usize) -> (usize, u64) {
-        let hashes  = &self.hashes;
-        let prime   = self.prime;
+    /// * `Ok(RandStrobes)` – If input and hashes are valid.
+    /// * `Err(StrobeError::SequenceTooShort)` – If no anchor has complete windows on both sides.
+    /// * `Err(StrobeError)` – On any other invalid input or hashing error.
+    pub fn canonical_with_hasher<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        validate_params!(seq, n, k, w_min, w_max, hasher);
 
-        let mut best_pos = start;
-        let mut best_val = u64::MAX;
+        // Canonical k-mer hashes from both strands
+        let hashes = canonical_hashes(hasher, seq, k)?;
 
-        for i in start..=end {
-            let cand = base.wrapping_add(hashes[i]) & prime;
-            if cand < best_val {
-                best_val = cand;
-                best_pos = i;
-            }
+        // Anchors need (n − 1) full windows on both sides
+        let end_hash = seq.len() - k;
+        let span = (n as usize - 1) * w_max;
+        if end_hash < 2 * span {
+            return Err(StrobeError::SequenceTooShort);
         }
-        (best_pos, best_val)
+
+        let valid = hasher_validity(hasher, seq, k, hashes.len())?;
+
+        let mut rs = Self::from_parts(seq.len(), n, k, w_min, w_max, hashes, Vec::new());
+        rs.valid = valid;
+        rs.canonical = true;
+        rs.kmer_rev = reverse_is_canonical(seq, k);
+        rs.idx = span;
+        rs.end_idx = end_hash - span;
+        rs.back = rs.end_idx + 1;
+        Ok(rs)
     }
-    // -------------------- order-specific next ---------------------------- //
 
-    /// Computes the next RandStrobe hash value for order 2.
+    /// Constructs a new [`RandStrobes`] iterator over the **reverse complement**
+    /// of `seq` using the default hash function (`NtHash64`).
     ///
-    /// # Returns
-    /// - `Some(u64)` – Combined hash of m1 and m2, if available.
-    /// - `None` – When `idx > end_idx` (no more valid strobes).
+    /// See [`RandStrobes::revcomp_with_hasher`] for details.
     ///
-    fn next_order2(&mut self) -> Option<u64> {
-        if self.idx > self.end_idx {
-            return None;
-        }
-
-        // Define the search window for m2
-        let w_start = self.idx + self.w_min;
-        let mut w_end = self.idx + self.w_max;
-        if w_end > self.end_hash {
-            if !self.shrink {
-                return None;
-            }
-            w_end = self.end_hash;
-        }
-
-        // Hash of the first k-mer (m1)
-        self.h1 = self.hashes[self.idx];
-        // Choose m2 by minimizing `(h1 + hash[m2]) & prime`
-        let (pos2, _) = self.choose_min(self.h1, w_start, w_end);
-        self.idx2 = pos2;
-        // Combine h1 and second k-mer’s hash
-        self.h2 = (self.h1 >> 1) + self.hashes[pos2] / 3;
-
-        // Advance to next starting index for m1
-        self.idx += 1;
-        Some(self.h2)
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let mut rs = RandStrobes::new_revcomp(seq, 2, 3, 3, 5).unwrap();
+    /// rs.next().unwrap();
+    /// let [m1, m2, _] = rs.indexes();
+    /// // Strobes follow m1 on the reverse strand, i.e. leftwards on the input
+    /// assert!(m2 < m1 && m1 <= seq.len() - 3);
+    /// ```
+    pub fn new_revcomp(seq: &[u8], n: u8, k: usize, w_min: usize, w_max: usize) -> Result<Self> {
+        Self::revcomp_with_hasher(seq, n, k, w_min, w_max, &NtHash64)
     }
 
-    /// Computes the next RandStrobe hash value for order 3.
+    /// Constructs a new [`RandStrobes`] iterator over the **reverse complement**
+    /// of `seq` with a user-defined hash function.
+    ///
+    /// Hashes are identical to those of [`RandStrobes::with_hasher`] run on the
+    /// reverse complement, but [`index`](RandStrobes::index) and
+    /// [`indexes`](RandStrobes::indexes) report k-mer start positions on the
+    /// original forward sequence: a k-mer at position `p` of the reverse
+    /// complement is reported at `seq.len() − k − p`. Seeds are therefore emitted
+    /// with decreasing m1, and m2 (and m3) lie to the left of m1. Positions
+    /// given to `set_first_strobe_positions` are forward coordinates as well.
     ///
     /// # Returns
-    /// - `Some(u64)` – Combined hash of m1, m2, and m3, if available.
-    /// - `None` – When no further strobes can be formed.
     ///
-    fn next_order3(&mut self) -> Option<u64> {
-        if self.idx > self.end_idx {
-            return None;
-        }
-
-        // First window range for selecting m2
-        let w1_start = self.idx + self.w_min;
-        let w1_end = self.idx + self.w_max;
-
-        // Second window range for selecting m3
-        let w2_start = self.idx + self.w_max + self.w_min;
-        let mut w2_end = self.idx + (self.w_max << 1);
-        if w2_start > self.end_hash {
-            return None;
-        }
-        if w2_end > self.end_hash {
-            if !self.shrink {
-                return None;
+    /// * `Ok(RandStrobes)` – If input and hashes are valid.
+    /// * `Err(StrobeError)` – On invalid input, hashing errors, or insufficient sequence length.
+    pub fn revcomp_with_hasher<H>(
+        seq: &[u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+
+        let rc = reverse_complement(seq);
+        let hashes = hasher.hash_all(&rc, k)?;
+        let valid = hasher_validity(hasher, &rc, k, hashes.len())?;
+
+        Ok(Self::from_strand_hashes(seq.len(), n, k, w_min, w_max, hashes, valid, true))
+    }
+
+    /// Assembles a plain (`revcomp = false`) or reverse-complement iterator from
+    /// the k-mer hashes and validity mask of the strand being seeded.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn from_strand_hashes(
+        seq_len: usize,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hashes: Vec<u64>,
+        valid: Option<Vec<bool>>,
+        revcomp: bool,
+    ) -> Self {
+        let mut rs = Self::from_parts(seq_len, n, k, w_min, w_max, hashes, Vec::new());
+        rs.valid = valid;
+        rs.revcomp = revcomp;
+        rs
+    }
+
+    /// Constructs a new [`RandStrobes`] iterator producing **gapped strobemers**.
+    ///
+    /// Each strobe is hashed through its own spaced-seed mask (`1` = care,
+    /// `0` = don't care, see [`SpacedSeed`](crate::SpacedSeed)): `masks[0]` for
+    /// m1, `masks[1]` for m2 and, for order 3, `masks[2]` for m3. This allows
+    /// tuning the substitution tolerance per strobe. All masks must have the
+    /// same length, which plays the role of `k`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq` – Input DNA/RNA sequence as ASCII bytes.
+    /// * `n` – Order of the strobemer (must be 2 or 3).
+    /// * `masks` – Exactly `n` spaced-seed masks of equal length (1..=64).
+    /// * `w_min`, `w_max` – Window offsets, as for [`RandStrobes::new`].
+    /// * `hasher` – Hasher applied to the care bases of each strobe.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – If input and masks are valid.
+    /// * `Err(StrobeError::OrderNotSupported)` – If `n` is not 2 or 3.
+    /// * `Err(StrobeError::InvalidSpacedMask)` – If a mask is malformed, the count differs
+    ///   from `n`, or the lengths differ.
+    /// * `Err(StrobeError)` – On any other invalid input or hashing error.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{NtHash64, RandStrobes};
+    /// let masks: [&[u8]; 2] = [b"11111", b"11011"];
+    /// let rs = RandStrobes::with_spaced_masks(b"ACGATCTGGTACCTAG", 2, &masks, 3, 5, &NtHash64).unwrap();
+    /// assert!(rs.count() > 0);
+    /// ```
+    pub fn with_spaced_masks<H>(
+        seq: &[u8],
+        n: u8,
+        masks: &[&[u8]],
+        w_min: usize,
+        w_max: usize,
+        hasher: &H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        if !(2..=3).contains(&n) {
+            return Err(StrobeError::OrderNotSupported);
+        }
+        let Some(k) = masks.first().map(|m| m.len()) else {
+            return Err(StrobeError::InvalidSpacedMask);
+        };
+        if masks.len() != n as usize || masks.iter().any(|m| m.len() != k) {
+            return Err(StrobeError::InvalidSpacedMask);
+        }
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+
+        // One hashing pass per strobe mask
+        let mut per_strobe = Vec::with_capacity(masks.len());
+        for mask in masks {
+            let offsets = parse_mask(mask)?;
+            per_strobe.push(hash_all_spaced(seq, &offsets, k, hasher)?);
+        }
+        let hashes = per_strobe.remove(0);
+
+        Ok(Self::from_parts(seq.len(), n, k, w_min, w_max, hashes, per_strobe))
+    }
+
+    /// Assembles an iterator from validated parameters and precomputed hashes.
+    fn from_parts(
+        seq_len: usize,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hashes: Vec<u64>,
+        link_hashes: Vec<Vec<u64>>,
+    ) -> Self {
+        // Calculate the valid iteration bounds
+        let end_hash = seq_len.saturating_sub(k); // maximum hash index
+        let end_idx = seq_len.saturating_sub(k + (n as usize - 1) * k); // max starting index for m₁
+
+        Self {
+            n,
+            k,
+            w_min,
+            w_max,
+            hashes,
+            link_hashes,
+            seq: None,
+            idx: 0,
+            end_idx,
+            end_hash,
+            back: end_idx + 1,
+            from_back: false,
+            idx2: 0,
+            idx3: 0,
+            prime: DEFAULT_PRIME_NUMBER,
+            shrink: true,
+            max_span: usize::MAX,
+            min_span: 0,
+            anchor: WindowAnchor::FromFirstStrobe,
+            h1: 0,
+            h2: 0,
+            h3: 0,
+            strategy: MaskedSum,
+            distance_penalty: 0,
+            step: 1,
+            qual_penalty: None,
+            tie: TieBreak::Leftmost,
+            margin: None,
+            unstable: false,
+            canonical: false,
+            kmer_rev: Vec::new(),
+            strand: None,
+            revcomp: false,
+            offset: 0,
+            combiner: None,
+            scheme: CombineScheme::default(),
+            first_mask: None,
+            valid: None,
+            soft_mask: None,
+            soft_any: false,
+            excluded: None,
+            stride: 1,
+            threshold: u64::MAX,
+            gc_density: None,
+            local_counts: None,
+        }
+    }
+}
+
+impl<S: SelectionStrategy> RandStrobes<S> {
+    /// Replaces the selection strategy used to pick m2 (and m3).
+    ///
+    /// Consumes the iterator and returns one scoring candidates with `strategy`
+    /// instead of the current rule. All other parameters and the iteration
+    /// position are carried over unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{MinXor, RandStrobes};
+    /// let rs = RandStrobes::new(b"ACGTACGTACGT", 2, 3, 1, 4).unwrap().with_strategy(MinXor);
+    /// ```
+    pub fn with_strategy<T: SelectionStrategy>(self, strategy: T) -> RandStrobes<T> {
+        RandStrobes {
+            n: self.n,
+            k: self.k,
+            w_min: self.w_min,
+            w_max: self.w_max,
+            hashes: self.hashes,
+            link_hashes: self.link_hashes,
+            seq: self.seq,
+            idx: self.idx,
+            end_idx: self.end_idx,
+            end_hash: self.end_hash,
+            back: self.back,
+            from_back: self.from_back,
+            idx2: self.idx2,
+            idx3: self.idx3,
+            prime: self.prime,
+            shrink: self.shrink,
+            max_span: self.max_span,
+            min_span: self.min_span,
+            anchor: self.anchor,
+            h1: self.h1,
+            h2: self.h2,
+            h3: self.h3,
+            strategy,
+            distance_penalty: self.distance_penalty,
+            step: self.step,
+            qual_penalty: self.qual_penalty,
+            tie: self.tie,
+            margin: self.margin,
+            unstable: self.unstable,
+            canonical: self.canonical,
+            kmer_rev: self.kmer_rev,
+            strand: self.strand,
+            revcomp: self.revcomp,
+            offset: self.offset,
+            combiner: self.combiner,
+            scheme: self.scheme,
+            first_mask: self.first_mask,
+            valid: self.valid,
+            soft_mask: self.soft_mask,
+            soft_any: self.soft_any,
+            excluded: self.excluded,
+            stride: self.stride,
+            threshold: self.threshold,
+            gc_density: self.gc_density,
+            local_counts: self.local_counts,
+        }
+    }
+
+    /// Sets a new prime number for combining hash values.
+    ///
+    /// The formula used is `(base_hash + candidate_hash) & prime`. The provided `q` must
+    /// be at least 256. Internally, `q` is rounded up to the next power of two and then
+    /// decremented by one to form a Mersenne prime.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` – Candidate prime (will be rounded to Mersenne form: `2^k − 1`).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `q` ≥ 256, updates `self.prime`.
+    /// * `Err(StrobeError::PrimeNumberTooSmall)` – If `q` < 256.
+    pub fn set_prime(&mut self, q: u64) -> Result<()> {
+        if q < 256 {
+            return Err(StrobeError::PrimeNumberTooSmall);
+        }
+        // Round up to next power of two, subtract one → Mersenne prime form
+        self.prime = roundup64(q) - 1;
+        Ok(())
+    }
+
+    /// Enables or disables window shrinking at the sequence end.
+    ///
+    /// When `shrink = true`, terminal windows may be smaller than `w_max`.
+    /// When `shrink = false`, iteration stops if a full window cannot be formed.
+    pub fn set_window_shrink(&mut self, s: bool) {
+        self.shrink = s;
+    }
+
+    /// Caps the span of every seed, from the start of m1 to the end of the
+    /// last strobe, at `max_span` bases.
+    ///
+    /// The last strobe's search window is cut short where a strobe would end
+    /// past the cap, like a window shrunk at the sequence end, so seeds keep
+    /// their anchors but never span more than `max_span`, whatever `w_max`.
+    /// In canonical mode mirrored windows are cut the same way. Banded
+    /// aligners can size their bands from it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If every last window keeps at least one candidate.
+    /// * `Err(StrobeError::MaxSpanTooSmall)` – If `max_span` is below
+    ///   `k + w_min` (order 2) or `k + w_max + w_min` (order 3), or below
+    ///   the [`min_span`](Self::set_min_span) floor.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let mut rs = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 2, 8)?;
+    /// rs.set_max_span(8)?;
+    /// while let Some(seed) = rs.next_seed() {
+    ///     assert!(seed.span <= 8);
+    /// }
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn set_max_span(&mut self, max_span: usize) -> Result<()> {
+        if max_span < self.k + (self.n as usize - 2) * self.w_max + self.w_min
+            || max_span < self.min_span
+        {
+            return Err(StrobeError::MaxSpanTooSmall);
+        }
+        self.max_span = max_span;
+        Ok(())
+    }
+
+    /// Guarantees every seed a span, from the start of m1 to the end of the
+    /// last strobe, of at least `min_span` bases.
+    ///
+    /// The last strobe's search window starts no earlier than where a strobe
+    /// would end at `min_span`; in canonical mode mirrored windows
+    /// are raised the same way. Selection within the raised window is unchanged.
+    /// Anchors whose raised window no longer fits the sequence yield no seed.
+    /// Seeds spanning a minimum distance carry more spatial information,
+    /// e.g. for detecting structural variants.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If every last window keeps at least one candidate.
+    /// * `Err(StrobeError::MinSpanTooLarge)` – If `min_span` exceeds
+    ///   `k + (n − 1)·w_max`, the longest span windows allow, or the
+    ///   [`max_span`](Self::set_max_span) cap.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let mut rs = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 2, 8)?;
+    /// rs.set_min_span(9)?;
+    /// while let Some(seed) = rs.next_seed() {
+    ///     assert!(seed.span >= 9);
+    /// }
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn set_min_span(&mut self, min_span: usize) -> Result<()> {
+        let longest = self.k + (self.n as usize - 1) * self.w_max;
+        if min_span > longest.min(self.max_span) {
+            return Err(StrobeError::MinSpanTooLarge);
+        }
+        self.min_span = min_span;
+        Ok(())
+    }
+
+    /// Sets where the search window of m3 is anchored, see [`WindowAnchor`].
+    ///
+    /// Under [`WindowAnchor::FromPreviousStrobe`] the window follows m2, cut
+    /// at the sequence end, the [`max_span`](Self::set_max_span) cap and
+    /// raised to the [`min_span`](Self::set_min_span) floor like the default
+    /// one; if the floor lies past the window, m3 is the k-mer at the floor.
+    /// Anchors producing seeds are the same under either rule.
+    pub fn set_window_anchor(&mut self, anchor: WindowAnchor) {
+        self.anchor = anchor;
+    }
+
+    /// Biases strobe selection toward shorter spans.
+    ///
+    /// Each candidate's score is increased by `penalty × offset`, where `offset`
+    /// is the candidate's distance from the start of its search window. Scores
+    /// live in `[0, prime]`, so the penalty should be chosen relative to the
+    /// prime (e.g. `prime / (w_max − w_min)` makes the far end of the window
+    /// cost as much as the full score range). `0` (the default) disables it.
+    ///
+    /// This trades seed randomness for lower span variance.
+    pub fn set_distance_penalty(&mut self, penalty: u64) {
+        self.distance_penalty = penalty;
+    }
+
+    /// Biases strobe selection away from low-quality bases.
+    ///
+    /// `qual` holds one Phred+33 quality character per base, as in a FASTQ
+    /// record. A candidate k-mer whose lowest base quality is `q < target`
+    /// has `penalty × (target − q)` added to its score, so m2 (and m3) prefer
+    /// reliable k-mers, while a low-quality k-mer still wins a window with no
+    /// better choice. Unlike [`with_quality_mask`](RandStrobes::with_quality_mask)
+    /// no sequence is discarded; first strobes are not affected. As with
+    /// [`set_distance_penalty`](Self::set_distance_penalty), choose `penalty`
+    /// relative to the prime. `qual` must match the sequence the iterator
+    /// hashes; in reverse-complement mode pass the input's qualities.
+    /// Replaces weights set before.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `qual` matches the iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `qual` does not match the iterator's length.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    /// let qual = b"IIIIIIII#####IIIIIIIIIIII";
+    /// let mut rs = RandStrobes::new(seq, 2, 3, 3, 5)?;
+    /// rs.set_quality_weights(qual, 30, u64::MAX / 64)?;
+    /// assert!(rs.count() > 0);
+    /// # Ok::<(), strobemers_rs::StrobeError>(())
+    /// ```
+    pub fn set_quality_weights(&mut self, qual: &[u8], target: u8, penalty: u64) -> Result<()> {
+        let penalties = quality_penalties(qual, self.k, target, penalty);
+        if penalties.len() != self.hashes.len() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        self.qual_penalty = Some(match self.revcomp {
+            // Qualities are strand-symmetric; flip into seeded-strand order
+            true => penalties.into_iter().rev().collect(),
+            false => penalties,
+        });
+        Ok(())
+    }
+
+    /// Sets which of several equally scored candidates is selected, see [`TieBreak`].
+    ///
+    /// The default, [`TieBreak::Leftmost`], picks the candidate nearest the
+    /// previous strobe.
+    pub fn set_tie_break(&mut self, tie: TieBreak) {
+        self.tie = tie;
+    }
+
+    /// Selects the accuracy/throughput trade-off of strobe selection.
+    ///
+    /// [`PerformanceMode::Exact`] (the default) scores every candidate; the
+    /// cheaper modes score only a sample of each window, see
+    /// [`PerformanceMode`] for the impact on sensitivity.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{PerformanceMode, RandStrobes};
+    /// let mut rs = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 2, 10).unwrap();
+    /// rs.set_performance_mode(PerformanceMode::Fast);
+    /// rs.next().unwrap();
+    /// let [m1, m2, _] = rs.indexes();
+    /// assert_eq!((m2 - m1 - 2) % 4, 0);
+    /// ```
+    pub fn set_performance_mode(&mut self, mode: PerformanceMode) {
+        self.step = mode.candidate_step();
+    }
+
+    /// Enables or disables flagging of unstable seeds.
+    ///
+    /// With `Some(margin)`, every selection compares the winning candidate's
+    /// score with the runner-up; if the gap is `≤ margin` (ties included) the
+    /// emitted seed is flagged, see [`is_unstable`](Self::is_unstable). Such
+    /// seeds are likely to change under small sequence edits. `None` (the
+    /// default) disables the check.
+    pub fn set_ambiguity_margin(&mut self, margin: Option<u64>) {
+        self.margin = margin;
+    }
+
+    /// Returns whether the most recently generated strobemer was flagged unstable.
+    ///
+    /// Always `false` unless a margin was set via
+    /// [`set_ambiguity_margin`](Self::set_ambiguity_margin).
+    pub fn is_unstable(&self) -> bool {
+        self.unstable
+    }
+
+    /// Restricts the first strobe (m1) to the given start positions.
+    ///
+    /// Start positions not listed are skipped without doing any selection work,
+    /// which thins the output, e.g. to mod-minimizer positions from
+    /// [`ModMinimizers`](crate::ModMinimizers). Positions beyond the sequence are
+    /// ignored.
+    pub fn set_first_strobe_positions<I>(&mut self, positions: I)
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut mask = vec![false; self.hashes.len()];
+        for p in positions {
+            if let Some(m) = self.input_to_strand(p).and_then(|p| mask.get_mut(p)) {
+                *m = true;
+            }
+        }
+        self.first_mask = Some(mask);
+    }
+
+    /// Emits seeds only at every `s`-th first-strobe position.
+    ///
+    /// Positions between the grid points are skipped inside `next()` without
+    /// any selection work, so this is the cheapest way to shrink an index by
+    /// roughly a factor `s`. The grid starts at the first anchor on the
+    /// seeded strand (position 0, or the first anchor with complete upstream
+    /// windows in canonical mode). Combined
+    /// with [`set_first_strobe_positions`](Self::set_first_strobe_positions),
+    /// only listed positions on the grid are kept. `s = 1` (the default)
+    /// seeds every position.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `s ≥ 1`.
+    /// * `Err(StrobeError::InvalidStride)` – If `s` is 0.
+    pub fn set_stride(&mut self, s: usize) -> Result<()> {
+        if s == 0 {
+            return Err(StrobeError::InvalidStride);
+        }
+        self.stride = s;
+        Ok(())
+    }
+
+    /// Drops seeds in soft-masked (lowercase) sequence, see [`SoftMask`].
+    ///
+    /// Seeds whose first strobe is masked are skipped before any selection
+    /// work; with [`SoftMask::SkipAnyStrobe`], seeds are also dropped after
+    /// selection if m2 (or m3) is masked. Strobe selection itself is unchanged,
+    /// so the remaining seeds are identical to those without the option.
+    /// `seq` must be the sequence the iterator hashes (the region's bytes for
+    /// [`new_in_region`](Self::new_in_region));
+    /// in reverse-complement mode pass the input, not its reverse complement.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `seq` matches the iterator.
+    /// * `Err(StrobeError::InvalidSequence)` – If `seq` does not match the iterator's length.
+    pub fn set_soft_mask(&mut self, seq: &[u8], mode: SoftMask) -> Result<()> {
+        let masked = soft_masked_kmers(seq, self.k);
+        if masked.len() != self.hashes.len() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        self.soft_mask = match mode {
+            SoftMask::Ignore => None,
+            // Soft-masking is strand-symmetric; flip into seeded-strand order
+            _ if self.revcomp => Some(masked.into_iter().rev().collect()),
+            _ => Some(masked),
+        };
+        self.soft_any = mode == SoftMask::SkipAnyStrobe;
+        Ok(())
+    }
+
+    /// Skips every seed with a strobe intersecting one of the masked `intervals`.
+    ///
+    /// Intervals are half-open `(start, end)` ranges of input positions, e.g.
+    /// repeat or low-quality annotations, and may be given in any order. Seeds
+    /// whose first strobe intersects an interval are skipped before any
+    /// selection work; the others are dropped after selection if m2 (or m3)
+    /// intersects one. Strobe selection itself is unchanged, so the remaining
+    /// seeds are identical to those without masking. Replaces intervals set
+    /// before.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – On success.
+    /// * `Err(StrobeError::InvalidRegion)` – If an interval has `start > end`.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let mut it = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 3, 5).unwrap();
+    /// it.set_masked_intervals([(10, 14)]).unwrap();
+    /// while it.next().is_some() {
+    ///     assert!(it.indexes()[..2].iter().all(|&p| p + 3 <= 10 || p >= 14));
+    /// }
+    /// ```
+    pub fn set_masked_intervals<I>(&mut self, intervals: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut hit = kmers_overlapping(intervals, self.offset, self.hashes.len(), self.k)?;
+        if self.revcomp {
+            hit.reverse();
+        }
+        self.excluded = Some(hit);
+        Ok(())
+    }
+
+    /// Sets the combiner producing the emitted hash from the strobe hashes.
+    ///
+    /// By default strobe hashes are combined with a built-in
+    /// [`CombineScheme`] ([`Mix`](CombineScheme::Mix) unless changed with
+    /// [`set_combine_scheme`](Self::set_combine_scheme)). Any
+    /// [`StrobeCombiner`], including a closure `Fn(&[u64]) -> u64`, can replace
+    /// it. Strobe selection is unaffected; only the emitted hash changes.
+    pub fn set_combiner<C: StrobeCombiner>(&mut self, combiner: C) {
+        self.combiner = Some(Combiner::new(combiner));
+    }
+
+    /// Enables or disables order-independent combination of strobe hashes.
+    ///
+    /// With the built-in schemes a seed and the same strobes visited in
+    /// reverse order hash differently. Enabling this is shorthand for
+    /// `set_combiner(SymmetricCombiner)`; disabling restores the built-in
+    /// scheme (and drops any combiner set before).
+    pub fn set_symmetric(&mut self, enable: bool) {
+        self.combiner = enable.then(|| Combiner::new(SymmetricCombiner));
+    }
+
+    /// Selects the built-in combination of strobe hashes (default
+    /// [`CombineScheme::Mix`]) and drops any combiner set before.
+    ///
+    /// [`CombineScheme::Legacy`] reproduces the seeds of the original
+    /// implementation and of indexes built before `Mix` became the default.
+    /// Strobe selection is unaffected.
+    pub fn set_combine_scheme(&mut self, scheme: CombineScheme) {
+        self.combiner = None;
+        self.scheme = scheme;
+    }
+
+    /// Keeps only strobemers whose combined hash is at most `f × u64::MAX`.
+    ///
+    /// This deterministically thins the output to roughly a fraction `f` of the
+    /// seeds, consistently across sequences. Rejected seeds never leave the
+    /// iterator. The rate is exactly `f` only if combined hashes are uniform
+    /// over `u64`, as with the default [`CombineScheme::Mix`]; the legacy
+    /// formulas use part of the range, so their realized rate is lower.
+    /// Replaces any density set with [`set_gc_density`](Self::set_gc_density).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If `0 < f ≤ 1`.
+    /// * `Err(StrobeError::InvalidDensity)` – Otherwise (including NaN).
+    pub fn set_density(&mut self, f: f64) -> Result<()> {
+        if !(f > 0.0 && f <= 1.0) {
+            return Err(StrobeError::InvalidDensity);
+        }
+        self.threshold = (f * u64::MAX as f64) as u64;
+        self.gc_density = None;
+        Ok(())
+    }
+
+    /// Thins the output to a fraction `f` of the seeds within every GC-content bin.
+    ///
+    /// Hash-based thinning as in [`set_density`](Self::set_density) keeps more
+    /// or fewer seeds depending on base composition, because k-mer hashes are
+    /// not independent of GC content. Here each first-strobe position is put
+    /// into one of `bins` equal-width bins by the GC fraction of the `window`
+    /// bases centered on its k-mer, and each bin gets its own threshold keeping
+    /// `f` of that bin's seeds, so seed density is uniform across AT- and
+    /// GC-rich regions. Thresholds are calibrated on this sequence by running
+    /// the remaining iteration once in advance, which roughly doubles the cost.
+    /// Since they depend on the sequence, the kept seeds are not consistent
+    /// across sequences as with `set_density`; use this for density and
+    /// coverage estimates rather than for seed matching.
+    ///
+    /// `seq` must be the sequence the iterator was built from. Replaces any
+    /// density set before, and is replaced by a later `set_density`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` – If the parameters are valid.
+    /// * `Err(StrobeError::InvalidDensity)` – If `f` is not in `(0, 1]`.
+    /// * `Err(StrobeError::InvalidWindowLength)` – If `window` is 0.
+    /// * `Err(StrobeError::InvalidBinCount)` – If `bins` is 0.
+    /// * `Err(StrobeError::InvalidSequence)` – If `seq` does not match the iterator's length.
+    pub fn set_gc_density(&mut self, seq: &[u8], f: f64, window: usize, bins: u8) -> Result<()>
+    where
+        S: Clone,
+    {
+        if !(f > 0.0 && f <= 1.0) {
+            return Err(StrobeError::InvalidDensity);
+        }
+        let mut gc = GcDensity::new(seq, self.k, self.hashes.len(), window, bins)?;
+
+        // Calibrate on the seeds the unthinned iterator would emit
+        let mut probe = self.clone();
+        probe.threshold = u64::MAX;
+        probe.gc_density = None;
+        let samples = std::iter::from_fn(|| {
+            let h = probe.next()?;
+            Some((probe.to_forward(probe.first_raw()?)?, h))
+        });
+        gc.calibrate(samples, f);
+
+        self.threshold = u64::MAX;
+        self.gc_density = Some(gc);
+        Ok(())
+    }
+
+    /// Moves the iterator so the next seed has its first strobe at `i` or later.
+    ///
+    /// Nothing between the current position and `i` is computed, and seeking
+    /// backwards revisits seeds already returned, e.g. to re-seed a region of
+    /// interest. Seeds already taken with `next_back` are not revisited;
+    /// seeking past them leaves the iterator exhausted. `i` is an input
+    /// coordinate: in reverse-complement mode, where m1 decreases, iteration
+    /// continues from `i` towards the start of the input. In canonical mode
+    /// positions before the first anchor with complete upstream windows are
+    /// skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let mut rs = RandStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// rs.seek(4);
+    /// rs.next().unwrap();
+    /// assert_eq!(rs.index(), Some(4));
+    /// ```
+    pub fn seek(&mut self, i: usize) {
+        let raw = match i.checked_sub(self.offset) {
+            Some(p) => self.to_forward(p).unwrap_or(0),
+            // Before a region's start: everything remains, or nothing in reverse-complement mode
+            None if self.revcomp => self.back,
+            None => 0,
+        };
+        self.idx = raw.max(self.first_anchor()).min(self.back);
+        self.from_back = false;
+    }
+
+    /// Returns the number of remaining anchors, a cheap upper bound on the
+    /// number of seeds still to come.
+    ///
+    /// The bound is exact unless seeds may be dropped after their anchor is
+    /// visited: by first-strobe positions, a density or GC density, a soft
+    /// mask, masked intervals, or ambiguous k-mers (skipped under
+    /// [`AmbiguityPolicy::SkipAffectedSeeds`](crate::AmbiguityPolicy::SkipAffectedSeeds)
+    /// or flagged by the hasher).
+    pub fn remaining_upper_bound(&self) -> usize {
+        self.remaining_anchors()
+    }
+
+    /// Generates seeds for at most `max_bases` first-strobe positions and returns.
+    ///
+    /// Emitted hashes are appended to `out`. Positions skipped by
+    /// [`set_first_strobe_positions`](Self::set_first_strobe_positions) or a
+    /// mask count towards the budget but cost no selection work; so do
+    /// anchors whose seed a density or mask drops. A budget of 0 is treated
+    /// as 1 so every call makes progress. Intended for event-loop integrations
+    /// that must regain control at a bounded interval.
+    ///
+    /// # Returns
+    ///
+    /// * `true` – Once the iterator is exhausted.
+    /// * `false` – If the budget ran out and more seeds may follow.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let mut it = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACA", 2, 3, 3, 5).unwrap();
+    /// let mut out = Vec::new();
+    /// while !it.poll_seeds(4, &mut out) {
+    ///     // yield to the event loop here
+    /// }
+    /// assert!(!out.is_empty());
+    /// ```
+    pub fn poll_seeds(&mut self, max_bases: usize, out: &mut Vec<u64>) -> bool {
+        let limit = self.idx.saturating_add(max_bases.max(1));
+        loop {
+            match self.next_until(limit) {
+                Some(Some(h)) => out.push(h),
+                Some(None) => return false,
+                None => return true,
+            }
+        }
+    }
+
+    /// Advances to the next emitted seed, visiting no anchor at or past `limit`.
+    ///
+    /// Returns `None` once exhausted and `Some(None)` if `limit` is reached first.
+    fn next_until(&mut self, limit: usize) -> Option<Option<u64>> {
+        loop {
+            self.idx = self.on_stride(self.idx);
+            // Skip start positions excluded by `set_first_strobe_positions` or ambiguous bases
+            while self.idx < self.back.min(limit) && self.skips_anchor(self.idx) {
+                self.idx = self.idx.saturating_add(self.stride);
+            }
+            if self.idx >= self.back {
+                return None;
+            }
+            if self.idx >= limit {
+                return Some(None);
+            }
+            let h = self.anchor_seed(self.idx)?;
+            self.idx += 1;
+            self.from_back = false;
+            if let Some(h) = self.emit(h) {
+                return Some(Some(h));
+            }
+        }
+    }
+
+    /// Writes the next seeds into `out` and returns how many were written.
+    ///
+    /// Fewer than `out.len()` seeds are written only once the iterator is
+    /// exhausted. Unless first-strobe positions, a mask, a density or a
+    /// combiner is set, every remaining anchor yields one seed, and the seeds
+    /// are computed in one loop over the anchors instead of one
+    /// [`next`](Iterator::next) call each. Either way the seeds, and the state
+    /// left behind (e.g. [`indexes`](Self::indexes)), are those of `next`.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAGGATTACA";
+    /// let mut it = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+    /// let mut buf = [0u64; 4];
+    /// let mut seeds = Vec::new();
+    /// loop {
+    ///     let n = it.fill_slice(&mut buf);
+    ///     seeds.extend_from_slice(&buf[..n]);
+    ///     if n < buf.len() {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(seeds, RandStrobes::new(seq, 2, 3, 3, 5).unwrap().collect::<Vec<u64>>());
+    /// ```
+    pub fn fill_slice(&mut self, out: &mut [u64]) -> usize {
+        if self.is_filtered() || self.combiner.is_some() {
+            return out.iter_mut().zip(self.by_ref()).map(|(slot, h)| *slot = h).count();
+        }
+        let last = self.last_complete_anchor();
+        let mut i = self.on_stride(self.idx);
+        let mut written = 0;
+        while written < out.len() && last.is_some_and(|last| i <= last) {
+            let Some(h) = self.anchor_seed(i) else { break };
+            self.idx = i + 1;
+            self.from_back = false;
+            out[written] = self.builtin(h, self.raw_indexes());
+            written += 1;
+            i += self.stride;
+        }
+        if written < out.len() {
+            // Leave the state of the `next` call that finds the end
+            let rest = self.next();
+            debug_assert!(rest.is_none());
+        }
+        written
+    }
+
+    /// Collects the remaining seeds into a vector sized once from the size
+    /// hint, using [`fill_slice`](Self::fill_slice).
+    pub fn into_vec(mut self) -> Vec<u64> {
+        let mut seeds = vec![0; self.size_hint().1.unwrap_or(0)];
+        let n = self.fill_slice(&mut seeds);
+        seeds.truncate(n);
+        seeds
+    }
+
+    /// Enables or disables counting of k-mer hash occurrences within the sequence.
+    ///
+    /// When enabled, a table of how often each k-mer hash occurs in this
+    /// sequence is built from the already computed hashes, and
+    /// [`first_strobe_count`](Self::first_strobe_count) reports the value for
+    /// each seed. High counts mark self-repetitive regions that downstream
+    /// code may want to down-weight without consulting a reference index.
+    pub fn set_local_counts(&mut self, enable: bool) {
+        self.local_counts = enable.then(|| {
+            let mut counts = HashMap::new();
+            for &h in &self.hashes {
+                // Saturate instead of wrapping on pathological repeats (> u32::MAX copies)
+                let c = counts.entry(h).or_insert(0u32);
+                *c = c.saturating_add(1);
+            }
+            counts
+        });
+    }
+
+    /// Returns how many times the most recent first strobe's hash occurs in the sequence.
+    ///
+    /// `None` if counting is disabled or no strobemer has been generated yet.
+    pub fn first_strobe_count(&self) -> Option<usize> {
+        let counts = self.local_counts.as_ref()?;
+        let h = self.hashes[self.first_raw()?];
+        counts.get(&h).map(|&c| c as usize)
+    }
+
+    /// Returns the strand the most recent canonical seed was read from.
+    ///
+    /// [`Strand::Forward`] means the strobes follow m1 downstream on the input;
+    /// [`Strand::Reverse`] means they were selected on the reverse complement,
+    /// i.e. upstream of m1 in input coordinates. `None` outside canonical mode
+    /// or before the first seed.
+    pub fn strand(&self) -> Option<Strand> {
+        self.strand
+    }
+
+    /// Returns, for each strobe `[m1, m2, m3]` of the most recent canonical seed,
+    /// the strand on which its k-mer reads in canonical form (the smaller of the
+    /// k-mer and its reverse complement, A < C < G < T).
+    ///
+    /// The orientation is taken from the bases, not the hashes, so it is the
+    /// same for every hasher, including strand-symmetric ones like `NtHash64`.
+    /// K-mers equal to their reverse complement count as forward.
+    /// For order 2 the third entry mirrors `m3 = 0` and is meaningless. `None`
+    /// outside canonical mode or before the first seed.
+    pub fn strobe_strands(&self) -> Option<[Strand; 3]> {
+        let orient = |pos: usize| match self.kmer_rev[pos] {
+            true => Strand::Reverse,
+            false => Strand::Forward,
+        };
+        let [m1, m2, m3] = self.raw_indexes();
+        self.strand.map(|_| [orient(m1), orient(m2), orient(m3)])
+    }
+
+    /// Recombines the strobes at raw indices `[m1, m2, m3]` with `combiner`.
+    fn recombine(&self, combiner: &Combiner, [m1, m2, m3]: [usize; 3]) -> u64 {
+        let strobes = self.strobes_at([m1, m2, m3]);
+        combiner.combine(&strobes[..self.n as usize], &[m1, m2, m3][..self.n as usize])
+    }
+
+    /// Applies the built-in scheme to the seed with raw strobes `[m1, m2, m3]`,
+    /// whose hash under the legacy formula (computed during selection) is `h`.
+    #[inline(always)]
+    fn builtin(&self, h: u64, strobes: [usize; 3]) -> u64 {
+        match self.scheme {
+            CombineScheme::Legacy => h,
+            scheme => scheme.combine(&self.strobes_at(strobes)[..self.n as usize]),
+        }
+    }
+
+    /// Returns the hashes of the strobes at raw indices `[m1, m2, m3]`.
+    #[inline(always)]
+    fn strobes_at(&self, [m1, m2, m3]: [usize; 3]) -> [u64; 3] {
+        [self.strobe_hashes(1)[m1], self.strobe_hashes(2)[m2], self.strobe_hashes(3)[m3]]
+    }
+
+    /// Advances the iterator, returning the next strobemer with its positions.
+    ///
+    /// Equivalent to calling `next()` followed by [`indexes`](Self::indexes).
+    pub fn next_seed(&mut self) -> Option<Strobemer> {
+        let hash = self.next()?;
+        Some(Strobemer::new(hash, self.indexes(), self.n, self.k))
+    }
+
+    /// Turns the iterator into one yielding [`Strobemer`] items instead of bare hashes.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let rs = RandStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// let seeds: Vec<_> = rs.iter_seeds().collect();
+    /// assert!(seeds.iter().all(|s| s.positions[0] < s.positions[1] && s.span <= 16));
+    /// ```
+    pub fn iter_seeds(mut self) -> impl Iterator<Item = Strobemer> {
+        std::iter::from_fn(move || self.next_seed())
+    }
+
+    /// Turns the iterator into one yielding seeds with 128-bit hashes.
+    ///
+    /// Positions and the high 64 bits are those of
+    /// [`iter_seeds`](Self::iter_seeds); the low 64 bits hash the strobe bases
+    /// (see [`Strobemer128`]). `seq` must be the sequence the iterator was built
+    /// from, as for [`seed_bytes`](Self::seed_bytes).
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let wide: Vec<_> = RandStrobes::new(seq, 2, 3, 3, 5).unwrap().iter_seeds128(seq).collect();
+    /// let narrow: Vec<_> = RandStrobes::new(seq, 2, 3, 3, 5).unwrap().iter_seeds().collect();
+    /// assert!(wide.iter().zip(&narrow).all(|(w, n)| (w.hash >> 64) as u64 == n.hash));
+    /// ```
+    pub fn iter_seeds128(mut self, seq: &[u8]) -> impl Iterator<Item = Strobemer128> {
+        std::iter::from_fn(move || {
+            let seed = self.next_seed()?;
+            Some(Strobemer128::new(seed, &self.seed_bytes(seq)))
+        })
+    }
+
+    /// Turns the iterator into one yielding `(hash, [m1, m2, m3])` pairs.
+    ///
+    /// A lighter alternative to [`iter_seeds`](Self::iter_seeds) that works
+    /// with `filter`, `zip` and friends without calling `indexes()` after each
+    /// `next()`. `m3` is 0 for order 2.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let rs = RandStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+    /// let near: Vec<u64> = rs
+    ///     .with_positions()
+    ///     .filter(|&(_, [m1, m2, _])| m2 - m1 <= 4)
+    ///     .map(|(h, _)| h)
+    ///     .collect();
+    /// ```
+    pub fn with_positions(mut self) -> impl Iterator<Item = (u64, [usize; 3])> {
+        std::iter::from_fn(move || self.next().map(|h| (h, self.indexes())))
+    }
+
+    /// Collects the remaining seeds column-wise: `(hashes, m1, m2, m3)`.
+    ///
+    /// One pass, with the columns pre-sized from the size hint, for index
+    /// builders that sort or serialize each column separately. `m3` is all
+    /// zeros for order 2.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((hashes, m1, m2, m3))` – The columns.
+    /// * `Err(StrobeError::SequenceTooLong)` – If a position could exceed
+    ///   `u32::MAX`, i.e. the last k-mer starts past it; checked before any
+    ///   seed is computed.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let (hashes, m1, m2, _) = RandStrobes::new(seq, 2, 3, 3, 5).unwrap().collect_soa().unwrap();
+    /// assert_eq!(hashes.len(), m1.len());
+    /// assert!(m1.iter().zip(&m2).all(|(a, b)| a < b));
+    /// ```
+    pub fn collect_soa(mut self) -> Result<SeedColumns> {
+        // The start of the last k-mer bounds every position
+        if self.offset + self.hashes.len() - 1 > u32::MAX as usize {
+            return Err(StrobeError::SequenceTooLong);
+        }
+        let cap = self.size_hint().1.unwrap_or(0);
+        let mut hashes = Vec::with_capacity(cap);
+        let mut cols = [(); 3].map(|_| Vec::with_capacity(cap));
+        while let Some(h) = self.next() {
+            hashes.push(h);
+            for (col, p) in cols.iter_mut().zip(self.indexes()) {
+                col.push(p as u32);
+            }
+        }
+        let [m1, m2, m3] = cols;
+        Ok((hashes, m1, m2, m3))
+    }
+
+    /// Returns the `budget` remaining seeds with the lowest reference abundance.
+    ///
+    /// Shorthand for [`top_informative`](crate::top_informative) over
+    /// [`with_positions`](Self::with_positions); see there for tie-breaking and
+    /// seeds absent from the reference.
+    pub fn top_informative<A>(self, budget: usize, source: &A) -> Vec<(u64, [usize; 3])>
+    where
+        A: AbundanceSource + ?Sized,
+    {
+        top_informative(self.with_positions(), budget, source)
+    }
+
+    /// Explains whether, and why, a seed linking `pos_a` (as m1) to `pos_b` is generated.
+    ///
+    /// Replays the selection at anchor `pos_a` under the current settings
+    /// (prime, strategy, distance penalty, window shrinking, first-strobe
+    /// positions, combiner and density) without advancing the iterator. The
+    /// report lists the anchor's search windows, the position selected in the
+    /// window containing `pos_b` and the score of every candidate scored there
+    /// (see [`set_performance_mode`](Self::set_performance_mode)). For order 3,
+    /// `pos_b` may be m2 or m3; m3 candidates are scored against the m2
+    /// actually selected. Positions are input coordinates, also in
+    /// reverse-complement mode. In canonical mode only the downstream seed is
+    /// replayed, so a [`SeedVerdict::Generated`] seed may still lose to the
+    /// mirrored upstream one.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{RandStrobes, SeedVerdict};
+    /// let mut rs = RandStrobes::new(b"ACGATCTGGTACCTAGGATTACACG", 2, 3, 3, 5).unwrap();
+    /// rs.next().unwrap();
+    /// let [m1, m2, _] = rs.indexes();
+    /// assert_eq!(rs.explain(m1, m2).verdict, SeedVerdict::Generated);
+    /// let other = if m2 == 3 { 4 } else { 3 };
+    /// let ex = rs.explain(m1, other);
+    /// assert_eq!(ex.verdict, SeedVerdict::Outscored);
+    /// assert!(ex.score_of(m2) <= ex.score_of(other));
+    /// ```
+    pub fn explain(&self, pos_a: usize, pos_b: usize) -> SeedExplanation {
+        let Some(i) = self.input_to_strand(pos_a) else {
+            return SeedExplanation::not_anchor();
+        };
+        let Some(windows) = self.windows_at(i) else {
+            return SeedExplanation::not_anchor();
+        };
+        if self.skips_anchor(i) || self.on_stride(i) != i {
+            return SeedExplanation::not_anchor();
+        }
+
+        let fwd = |p: usize| self.strand_to_input(p).unwrap_or(0);
+        let mut ex = SeedExplanation::not_anchor();
+        ex.windows = windows
+            .iter()
+            .map(|&(s, e)| if self.revcomp { (fwd(e), fwd(s)) } else { (fwd(s), fwd(e)) })
+            .collect();
+        let b = self.input_to_strand(pos_b);
+        let Some(j) = windows.iter().position(|&(s, e)| b.is_some_and(|b| s <= b && b <= e))
+        else {
+            ex.verdict = SeedVerdict::OutsideWindow;
+            return ex;
+        };
+
+        // Replay the selection of m2 (and m3) at this anchor
+        let h1 = self.hashes[i];
+        let (s2, e2) = windows[0];
+        let (m2, ..) = self.choose_min(2, h1, s2, e2, false);
+        let h2 = h1 / 3 + (self.strobe_hashes(2)[m2] >> 2);
+        let m3 = windows.get(1).map_or(0, |&(s3, e3)| self.choose_min(3, h2, s3, e3, false).0);
+
+        let (start, end) = windows[j];
+        let (strobe, base, chosen) = if j == 0 { (2, h1, m2) } else { (3, h2, m3) };
+        let hashes = self.strobe_hashes(strobe);
+        ex.strobe = Some(strobe as u8);
+        ex.chosen = Some(fwd(chosen));
+        ex.scores = (start..=end)
+            .step_by(self.step)
+            .map(|p| (fwd(p), self.score(base, hashes[p], p, p - start)))
+            .collect();
+        if b != Some(chosen) {
+            ex.verdict = SeedVerdict::Outscored;
+            return ex;
+        }
+
+        let legacy = match self.n {
+            2 => (h1 >> 1) + self.strobe_hashes(2)[m2] / 3,
+            _ => h2 + self.strobe_hashes(3)[m3] / 5,
+        };
+        let hash = match &self.combiner {
+            Some(c) => self.recombine(c, [i, m2, m3]),
+            None => self.builtin(legacy, [i, m2, m3]),
+        };
+        ex.verdict = match hash <= self.threshold_at(i) && !self.drops_strobes([i, m2, m3]) {
+            true => SeedVerdict::Generated,
+            false => SeedVerdict::Thinned,
+        };
+        ex
+    }
+
+    /// Returns the last m1 in `idx..back` whose windows fit the sequence, if any.
+    fn last_complete_anchor(&self) -> Option<usize> {
+        let links = self.n as usize - 1;
+        // Offset of the farthest window bound that must lie within the sequence
+        let reach = match self.canonical {
+            true => 0,
+            false if self.shrink => self.last_window_start(0),
+            false => links * self.w_max,
+        };
+        let last = self.end_hash.checked_sub(reach)?.min(self.back.checked_sub(1)?);
+        let first = self.first_anchor();
+        let last = first + last.checked_sub(first)? / self.stride * self.stride;
+        (last >= self.idx).then_some(last)
+    }
+
+    /// Returns the first m1 iteration starts from, before any seek.
+    #[inline(always)]
+    fn first_anchor(&self) -> usize {
+        if self.canonical { (self.n as usize - 1) * self.w_max } else { 0 }
+    }
+
+    /// Rounds `i` up to the next first-strobe position allowed by the stride.
+    #[inline(always)]
+    fn on_stride(&self, i: usize) -> usize {
+        let first = self.first_anchor();
+        first + i.saturating_sub(first).next_multiple_of(self.stride)
+    }
+
+    /// Returns how many anchors from the current one on produce a seed, before
+    /// any filtering.
+    fn remaining_anchors(&self) -> usize {
+        self.last_complete_anchor()
+            .map_or(0, |l| (l - self.on_stride(self.idx)) / self.stride + 1)
+    }
+
+    /// Returns the inclusive search windows of the strobes linked to anchor `i`
+    /// as iteration uses them, or `None` if no seed starts at `i`.
+    fn windows_at(&self, i: usize) -> Option<Vec<(usize, usize)>> {
+        let links = self.n as usize - 1;
+        let first = if self.canonical { links * self.w_max } else { 0 };
+        if i < first || i > self.end_idx {
+            return None;
+        }
+        let mut windows = Vec::with_capacity(links);
+        for j in 0..links {
+            let start = match j + 1 == links {
+                true => self.last_window_start(i),
+                false => i + j * self.w_max + self.w_min,
+            };
+            let mut end = i + (j + 1) * self.w_max;
+            if start > self.end_hash {
+                return None;
+            }
+            if end > self.end_hash {
+                if !self.shrink || self.canonical {
+                    return None;
+                }
+                end = self.end_hash;
+            }
+            windows.push((start, self.span_end(i, end)));
+        }
+        if links == 2 && self.anchor == WindowAnchor::FromPreviousStrobe {
+            let (m2, ..) = self.choose_min(2, self.hashes[i], windows[0].0, windows[0].1, false);
+            windows[1] = self.anchor_window(i, m2, windows[1], false);
+        }
+        Some(windows)
+    }
+
+    /// Returns the concatenated strobe bases of the most recent strobemer.
+    ///
+    /// Unless built with [`from_owned`](RandStrobes::from_owned), the iterator
+    /// does not keep the sequence, so `seq` must be the one it was built from;
+    /// panics if it is shorter. Strobes are concatenated in order m1, m2(, m3).
+    /// Returns an empty vector before the first strobemer.
+    ///
+    /// In reverse-complement mode each strobe is reverse-complemented, giving
+    /// the bases that were hashed. In canonical mode the forward bases are
+    /// returned; see [`strobe_strands`](Self::strobe_strands) for orientation.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::RandStrobes;
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let mut rs = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+    /// rs.next().unwrap();
+    /// let [m1, m2, _] = rs.indexes();
+    /// assert_eq!(rs.seed_bytes(seq), [&seq[m1..m1 + 3], &seq[m2..m2 + 3]].concat());
+    /// ```
+    pub fn seed_bytes(&self, seq: &[u8]) -> Vec<u8> {
+        if self.index().is_none() || self.canonical && self.strand.is_none() {
+            return Vec::new();
+        }
+        strobe_bytes(seq, &self.indexes()[..self.n as usize], self.k, self.revcomp)
+    }
+
+    /// Returns the hash buffer to `scratch` for reuse by the next construction.
+    pub fn recycle(self, scratch: &mut Scratch) {
+        scratch.hashes = self.hashes;
+    }
+
+    /// Returns the sequence kept by [`from_owned`](RandStrobes::from_owned), if any.
+    pub fn sequence(&self) -> Option<&[u8]> {
+        self.seq.as_deref()
+    }
+
+    /// Returns the k-mer hashes strobes are selected from, by k-mer index on
+    /// the seeded strand, e.g. to extract minimizers or fill a Bloom filter
+    /// without hashing the sequence again.
+    ///
+    /// Canonical iterators hold canonical k-mer hashes, and reverse-complement
+    /// ones the hashes along the reverse complement. With per-strobe spaced
+    /// masks, these are the hashes of m1's mask.
+    ///
+    /// # Example
+    /// ```
+    /// use strobemers_rs::{KmerHasher, NtHash64, RandStrobes};
+    /// let seq = b"ACGATCTGGTACCTAG";
+    /// let rs = RandStrobes::new(seq, 2, 3, 3, 5).unwrap();
+    /// assert_eq!(rs.hashes(), NtHash64.hash_all(seq, 3).unwrap());
+    /// ```
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// Returns the index of the last returned first-strobe (m1).
+    ///
+    /// If no strobe has been generated yet, returns `None`.
+    pub fn index(&self) -> Option<usize> {
+        self.first_raw().and_then(|p| self.strand_to_input(p))
+    }
+
+    /// Returns the indices of the most recently generated strobes: [m1, m2, (m3)].
+    ///
+    /// If no strobe has been generated yet, returns `[0, 0, 0]`.
+    pub fn indexes(&self) -> [usize; 3] {
+        let [m1, m2, m3] = self.raw_indexes();
+        if !self.revcomp && self.offset == 0 || self.first_raw().is_none() {
+            return [m1, m2, m3];
+        }
+        let map = |p: usize| self.strand_to_input(p).unwrap_or(0);
+        [map(m1), map(m2), if self.n == 3 { map(m3) } else { 0 }]
+    }
+
+    /// Strobe indices `[m1, m2, m3]` into `hashes`, without coordinate mapping.
+    #[inline(always)]
+    fn raw_indexes(&self) -> [usize; 3] {
+        [self.first_raw().unwrap_or(0), self.idx2, self.idx3]
+    }
+
+    /// Index into `hashes` of the current item's m1, `None` before the first item.
+    #[inline(always)]
+    fn first_raw(&self) -> Option<usize> {
+        if self.from_back { Some(self.back) } else { self.idx.checked_sub(1) }
+    }
+
+    /// Maps a k-mer index between the seeded and the forward strand of the hashed
+    /// sequence (an involution).
+    ///
+    /// Returns `None` for an index past the last k-mer in reverse-complement mode.
+    #[inline(always)]
+    fn to_forward(&self, p: usize) -> Option<usize> {
+        if self.revcomp { self.end_hash.checked_sub(p) } else { Some(p) }
+    }
+
+    /// Maps a k-mer index on the seeded strand to a position in the input sequence.
+    #[inline(always)]
+    fn strand_to_input(&self, p: usize) -> Option<usize> {
+        self.to_forward(p).map(|p| p + self.offset)
+    }
+
+    /// Maps a position in the input sequence to a k-mer index on the seeded strand.
+    ///
+    /// Returns `None` for a position before the region in
+    /// [`new_in_region`](Self::new_in_region) mode, or past the last k-mer in
+    /// reverse-complement mode.
+    #[inline(always)]
+    fn input_to_strand(&self, p: usize) -> Option<usize> {
+        self.to_forward(p.checked_sub(self.offset)?)
+    }
+
+    /// Chooses the position within `start..=end` with the lowest strategy score
+    /// (by default `(base_hash + hashes[pos]) & prime`), plus the distance and
+    /// quality penalties.
+    ///
+    /// # Arguments
+    ///
+    /// * `strobe` – Which strobe is being chosen (2 or 3); selects its hash vector.
+    /// * `base` – The hash value of the previous strobe (m1 or m2).
+    /// * `start`, `end` – Inclusive range of indices to consider for the next strobe.
+    /// * `upstream` – Scan from `end` down to `start` instead (mirrored windows
+    ///   in canonical mode), so leftmost ties and distance penalties favor
+    ///   positions nearest the previous strobe in both directions.
+    ///
+    /// # Returns
+    ///
+    /// *(best_pos, best_val, runner_up)* – Index of the chosen k-mer, its score,
+    /// and the second-lowest score in the window (`u64::MAX` if there is none,
+    /// or if no ambiguity margin is set).
+    ///
+    #[inline(always)]
+    fn choose_min(
+        &self,
+        strobe: usize,
+        base: u64,
+        start: usize,
+        end: usize,
+        upstream: bool,
+    ) -> (usize, u64, u64) {
+        // Plain scores over every candidate: score the window in SIMD lanes. A
+        // leftmost tie in upstream scan order is the rightmost position.
+        if self.step == 1
+            && self.valid.is_none()
+            && self.qual_penalty.is_none()
+            && self.distance_penalty == 0
+            && self.margin.is_none()
+        {
+            let window = &self.strobe_hashes(strobe)[start..=end];
+            let rightmost = (self.tie == TieBreak::Rightmost) != upstream;
+            let score = |h| self.strategy.score(base, h, self.prime);
+            if self.tie == TieBreak::SecondaryHash {
+                let (at, key) = lane_min(window, rightmost, |h| secondary_key(score(h), h));
+                return (start + at, (key >> 64) as u64, u64::MAX);
+            }
+            let (at, val) = lane_min(window, rightmost, score);
+            return (start + at, val, u64::MAX);
+        }
+
+        let mut best_pos = if upstream { end } else { start };
+        let mut best_val = u64::MAX;
+        let mut best_hash = u64::MAX;
+        let mut runner_up = u64::MAX;
+
+        let valid = self.valid.as_deref();
+        // Branch-free selects: candidate scores are random, so branches on them
+        // mispredict often
+        let mut consider = |pos: usize, offset: usize, h: u64| {
+            // Never pick k-mers with ambiguous bases
+            let ok = valid.is_none_or(|valid| valid[pos]);
+            let cand = self.score(base, h, pos, offset);
+            let replace = ok && self.tie.replaces(cand, h, best_val, best_hash);
+            // A replaced best becomes the runner-up; it never exceeds the old one
+            let demoted = if replace { best_val } else { cand };
+            runner_up = if ok { runner_up.min(demoted) } else { runner_up };
+            best_val = if replace { cand } else { best_val };
+            best_hash = if replace { h } else { best_hash };
+            best_pos = if replace { pos } else { best_pos };
+        };
+
+        let window = &self.strobe_hashes(strobe)[start..=end];
+        if upstream {
+            for (i, &h) in window.iter().rev().enumerate().step_by(self.step) {
+                consider(end - i, i, h);
+            }
+        } else {
+            for (i, &h) in window.iter().enumerate().step_by(self.step) {
+                consider(start + i, i, h);
+            }
+        }
+        (best_pos, best_val, runner_up)
+    }
+
+    /// Scores candidate hash `h` of k-mer `pos`, `offset` bases into its window,
+    /// against `base`.
+    #[inline(always)]
+    fn score(&self, base: u64, h: u64, pos: usize, offset: usize) -> u64 {
+        let qual = self.qual_penalty.as_ref().map_or(0, |penalty| penalty[pos]);
+        self.strategy
+            .score(base, h, self.prime)
+            .saturating_add(self.distance_penalty.saturating_mul(offset as u64))
+            .saturating_add(qual)
+    }
+
+    /// Returns the hash vector used for strobe `strobe` (1-based).
+    #[inline(always)]
+    fn strobe_hashes(&self, strobe: usize) -> &[u64] {
+        match strobe {
+            1 => &self.hashes,
+            _ => self.link_hashes.get(strobe - 2).unwrap_or(&self.hashes),
+        }
+    }
+
+    /// Returns whether a selection with score `best` and runner-up `runner_up`
+    /// falls within the ambiguity margin.
+    #[inline(always)]
+    fn is_ambiguous(&self, best: u64, runner_up: u64) -> bool {
+        self.margin.is_some_and(|m| runner_up - best <= m)
+    }
+
+    /// Computes the seed anchored at `i`, or `None` if its windows do not fit.
+    #[inline(always)]
+    fn anchor_seed(&mut self, i: usize) -> Option<u64> {
+        match self.n {
+            _ if self.canonical => Some(self.canonical_at(i)),
+            2 => self.order2_at(i),
+            3 => self.order3_at(i),
+            _ => None, // Should not occur due to prior validation
+        }
+    }
+
+    /// Applies the combiner and the density threshold to the current seed.
+    #[inline(always)]
+    fn emit(&self, h: u64) -> Option<u64> {
+        let strobes = self.raw_indexes();
+        let h = match &self.combiner {
+            Some(c) => self.recombine(c, strobes),
+            None => self.builtin(h, strobes),
+        };
+        if self.drops_strobes(strobes) {
+            return None;
+        }
+        // Drop seeds above the density threshold
+        (h <= self.threshold_at(strobes[0])).then_some(h)
+    }
+
+    /// Returns the density threshold for a seed anchored at raw index `i`.
+    #[inline(always)]
+    fn threshold_at(&self, i: usize) -> u64 {
+        match &self.gc_density {
+            Some(gc) => gc.threshold(self.to_forward(i).unwrap_or(0)),
+            None => self.threshold,
+        }
+    }
+
+    /// Returns whether no seed may start at `i`: it is excluded by
+    /// `set_first_strobe_positions`, overlaps an ambiguous base, is soft-masked,
+    /// or intersects a masked interval.
+    #[inline(always)]
+    fn skips_anchor(&self, i: usize) -> bool {
+        self.first_mask.as_ref().is_some_and(|mask| !mask[i])
+            || self.valid.as_ref().is_some_and(|valid| !valid[i])
+            || self.soft_mask.as_ref().is_some_and(|masked| masked[i])
+            || self.excluded.as_ref().is_some_and(|excluded| excluded[i])
+    }
+
+    /// Returns whether the seed with raw strobes `[m1, m2, m3]` is dropped for
+    /// its linked strobes: a window held only k-mers with ambiguous bases, a
+    /// strobe is soft-masked under [`SoftMask::SkipAnyStrobe`], or a strobe
+    /// intersects a masked interval.
+    #[inline(always)]
+    fn drops_strobes(&self, [_, m2, m3]: [usize; 3]) -> bool {
+        let links = [m2, m3];
+        let links = &links[..self.n as usize - 1];
+        self.valid.as_ref().is_some_and(|valid| links.iter().any(|&m| !valid[m]))
+            || self.soft_any
+                && self.soft_mask.as_ref().is_some_and(|masked| links.iter().any(|&m| masked[m]))
+            || self.excluded.as_ref().is_some_and(|excluded| links.iter().any(|&m| excluded[m]))
+    }
+
+    /// Returns whether seeds may be dropped after their anchor was visited.
+    fn is_filtered(&self) -> bool {
+        self.first_mask.is_some()
+            || self.valid.is_some()
+            || self.soft_mask.is_some()
+            || self.excluded.is_some()
+            || self.threshold < u64::MAX
+            || self.gc_density.is_some()
+    }
+
+    // -------------------- order-specific seeds --------------------------- //
+
+    /// Computes the RandStrobe of order 2 anchored at `i`.
+    ///
+    /// # Returns
+    /// - `Some(u64)` – Combined hash of m1 and m2, if available.
+    /// - `None` – When the window does not fit without shrinking.
+    ///
+    fn order2_at(&mut self, i: usize) -> Option<u64> {
+        // Define the search window for m2
+        let w_start = self.last_window_start(i);
+        let mut w_end = i + self.w_max;
+        if w_start > self.end_hash {
+            return None;
+        }
+        if w_end > self.end_hash {
+            if !self.shrink {
+                return None;
+            }
+            w_end = self.end_hash;
+        }
+        let w_end = self.span_end(i, w_end);
+
+        // Hash of the first k-mer (m1)
+        self.h1 = self.hashes[i];
+        // Choose m2 by minimizing `(h1 + hash[m2]) & prime`
+        let (pos2, best2, second2) = self.choose_min(2, self.h1, w_start, w_end, false);
+        self.idx2 = pos2;
+        self.unstable = self.is_ambiguous(best2, second2);
+        // Combine h1 and second k-mer’s hash
+        self.h2 = (self.h1 >> 1) + self.strobe_hashes(2)[pos2] / 3;
+
+        Some(self.h2)
+    }
+
+    /// Returns the start of anchor `i`'s last search window, raised so that a
+    /// strobe in it ends no earlier than the span floor.
+    #[inline(always)]
+    fn last_window_start(&self, i: usize) -> usize {
+        let links = self.n as usize - 1;
+        i + ((links - 1) * self.w_max + self.w_min).max(self.min_span.saturating_sub(self.k))
+    }
+
+    /// Moves the m3 window `(start, end)` of anchor `i`, placed relative to m1,
+    /// next to m2 at `pos2` under [`WindowAnchor::FromPreviousStrobe`].
+    ///
+    /// The moved window keeps the bound of `(start, end)` away from m1 and the
+    /// span floor, so it never leaves the range checked for the anchor.
+    #[inline(always)]
+    fn anchor_window(
+        &self,
+        i: usize,
+        pos2: usize,
+        (start, end): (usize, usize),
+        upstream: bool,
+    ) -> (usize, usize) {
+        if self.anchor == WindowAnchor::FromFirstStrobe {
+            return (start, end);
+        }
+        let floor = self.min_span.saturating_sub(self.k);
+        if upstream {
+            let end = (pos2 - self.w_min).min(i - floor);
+            ((pos2 - self.w_max).max(start).min(end), end)
+        } else {
+            let start = (pos2 + self.w_min).max(i + floor);
+            (start, (pos2 + self.w_max).min(end).max(start))
+        }
+    }
+
+    /// Cuts the window end `end` of anchor `i` so that no strobe ends past the span cap.
+    #[inline(always)]
+    fn span_end(&self, i: usize, end: usize) -> usize {
+        end.min(i.saturating_add(self.max_span - self.k))
+    }
+
+    /// Computes the RandStrobe of order 3 anchored at `i`.
+    ///
+    /// # Returns
+    /// - `Some(u64)` – Combined hash of m1, m2, and m3, if available.
+    /// - `None` – When no third strobe fits after `i`.
+    ///
+    fn order3_at(&mut self, i: usize) -> Option<u64> {
+        // First window range for selecting m2, shrunk or stopped at the sequence
+        // end on its own rather than relying on the second window's checks
+        let w1_start = i + self.w_min;
+        let mut w1_end = i + self.w_max;
+        if w1_start > self.end_hash {
+            return None;
+        }
+        if w1_end > self.end_hash {
+            if !self.shrink {
+                return None;
+            }
+            w1_end = self.end_hash;
+        }
+
+        // Second window range for selecting m3
+        let w2_start = self.last_window_start(i);
+        let mut w2_end = i + (self.w_max << 1);
+        if w2_start > self.end_hash {
+            return None;
+        }
+        if w2_end > self.end_hash {
+            if !self.shrink {
+                return None;
             }
             w2_end = self.end_hash;
         }
+        let w2_end = self.span_end(i, w2_end);
 
         // Compute m1 (first k-mer)
-        self.h1 = self.hashes[self.idx];
+        self.h1 = self.hashes[i];
         // Select m2
-        let (pos2, _) = self.choose_min(self.h1, w1_start, w1_end);
+        let (pos2, best2, second2) = self.choose_min(2, self.h1, w1_start, w1_end, false);
         self.idx2 = pos2;
-        self.h2 = self.h1 / 3     + (self.hashes[pos2] >> 2);
+        self.h2 = self.h1 / 3     + (self.strobe_hashes(2)[pos2] >> 2);
 
         // Select m3
-        let (pos3, _) = self.choose_min(self.h2, w2_start, w2_end);
+        let (w2_start, w2_end) = self.anchor_window(i, pos2, (w2_start, w2_end), false);
+        let (pos3, best3, second3) = self.choose_min(3, self.h2, w2_start, w2_end, false);
         self.idx3 = pos3;
-        self.h3 = self.h2 + self.hashes[pos3] / 5;
+        self.unstable = self.is_ambiguous(best2, second2) || self.is_ambiguous(best3, second3);
+        self.h3 = self.h2 + self.strobe_hashes(3)[pos3] / 5;
 
-        // Advance to next starting index for m1
-        self.idx += 1;
         Some(self.h3)
     }
 }
 
-impl Iterator for RandStrobes {
+impl<S: SelectionStrategy> RandStrobes<S> {
+    /// Computes the canonical seed anchored at `i`: the smaller of the
+    /// downstream and the mirrored upstream seed.
+    fn canonical_at(&mut self, i: usize) -> u64 {
+        let down = self.seed_at(i, false);
+        let up = self.seed_at(i, true);
+        let (hash, pos2, pos3, unstable) = if up.0 < down.0 {
+            self.strand = Some(Strand::Reverse);
+            up
+        } else {
+            self.strand = Some(Strand::Forward);
+            down
+        };
+        self.idx2 = pos2;
+        self.idx3 = pos3;
+        self.unstable = unstable;
+        hash
+    }
+
+    /// Forms the full-window seed anchored at `i`, downstream or mirrored upstream.
+    ///
+    /// Returns *(hash, m2, m3, unstable)*; `m3` is 0 for order 2.
+    fn seed_at(&self, i: usize, upstream: bool) -> (u64, usize, usize, bool) {
+        // Window of the j-th linked strobe (j = 0 for m2, 1 for m3), cut to the span cap
+        let reach = self.max_span - self.k;
+        let window = |j: usize| {
+            let shift = j * self.w_max;
+            // Offset of the window's near end, raised for the last strobe by the span floor
+            let near = match j + 1 == self.n as usize - 1 {
+                true => self.last_window_start(0),
+                false => shift + self.w_min,
+            };
+            if upstream {
+                ((i - shift - self.w_max).max(i.saturating_sub(reach)), i - near)
+            } else {
+                (i + near, self.span_end(i, i + shift + self.w_max))
+            }
+        };
+
+        let h1 = self.hashes[i];
+        let (start, end) = window(0);
+        let (pos2, best2, second2) = self.choose_min(2, h1, start, end, upstream);
+        let unstable = self.is_ambiguous(best2, second2);
+        if self.n == 2 {
+            let h2 = (h1 >> 1) + self.strobe_hashes(2)[pos2] / 3;
+            return (h2, pos2, 0, unstable);
+        }
+
+        let h2 = h1 / 3 + (self.strobe_hashes(2)[pos2] >> 2);
+        let (start, end) = self.anchor_window(i, pos2, window(1), upstream);
+        let (pos3, best3, second3) = self.choose_min(3, h2, start, end, upstream);
+        let h3 = h2 + self.strobe_hashes(3)[pos3] / 5;
+        (
+            h3,
+            pos2,
+            pos3,
+            unstable || self.is_ambiguous(best3, second3),
+        )
+    }
+}
+
+impl<S: SelectionStrategy> Iterator for RandStrobes<S> {
     type Item = u64;
 
     /// Advances the iterator, returning the next strobemer hash value.
     ///
-    /// Dispatches to `next_order2` or `next_order3` based on `self.n`.
+    /// Dispatches to `order2_at` or `order3_at` based on `self.n`.
     /// If `n` is not 2 or 3, returns `None`.
     fn next(&mut self) -> Option<Self::Item> {
-        match self.n {
-            2 => self.next_order2(),
-            3 => self.next_order3(),
-            _ => None, // Should not occur due to prior validation
+        self.next_until(usize::MAX).flatten()
+    }
+
+    /// Skips `n` seeds; without first-strobe positions or a density set,
+    /// every remaining anchor yields one seed, so skipped seeds are not computed.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.is_filtered() {
+            for _ in 0..n {
+                self.next()?;
+            }
+        } else if n < self.remaining_anchors() {
+            self.idx = self.on_stride(self.idx) + n * self.stride;
+        } else {
+            self.idx = self.back;
+            return None;
+        }
+        self.next()
+    }
+
+    /// Returns exact bounds unless seeds may be dropped after their anchor is
+    /// visited (see [`remaining_upper_bound`](Self::remaining_upper_bound)),
+    /// in which case the lower bound is 0.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = self.remaining_anchors();
+        (if self.is_filtered() { 0 } else { upper }, Some(upper))
+    }
+}
+
+impl<S: SelectionStrategy> DoubleEndedIterator for RandStrobes<S> {
+    /// Returns the remaining seed with the rightmost first strobe.
+    ///
+    /// Seeds are identical to those of forward iteration, which stops where
+    /// the two ends meet. After a call, [`indexes`](RandStrobes::indexes)
+    /// reports the strobes of the seed just returned. In reverse-complement
+    /// mode "rightmost" refers to the seeded strand, so the first strobe
+    /// closest to the start of the input comes first.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut i = self.last_complete_anchor()?;
+            // Skip start positions excluded by `set_first_strobe_positions` or ambiguous bases
+            while self.skips_anchor(i) {
+                i = i.checked_sub(self.stride).filter(|&i| i >= self.idx)?;
+            }
+            let h = self.anchor_seed(i)?;
+            self.back = i;
+            self.from_back = true;
+            if let Some(h) = self.emit(h) {
+                return Some(h);
+            }
         }
     }
 }
 
+impl<S: SelectionStrategy> FusedIterator for RandStrobes<S> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn collect_soa_rejects_positions_past_u32() {
+        let rs = RandStrobes::new(b"ACGATCTGGTACCTAG", 2, 3, 3, 5).unwrap();
+        // Pretend the sequence was cut from a region ending at u32::MAX
+        let (mut fits, mut overflows) = (rs.clone(), rs);
+        fits.offset = u32::MAX as usize - 13;
+        overflows.offset = fits.offset + 1;
+        let m1 = fits.collect_soa().unwrap().1;
+        assert_eq!(m1.last(), Some(&(u32::MAX - 13 + m1.len() as u32 - 1)));
+        assert_eq!(overflows.collect_soa().err(), Some(StrobeError::SequenceTooLong));
+    }
+
+    #[test]
+    fn poll_seeds_bounds_skipped_and_dropped_anchors() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC".repeat(20);
+        let mut rs = RandStrobes::new(&seq, 2, 3, 2, 5).unwrap();
+        rs.set_first_strobe_positions([700]);
+        let mut out = Vec::new();
+        assert!(!rs.poll_seeds(16, &mut out));
+        assert!(out.is_empty() && rs.idx <= 16);
+
+        let mut rs = RandStrobes::new(&seq, 2, 3, 2, 5).unwrap();
+        rs.set_density(1e-9).unwrap();
+        assert!(!rs.poll_seeds(16, &mut out));
+        assert!(rs.idx <= 16);
+        while !rs.poll_seeds(16, &mut out) {}
+        let mut all = RandStrobes::new(&seq, 2, 3, 2, 5).unwrap();
+        all.set_density(1e-9).unwrap();
+        assert_eq!(out, all.collect::<Vec<_>>());
+    }
+
     #[test]
     fn order2_basic() {
         // Basic smoke test: sequence "ACGTACGTACGT", order=2, k=3, w_min=1, w_max=4
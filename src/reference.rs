@@ -0,0 +1,211 @@
+//! Naive reference implementations for differential testing (feature `reference`).
+//!
+//! Each function recomputes every strobemer from scratch with plain window
+//! scans, mirroring the documented behavior of the optimized iterators
+//! (window shrinking, tie handling, stop conditions). They are slow on purpose
+//! and only meant to be compared against [`MinStrobes`] and [`RandStrobes`].
+//! Hashes follow [`CombineScheme::Legacy`], which selection is built on;
+//! [`mixed`] recombines them with the default [`CombineScheme::Mix`].
+
+use crate::{
+    CombineScheme, MinStrobes, RandStrobes, StrobeCombiner,
+    constants::DEFAULT_PRIME_NUMBER,
+    hashes::{KmerHasher, NtHash64},
+};
+
+/// One reference strobemer: combined hash and strobe positions `[m1, m2, m3]`.
+pub type RefSeed = (u64, [usize; 3]);
+
+/// Leftmost minimum of `score(hashes[pos])` over `start..=end` (empty → `(start, u64::MAX)`).
+fn leftmost_min(hashes: &[u64], start: usize, end: usize, score: impl Fn(u64) -> u64) -> (usize, u64) {
+    let mut best = (start, u64::MAX);
+    for (pos, &h) in hashes.iter().enumerate().take(end + 1).skip(start) {
+        if score(h) < best.1 {
+            best = (pos, score(h));
+        }
+    }
+    best
+}
+
+/// Rightmost minimum of `hashes[start..=end]`, matching the monotonic deque in
+/// `compute_min_hashes`, which keeps the newest of equal values.
+fn rightmost_min(hashes: &[u64], start: usize, end: usize) -> (usize, u64) {
+    let mut best = (start, u64::MAX);
+    for (pos, &h) in hashes.iter().enumerate().take(end + 1).skip(start) {
+        if h <= best.1 {
+            best = (pos, h);
+        }
+    }
+    best
+}
+
+/// Reference MinStrobes over precomputed k-mer `hashes` of a sequence of length `seq_len`.
+pub fn naive_minstrobes(
+    hashes: &[u64],
+    seq_len: usize,
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    shrink: bool,
+) -> Vec<RefSeed> {
+    let prime = DEFAULT_PRIME_NUMBER;
+    let end_hash = seq_len - k;
+    let end_idx = seq_len - n as usize * k;
+
+    let mut out = Vec::new();
+    for idx in 0..=end_idx {
+        let h1 = hashes[idx];
+        if n == 2 {
+            let w_start = idx + w_min;
+            let mut w_end = idx + w_max;
+            if w_end > end_hash {
+                if !shrink {
+                    break;
+                }
+                w_end = end_hash;
+            }
+            let (p2, m2) = if w_end == idx + w_max {
+                rightmost_min(hashes, w_start, w_end)
+            } else {
+                leftmost_min(hashes, w_start, w_end, |h| h)
+            };
+            out.push((h1 / 2 + m2 / 3, [idx, p2, 0]));
+        } else {
+            let w2_start = idx + w_max + w_min;
+            let mut w2_end = idx + 2 * w_max;
+            if w2_start > end_hash {
+                break;
+            }
+            if w2_end > end_hash {
+                if !shrink {
+                    break;
+                }
+                w2_end = end_hash;
+            }
+            let (p2, m2) = rightmost_min(hashes, idx + w_min, idx + w_max);
+            let h2 = h1 / 3 + m2 / 4;
+            let (p3, h3) = if w2_end == idx + 2 * w_max {
+                let (p3, m3) = rightmost_min(hashes, w2_start, w2_end);
+                (p3, h2 + m3 / 5)
+            } else {
+                let (p3, _) = leftmost_min(hashes, w2_start, w2_end, |h| h2.wrapping_add(h) & prime);
+                (p3, h2 + hashes[p3] / 5)
+            };
+            out.push((h3, [idx, p2, p3]));
+        }
+    }
+    out
+}
+
+/// Reference RandStrobes over precomputed k-mer `hashes` of a sequence of length `seq_len`.
+pub fn naive_randstrobes(
+    hashes: &[u64],
+    seq_len: usize,
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    shrink: bool,
+) -> Vec<RefSeed> {
+    let prime = DEFAULT_PRIME_NUMBER;
+    let end_hash = seq_len.saturating_sub(k);
+    let end_idx = seq_len.saturating_sub(n as usize * k);
+
+    let mut out = Vec::new();
+    for idx in 0..=end_idx {
+        let h1 = hashes[idx];
+        if n == 2 {
+            let mut w_end = idx + w_max;
+            if w_end > end_hash {
+                if !shrink {
+                    break;
+                }
+                w_end = end_hash;
+            }
+            let (p2, _) = leftmost_min(hashes, idx + w_min, w_end, |h| h1.wrapping_add(h) & prime);
+            out.push((h1 / 2 + hashes[p2] / 3, [idx, p2, 0]));
+        } else {
+            let w2_start = idx + w_max + w_min;
+            let mut w2_end = idx + 2 * w_max;
+            if w2_start > end_hash {
+                break;
+            }
+            if w2_end > end_hash {
+                if !shrink {
+                    break;
+                }
+                w2_end = end_hash;
+            }
+            let (p2, _) = leftmost_min(hashes, idx + w_min, idx + w_max, |h| h1.wrapping_add(h) & prime);
+            let h2 = h1 / 3 + hashes[p2] / 4;
+            let (p3, _) = leftmost_min(hashes, w2_start, w2_end, |h| h2.wrapping_add(h) & prime);
+            out.push((h2 + hashes[p3] / 5, [idx, p2, p3]));
+        }
+    }
+    out
+}
+
+/// Collects `(hash, indexes)` pairs from an iterator exposing `indexes()`.
+macro_rules! collect_with_indexes {
+    ($it:expr) => {{
+        let mut it = $it;
+        let mut v = Vec::new();
+        while let Some(h) = it.next() {
+            let [m1, m2, m3] = it.indexes();
+            v.push((h, [m1, m2, m3]));
+        }
+        v
+    }};
+}
+
+/// Recombines reference seeds of order `n` with [`CombineScheme::Mix`]; a
+/// strobe past the last k-mer (empty shrunk window) counts as `u64::MAX`.
+pub fn mixed(seeds: &[RefSeed], hashes: &[u64], n: u8) -> Vec<RefSeed> {
+    let strobe = |m: usize| hashes.get(m).copied().unwrap_or(u64::MAX);
+    seeds
+        .iter()
+        .map(|&(_, pos)| (CombineScheme::Mix.combine(&pos.map(strobe)[..n as usize]), pos))
+        .collect()
+}
+
+/// Asserts that both optimized iterators match the reference implementations,
+/// with [`CombineScheme::Legacy`] and with the default scheme.
+///
+/// Parameter sets rejected by the constructors are skipped. Panics with the
+/// offending parameters on the first mismatch, which makes it directly
+/// usable as a fuzz target body.
+pub fn assert_matches_reference(
+    seq: &[u8],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    shrink: bool,
+) {
+    let Ok(hashes) = NtHash64.hash_all(seq, k) else {
+        return;
+    };
+
+    if let Ok(mut ms) = MinStrobes::new(seq, n, k, w_min, w_max) {
+        ms.set_window_shrink(shrink);
+        let default: Vec<RefSeed> = collect_with_indexes!(ms.clone());
+        ms.set_combine_scheme(CombineScheme::Legacy);
+        let got: Vec<RefSeed> = collect_with_indexes!(ms);
+        let want = naive_minstrobes(&hashes, seq.len(), n, k, w_min, w_max, shrink);
+        assert_eq!(got, want, "MinStrobes mismatch: n={n} k={k} w=({w_min},{w_max}) shrink={shrink}");
+        let want = mixed(&want, &hashes, n);
+        assert_eq!(default, want, "MinStrobes mismatch: n={n} k={k} w=({w_min},{w_max}) shrink={shrink}");
+    }
+
+    if let Ok(mut rs) = RandStrobes::new(seq, n, k, w_min, w_max) {
+        rs.set_window_shrink(shrink);
+        let default: Vec<RefSeed> = collect_with_indexes!(rs.clone());
+        rs.set_combine_scheme(CombineScheme::Legacy);
+        let got: Vec<RefSeed> = collect_with_indexes!(rs);
+        let want = naive_randstrobes(&hashes, seq.len(), n, k, w_min, w_max, shrink);
+        assert_eq!(got, want, "RandStrobes mismatch: n={n} k={k} w=({w_min},{w_max}) shrink={shrink}");
+        let want = mixed(&want, &hashes, n);
+        assert_eq!(default, want, "RandStrobes mismatch: n={n} k={k} w=({w_min},{w_max}) shrink={shrink}");
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::{Result, StrobeError, randstrobes::RandStrobes};
+
+/// A region annotated as a tandem (satellite or telomeric) repeat.
+///
+/// Produced by [`detect_tandem_repeats`]. Coordinates are half-open `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepeatRegion {
+    /// First base of the region.
+    pub start: usize,
+    /// One past the last base of the region.
+    pub end: usize,
+    /// Most common distance between identical seeds, i.e. the estimated repeat unit length.
+    pub period: usize,
+    /// Fraction of the region's seeds that repeat an identical seed within `window` bases.
+    pub duplication: f64,
+}
+
+/// Detects tandem repeats from the local duplication and periodicity of RandStrobe seeds.
+///
+/// In a tandem repeat with unit length `p`, the k-mers, the selected strobe
+/// offsets and therefore the seeds themselves recur every `p` bases. Each
+/// seed is paired with the previous identical seed; if it lies at most
+/// `window` bases upstream, the seed counts as duplicated and the distance as
+/// a period observation. The sequence is split into consecutive bins of
+/// `window` first-strobe positions, bins whose duplicated fraction reaches
+/// `min_duplication` are flagged, and adjacent flagged bins are merged.
+///
+/// # Arguments
+///
+/// * `seq` – Input sequence.
+/// * `n`, `k`, `w_min`, `w_max` – Strobemer parameters, as for [`RandStrobes::new`].
+/// * `window` – Bin length, which is also the largest detectable period; must be ≥ 1.
+/// * `min_duplication` – Duplicated-seed fraction at or above which a bin is flagged.
+///
+/// # Returns
+///
+/// * `Ok(Vec<RepeatRegion>)` – Disjoint regions in increasing order.
+/// * `Err(StrobeError::InvalidWindowLength)` if `window` is 0.
+/// * `Err(StrobeError)` if the sequence or the strobemer parameters are rejected.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::detect_tandem_repeats;
+/// let mut seq = b"GACCTAGCATCGGTACGTTAGCCATGCAGTCAAGCTTGACCGTA".to_vec();
+/// seq.extend(b"TTAGGG".repeat(20));
+/// let regions = detect_tandem_repeats(&seq, 2, 5, 2, 6, 24, 0.8).unwrap();
+/// assert_eq!(regions.len(), 1);
+/// assert_eq!(regions[0].period, 6);
+/// ```
+pub fn detect_tandem_repeats(
+    seq: &[u8],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    window: usize,
+    min_duplication: f64,
+) -> Result<Vec<RepeatRegion>> {
+    if window == 0 {
+        return Err(StrobeError::InvalidWindowLength);
+    }
+    let mut rs = RandStrobes::new(seq, n, k, w_min, w_max)?;
+
+    // Per bin: number of seeds, duplicated seeds and period observations
+    let mut bins: Vec<(usize, usize, HashMap<usize, usize>)> = Vec::new();
+    let mut last_seen: HashMap<u64, usize> = HashMap::new();
+    while let Some(h) = rs.next() {
+        let pos = rs.index().unwrap_or(0);
+        let bin = pos / window;
+        if bins.len() <= bin {
+            bins.resize_with(bin + 1, Default::default);
+        }
+        let (seeds, dups, periods) = &mut bins[bin];
+        *seeds += 1;
+        if let Some(prev) = last_seen.insert(h, pos) {
+            let d = pos - prev;
+            if d <= window {
+                *dups += 1;
+                *periods.entry(d).or_default() += 1;
+            }
+        }
+    }
+
+    let nbins = bins.len();
+    let mut regions = Vec::new();
+    // Flagged run being merged: start bin, seeds, duplicated seeds, periods
+    let mut run: Option<(usize, usize, usize, HashMap<usize, usize>)> = None;
+    for (bin, (seeds, dups, periods)) in bins.into_iter().enumerate() {
+        let flagged = seeds > 0 && dups as f64 >= min_duplication * seeds as f64;
+        match (&mut run, flagged) {
+            (Some((_, s, d, p)), true) => {
+                *s += seeds;
+                *d += dups;
+                for (period, count) in periods {
+                    *p.entry(period).or_default() += count;
+                }
+            }
+            (None, true) => run = Some((bin, seeds, dups, periods)),
+            (_, false) => {
+                if let Some(r) = run.take() {
+                    regions.push(region(r, bin, window, seq.len()));
+                }
+            }
+        }
+    }
+    if let Some(r) = run {
+        regions.push(region(r, nbins, window, seq.len()));
+    }
+    Ok(regions)
+}
+
+/// Builds the region for flagged bins `start_bin..end_bin`.
+fn region(
+    (start_bin, seeds, dups, periods): (usize, usize, usize, HashMap<usize, usize>),
+    end_bin: usize,
+    window: usize,
+    seq_len: usize,
+) -> RepeatRegion {
+    // Most frequent period; ties go to the shorter one
+    let period = periods
+        .into_iter()
+        .max_by_key(|&(p, c)| (c, std::cmp::Reverse(p)))
+        .map_or(0, |(p, _)| p);
+    RepeatRegion {
+        start: start_bin * window,
+        end: (end_bin * window).min(seq_len),
+        period,
+        duplication: dups as f64 / seeds as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_sequence_has_no_repeats() {
+        let seq = b"GACCTAGCATCGGTACGTTAGCCATGCAGTCAAGCTTGACCGTAGGCTATCAGTGCA";
+        let regions = detect_tandem_repeats(seq, 2, 5, 2, 6, 20, 0.5).unwrap();
+        assert!(regions.is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+use std::iter::FusedIterator;
+
+use crate::{
+    CombineScheme, Result, StrobeCombiner, StrobeError, Strobemer,
+    constants::DEFAULT_PRIME_NUMBER,
+    hashes::KmerHasher,
+    selection::{MaskedSum, SelectionStrategy, lane_min},
+    streaming::{StreamingHashes, StreamingKmerHasher},
+};
+
+/// RandStrobes iterator holding only the k-mer hashes its windows still need.
+///
+/// Built by [`RandStrobes::streaming`](crate::RandStrobes::streaming). Hashes
+/// are pulled from a [`StreamingKmerHasher`] into a ring buffer of the last
+/// `(n − 1)·w_max + 1` values, so memory beyond the borrowed sequence is
+/// O(w_max) instead of 8 bytes per base; seeding a chromosome needs a few
+/// kilobytes of hashes rather than gigabytes.
+///
+/// Seeds and positions are those of
+/// [`RandStrobes::with_hasher`](crate::RandStrobes::with_hasher) with default
+/// settings, or with the same [`CombineScheme`] after
+/// [`set_combine_scheme`](Self::set_combine_scheme), for hashers without a
+/// [`validity`](KmerHasher::validity) mask (streaming hashers produce hashes
+/// only). A hashing error is yielded once, after which the iterator is
+/// exhausted.
+#[derive(Debug, Clone)]
+pub struct StreamingRandStrobes<'a, H: StreamingKmerHasher> {
+    hashes: StreamingHashes<'a, H>,
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    scheme: CombineScheme,
+    end_hash: usize, // Index of the last k-mer hash
+    end_idx: usize,  // Last index at which a complete strobemer can start
+    ring: Vec<u64>,  // Hash of k-mer `j` at `j & mask` and again `mask + 1` later
+    mask: usize,     // Ring capacity minus one (a power of two)
+    filled: usize,   // Hashes pulled from the stream so far
+    idx: usize,      // Next first-strobe position
+    done: bool,      // End of the sequence or error reached
+}
+
+impl<'a, H: StreamingKmerHasher> StreamingRandStrobes<'a, H> {
+    pub(crate) fn new(
+        seq: &'a [u8],
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+        hasher: &'a H,
+    ) -> Result<Self>
+    where
+        H: KmerHasher,
+    {
+        validate_params!(seq, n, k, w_min, w_max, hasher);
+
+        // Hashes from m1 to the end of the last window must be held at once
+        let cap = ((n as usize - 1) * w_max + 1).next_power_of_two();
+        let end_hash = seq.len() - k;
+        Ok(Self {
+            hashes: hasher.stream(seq, k)?,
+            n,
+            k,
+            w_min,
+            w_max,
+            scheme: CombineScheme::default(),
+            end_hash,
+            end_idx: end_hash - (n as usize - 1) * k,
+            ring: vec![0; 2 * cap],
+            mask: cap - 1,
+            filled: 0,
+            idx: 0,
+            done: false,
+        })
+    }
+
+    /// Selects the combination of strobe hashes (default
+    /// [`CombineScheme::Mix`]); strobe selection is unaffected.
+    pub fn set_combine_scheme(&mut self, scheme: CombineScheme) {
+        self.scheme = scheme;
+    }
+
+    /// Pulls hashes from the stream until the one of k-mer `j` is held.
+    fn fill(&mut self, j: usize) -> Result<()> {
+        while self.filled <= j {
+            let h = self.hashes.next().ok_or(StrobeError::IncompleteHashValues)??;
+            let slot = self.filled & self.mask;
+            self.ring[slot] = h;
+            self.ring[slot + self.mask + 1] = h;
+            self.filled += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the held hash of k-mer `j`.
+    #[inline(always)]
+    fn hash(&self, j: usize) -> u64 {
+        self.ring[j & self.mask]
+    }
+
+    /// Chooses the k-mer in `start..=end` minimizing `(base + hash) & prime`,
+    /// ties to the leftmost; returns its index.
+    #[inline(always)]
+    fn choose_min(&self, base: u64, start: usize, end: usize) -> usize {
+        let from = start & self.mask;
+        let window = &self.ring[from..=from + (end - start)];
+        start + lane_min(window, false, |h| MaskedSum.score(base, h, DEFAULT_PRIME_NUMBER)).0
+    }
+
+    /// Computes the seed anchored at `i`, or `None` once no window fits.
+    fn seed_at(&mut self, i: usize) -> Option<Result<Strobemer>> {
+        let links = self.n as usize - 1;
+        // Start of the last window; the windows shrink at the sequence end
+        let last_start = i + (links - 1) * self.w_max + self.w_min;
+        if i > self.end_idx || last_start > self.end_hash {
+            return None;
+        }
+        if let Err(e) = self.fill((i + links * self.w_max).min(self.end_hash)) {
+            return Some(Err(e));
+        }
+
+        let h1 = self.hash(i);
+        let w1_end = (i + self.w_max).min(self.end_hash);
+        let m2 = self.choose_min(h1, i + self.w_min, w1_end);
+        let (legacy, m3) = if self.n == 2 {
+            ((h1 >> 1) + self.hash(m2) / 3, 0)
+        } else {
+            let h2 = h1 / 3 + (self.hash(m2) >> 2);
+            let m3 = self.choose_min(h2, last_start, (i + 2 * self.w_max).min(self.end_hash));
+            (h2 + self.hash(m3) / 5, m3)
+        };
+        let hash = match self.scheme {
+            CombineScheme::Legacy => legacy,
+            scheme => scheme.combine(&[h1, self.hash(m2), self.hash(m3)][..self.n as usize]),
+        };
+        Some(Ok(Strobemer::new(hash, [i, m2, m3], self.n, self.k)))
+    }
+}
+
+impl<H: StreamingKmerHasher> Iterator for StreamingRandStrobes<'_, H> {
+    type Item = Result<Strobemer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let seed = self.seed_at(self.idx);
+        self.idx += 1;
+        self.done = !matches!(seed, Some(Ok(_)));
+        seed
+    }
+}
+
+impl<H: StreamingKmerHasher> FusedIterator for StreamingRandStrobes<'_, H> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KmerCodeHasher, NtHash64, RandStrobes};
+
+    #[test]
+    fn ring_holds_only_the_windows() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC".repeat(50);
+        let it = RandStrobes::streaming(&seq, 3, 5, 4, 12, &NtHash64).unwrap();
+        assert_eq!(it.ring.len(), 2 * 32);
+        let seeds: Vec<Strobemer> = it.map(|s| s.unwrap()).collect();
+        let whole = RandStrobes::with_hasher(&seq, 3, 5, 4, 12, &NtHash64).unwrap();
+        assert_eq!(seeds, whole.iter_seeds().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn hashing_errors_end_iteration() {
+        let seq = b"ACGATCTGGTACCTAGGATTANCACGATCGG";
+        let mut it = RandStrobes::streaming(seq, 2, 3, 2, 4, &KmerCodeHasher).unwrap();
+        assert!(it.by_ref().take_while(|s| s.is_ok()).count() > 0);
+        assert!(it.next().is_none());
+    }
+}
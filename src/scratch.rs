@@ -0,0 +1,38 @@
+/// Reusable working buffers for constructing generators.
+///
+/// Generators allocate sequence-length buffers per construction: the k-mer
+/// hashes and, for those precomputing sliding-window minima
+/// ([`MinStrobes`](crate::MinStrobes), [`ModMinimizers`](crate::ModMinimizers),
+/// [`BdAnchors`](crate::BdAnchors)), the minima. When seeding
+/// many sequences, keep one `Scratch` per thread, build each generator with
+/// its `with_scratch` constructor and hand the buffers back with `recycle`
+/// once the generator is exhausted; after warm-up, construction no longer
+/// allocates for these buffers.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{MinStrobes, NtHash64, Scratch};
+/// let mut scratch = Scratch::new();
+/// for read in [&b"ACGATCTGGTACCTAG"[..], b"GGATTACACGATCGGATC"] {
+///     let mut ms = MinStrobes::with_scratch(read, 2, 3, 3, 5, &NtHash64, &mut scratch).unwrap();
+///     let seeds: Vec<u64> = ms.by_ref().collect();
+///     assert!(!seeds.is_empty());
+///     ms.recycle(&mut scratch);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scratch {
+    pub(crate) hashes: Vec<u64>,   // k-mer hashes
+    pub(crate) minloc: Vec<usize>, // Window-minimum locations
+    pub(crate) minloc32: Vec<u32>, // Window-minimum locations of `u32` tables
+    pub(crate) minval: Vec<u64>,   // Window-minimum values
+    pub(crate) idx_q: Vec<usize>,  // Monotonic deque: positions
+    pub(crate) val_q: Vec<u64>,    // Monotonic deque: hash values
+}
+
+impl Scratch {
+    /// Creates an empty scratch space; buffers grow on first use.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
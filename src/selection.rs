@@ -0,0 +1,294 @@
+use crate::alphabet::mix;
+
+/// Scoring rule used by [`RandStrobes`](crate::RandStrobes) to pick the next strobe.
+///
+/// For every candidate k-mer in the search window, the iterator calls
+/// [`score`](SelectionStrategy::score) with the hash of the previous strobe
+/// (`base`), the candidate's hash, and the current prime mask. The candidate
+/// with the **lowest** score wins; ties resolve as set by [`TieBreak`]
+/// (leftmost by default).
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{RandStrobes, SelectionStrategy};
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct MinPopcountXor;
+/// impl SelectionStrategy for MinPopcountXor {
+///     fn score(&self, base: u64, candidate: u64, _prime: u64) -> u64 {
+///         (base ^ candidate).count_ones() as u64
+///     }
+/// }
+///
+/// let rs = RandStrobes::new(b"ACGTACGTACGT", 2, 3, 1, 4)
+///     .unwrap()
+///     .with_strategy(MinPopcountXor);
+/// assert!(rs.count() > 0);
+/// ```
+pub trait SelectionStrategy: Send + Sync + 'static {
+    /// Returns the score of `candidate` given the previous strobe hash `base`.
+    fn score(&self, base: u64, candidate: u64, prime: u64) -> u64;
+}
+
+/// Default strategy: `(base + candidate) & prime`, the original RandStrobe link function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaskedSum;
+
+impl SelectionStrategy for MaskedSum {
+    #[inline(always)]
+    fn score(&self, base: u64, candidate: u64, prime: u64) -> u64 {
+        base.wrapping_add(candidate) & prime
+    }
+}
+
+/// Min-XOR strategy: `(base ^ candidate) & prime`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MinXor;
+
+impl SelectionStrategy for MinXor {
+    #[inline(always)]
+    fn score(&self, base: u64, candidate: u64, prime: u64) -> u64 {
+        (base ^ candidate) & prime
+    }
+}
+
+/// Min-absolute-difference strategy: `|base − candidate| & prime`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MinAbsDiff;
+
+impl SelectionStrategy for MinAbsDiff {
+    #[inline(always)]
+    fn score(&self, base: u64, candidate: u64, prime: u64) -> u64 {
+        base.abs_diff(candidate) & prime
+    }
+}
+
+/// Which of several window positions sharing the minimal score is selected.
+///
+/// Other strobemer implementations differ in how they break ties, so matching
+/// one exactly requires matching its rule. Set with `set_tie_break` on
+/// [`RandStrobes`](crate::RandStrobes) (default [`Leftmost`](Self::Leftmost))
+/// or [`MinStrobes`](crate::MinStrobes), and with
+/// [`compute_min_hashes_with`](crate::compute_min_hashes_with).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// The first tied candidate in scan order, i.e. the leftmost (mirrored
+    /// windows of canonical seeds are scanned right to left).
+    #[default]
+    Leftmost,
+    /// The last tied candidate in scan order, i.e. the rightmost.
+    Rightmost,
+    /// The tied candidate whose k-mer hash has the smallest secondary hash
+    /// (a SplitMix64 remix); remaining ties, i.e. equal k-mer hashes, go to
+    /// the leftmost. Independent of scan direction, so equal windows select
+    /// the same k-mer whichever way they are scanned.
+    SecondaryHash,
+}
+
+impl TieBreak {
+    /// Returns whether a candidate with `score` and k-mer `hash`, scanned
+    /// after the current best, replaces it.
+    #[inline(always)]
+    pub(crate) fn replaces(self, score: u64, hash: u64, best: u64, best_hash: u64) -> bool {
+        match self {
+            Self::Leftmost => score < best,
+            Self::Rightmost => score <= best,
+            Self::SecondaryHash => {
+                score < best || (score == best && mix(hash) < mix(best_hash))
+            }
+        }
+    }
+}
+
+/// Where the search window of the third strobe of an order-3 seed is anchored.
+///
+/// Reference implementations differ here: some place every window at fixed
+/// offsets from m1, others place each window relative to the strobe selected
+/// before it, which spreads spans wider and changes seed statistics. Set with
+/// `set_window_anchor` on [`RandStrobes`](crate::RandStrobes) or
+/// [`MinStrobes`](crate::MinStrobes). Order-2 seeds are unaffected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowAnchor {
+    /// m3 is searched in `[i + w_max + w_min, i + 2·w_max]` for m1 at `i`
+    /// (the original behavior).
+    #[default]
+    FromFirstStrobe,
+    /// m3 is searched in `[m2 + w_min, m2 + w_max]`, for m2 as selected.
+    FromPreviousStrobe,
+}
+
+/// Number of window candidates scored side by side by [`lane_min`].
+const LANES: usize = 8;
+
+/// Score type [`lane_min`] minimizes: plain `u64` scores, or `u128` keys
+/// ordering by score, then by secondary hash (see [`secondary_key`]).
+pub(crate) trait LaneScore: Copy + Ord {
+    /// Score no candidate exceeds.
+    const MAX: Self;
+}
+
+impl LaneScore for u64 {
+    const MAX: Self = u64::MAX;
+}
+
+impl LaneScore for u128 {
+    const MAX: Self = u128::MAX;
+}
+
+/// Key of a candidate with `score` and k-mer hash `hash` under
+/// [`TieBreak::SecondaryHash`]: equal scores order by the hash's remix.
+/// Equal keys only arise from equal hashes, which tie to the leftmost.
+#[inline(always)]
+pub(crate) fn secondary_key(score: u64, hash: u64) -> u128 {
+    (score as u128) << 64 | mix(hash) as u128
+}
+
+/// Returns the offset into `window` and the score of its lowest-scoring
+/// candidate, ties going to the leftmost (or, if `rightmost`, the rightmost);
+/// an all-`MAX` window yields its first (or last) candidate.
+///
+/// Candidates are scored eight at a time into independent per-lane minima
+/// that are merged at the end. Every update, the merge and the tail are
+/// branch-free selects (`cmov` on x86-64, `csel` on AArch64), as hash scores
+/// are random and a compare-and-branch scan mispredicts on a large share of
+/// candidates. The lane updates carry no dependency from one candidate to
+/// the next, so the compiler keeps the lanes in vector registers (SSE2/AVX2
+/// on x86-64, NEON on AArch64) on stable Rust and without `unsafe`. Results
+/// equal those of a scalar scan with [`TieBreak::Leftmost`] or
+/// [`TieBreak::Rightmost`], or with [`TieBreak::SecondaryHash`] over
+/// [`secondary_key`] scores.
+#[inline(always)]
+pub(crate) fn lane_min<T: LaneScore>(
+    window: &[u64],
+    rightmost: bool,
+    score: impl Fn(u64) -> T,
+) -> (usize, T) {
+    let mut best = [T::MAX; LANES];
+    let mut best_at: [usize; LANES] = std::array::from_fn(|j| j);
+    let chunks = window.chunks_exact(LANES);
+    let tail = chunks.remainder();
+    for (c, chunk) in chunks.enumerate() {
+        for j in 0..LANES {
+            let s = score(chunk[j]);
+            let better = if rightmost { s <= best[j] } else { s < best[j] };
+            best[j] = if better { s } else { best[j] };
+            best_at[j] = if better { c * LANES + j } else { best_at[j] };
+        }
+    }
+
+    // Merge the lanes, then continue with the candidates left over
+    let (mut at, mut val) = (if rightmost { window.len().saturating_sub(1) } else { 0 }, T::MAX);
+    if window.len() >= LANES {
+        (at, val) = (best_at[0], best[0]);
+        for j in 1..LANES {
+            let better = best[j] < val || best[j] == val && (best_at[j] > at) == rightmost;
+            at = if better { best_at[j] } else { at };
+            val = if better { best[j] } else { val };
+        }
+    }
+    let offset = window.len() - tail.len();
+    for (j, &h) in tail.iter().enumerate() {
+        let s = score(h);
+        let better = s < val || rightmost && s == val;
+        at = if better { offset + j } else { at };
+        val = if better { s } else { val };
+    }
+    (at, val)
+}
+
+/// Coarse accuracy-versus-throughput preset for strobe selection.
+///
+/// Cheaper modes sample the candidates of each search window instead of
+/// scanning all of them: only offsets `0, s, 2s, …` from the window start are
+/// scored, where `s` is the [`candidate_step`](Self::candidate_step). The
+/// sampled offsets are relative to the previous strobe, so identical sequence
+/// still yields identical seeds and exact matches are found as before. What
+/// degrades is robustness to indels: an insertion or deletion between two
+/// strobes shifts the best candidate by one base, and with a step `s` the
+/// shifted candidate is only scored again if the shift is a multiple of `s`.
+/// Seeds also spread over fewer distinct spans, which raises the chance of
+/// spurious hash collisions. Selection cost per seed drops by roughly `s`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PerformanceMode {
+    /// Score every candidate (the original behavior).
+    #[default]
+    Exact,
+    /// Score every second candidate.
+    Balanced,
+    /// Score every fourth candidate.
+    Fast,
+}
+
+impl PerformanceMode {
+    /// Returns the distance between scored candidates in a search window.
+    pub const fn candidate_step(self) -> usize {
+        match self {
+            Self::Exact => 1,
+            Self::Balanced => 2,
+            Self::Fast => 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_scores() {
+        let prime = 0xFF;
+        assert_eq!(MaskedSum.score(0x1F0, 0x20, prime), 0x10);
+        assert_eq!(MinXor.score(0b1010, 0b0110, prime), 0b1100);
+        assert_eq!(MinAbsDiff.score(3, 10, prime), 7);
+        assert_eq!(MinAbsDiff.score(10, 3, prime), 7);
+    }
+
+    #[test]
+    fn lane_min_matches_scalar_scan() {
+        let mut state = 3u64;
+        for len in 1..40 {
+            for spread in [2, 1 << 20] {
+                let window: Vec<u64> = (0..len)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        match state >> 60 {
+                            0 => u64::MAX,
+                            _ => (state >> 33) % spread,
+                        }
+                    })
+                    .collect();
+                for (tie, rightmost) in [(TieBreak::Leftmost, false), (TieBreak::Rightmost, true)] {
+                    let score = |h: u64| MaskedSum.score(7, h, u64::MAX >> 1);
+                    let (mut at, mut val) = (0, u64::MAX);
+                    for (i, &h) in window.iter().enumerate() {
+                        if tie.replaces(score(h), h, val, u64::MAX) {
+                            (at, val) = (i, score(h));
+                        }
+                    }
+                    assert_eq!(lane_min(&window, rightmost, score), (at, val), "{window:?}");
+                }
+                let score = |h: u64| MaskedSum.score(7, h, 0xff);
+                let (mut at, mut val, mut best_hash) = (0, u64::MAX, u64::MAX);
+                for (i, &h) in window.iter().enumerate() {
+                    if TieBreak::SecondaryHash.replaces(score(h), h, val, best_hash) {
+                        (at, val, best_hash) = (i, score(h), h);
+                    }
+                }
+                let (lane_at, key) = lane_min(&window, false, |h| secondary_key(score(h), h));
+                assert_eq!((lane_at, (key >> 64) as u64), (at, val), "{window:?}");
+            }
+            assert_eq!(lane_min(&vec![u64::MAX; len], true, |h| h), (len - 1, u64::MAX));
+            assert_eq!(lane_min(&vec![u64::MAX; len], false, |h| h), (0, u64::MAX));
+        }
+    }
+
+    #[test]
+    fn tie_breaks() {
+        assert!(!TieBreak::Leftmost.replaces(5, 1, 5, 2));
+        assert!(TieBreak::Rightmost.replaces(5, 1, 5, 2));
+        assert_eq!(TieBreak::SecondaryHash.replaces(5, 1, 5, 2), mix(1) < mix(2));
+        assert!(!TieBreak::SecondaryHash.replaces(5, 1, 5, 1));
+        assert!(TieBreak::Leftmost.replaces(4, 9, 5, 2));
+        assert!(!TieBreak::Rightmost.replaces(6, 9, 5, 2));
+    }
+}
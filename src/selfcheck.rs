@@ -0,0 +1,52 @@
+use crate::{
+    KmerCodeHasher, LegacyCombiner, MinStrobes, RandStrobes, Result, StrobeCombiner, StrobeError,
+    hashes::{KmerHasher, compute_min_hashes},
+};
+
+/// Snapshot sequence shared with the regression tests.
+const SEQ: &[u8] = b"ACGATCTGGTACCTAG";
+
+/// Runs known-answer tests of the hashing and seeding pipeline on the running target.
+///
+/// All hashing is pure integer arithmetic on `u64` and positions never leave
+/// `usize`, so results must not depend on pointer width or byte order. This
+/// check confirms that at runtime, which is useful on platforms without CI
+/// coverage (e.g. 32-bit ARM or big-endian appliances): it compares 2-bit
+/// k-mer codes, the legacy combiner, sliding-window minima and the first
+/// MinStrobes/RandStrobes hashes of a fixed sequence against values recorded
+/// on x86_64.
+///
+/// # Returns
+///
+/// * `Ok(())` if every value matches.
+/// * `Err(StrobeError::SelfCheckFailed)` naming the first component that differs.
+pub fn self_check() -> Result<()> {
+    let check = |ok: bool, what: &'static str| match ok {
+        true => Ok(()),
+        false => Err(StrobeError::SelfCheckFailed(what)),
+    };
+
+    let codes = KmerCodeHasher.hash_all(SEQ, SEQ.len())?;
+    check(codes == [0x18de_b172], "2-bit k-mer codes")?;
+
+    check(
+        LegacyCombiner.combine(&[u64::MAX, u64::MAX - 1]) == 0xd555_5555_5555_5553
+            && LegacyCombiner.combine(&[u64::MAX, 1 << 63, 12345]) == 0x7555_5555_5555_5efa,
+        "legacy combiner",
+    )?;
+
+    let (locs, mins) = compute_min_hashes(&[5, 3, 3, 7, 1, u64::MAX], 3);
+    check(locs[2..] == [2, 2, 4, 4] && mins[2..] == [3, 3, 1, 1], "window minima")?;
+
+    let first = |v: Option<u64>| v.unwrap_or(0);
+    check(
+        first(MinStrobes::new(SEQ, 2, 3, 3, 5)?.next()) == 5034711282162315152
+            && first(MinStrobes::new(SEQ, 3, 3, 3, 5)?.next()) == 17242662893449726642,
+        "MinStrobes snapshot",
+    )?;
+    check(
+        first(RandStrobes::new(SEQ, 2, 3, 3, 5)?.next()) == 6611472613047971768
+            && first(RandStrobes::new(SEQ, 3, 3, 3, 5)?.next()) == 15218581588448553222,
+        "RandStrobes snapshot",
+    )
+}
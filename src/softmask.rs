@@ -0,0 +1,40 @@
+/// Which seeds to drop for soft-masked (lowercase) sequence.
+///
+/// Repeat annotators such as RepeatMasker lowercase the bases they mask.
+/// Hashing ignores case, so without this option repeats are seeded like any
+/// other sequence. A strobe counts as soft-masked if all of its k bases are
+/// lowercase. See `set_soft_mask` on the iterators.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SoftMask {
+    /// Seed soft-masked sequence like any other (the original behavior).
+    #[default]
+    Ignore,
+    /// Drop seeds whose first strobe is soft-masked.
+    SkipFirstStrobe,
+    /// Drop seeds with any soft-masked strobe.
+    SkipAnyStrobe,
+}
+
+/// Returns, per k-mer start of `seq`, whether all k bases are lowercase.
+pub(crate) fn soft_masked_kmers(seq: &[u8], k: usize) -> Vec<bool> {
+    let mut masked = Vec::with_capacity((seq.len() + 1).saturating_sub(k));
+    // Length of the lowercase run ending at the current base, capped at k
+    let mut run = 0usize;
+    for (i, &b) in seq.iter().enumerate() {
+        run = if b.is_ascii_lowercase() { (run + 1).min(k) } else { 0 };
+        if i + 1 >= k {
+            masked.push(run == k);
+        }
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kmers_fully_lowercase() {
+        assert_eq!(soft_masked_kmers(b"ACgtaCG", 2), [false, false, true, true, false, false]);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{Result, StrobeError, hashes::KmerHasher};
+
+/// Number of windows whose care bases are gathered per inner hashing pass.
+const SPACED_BLOCK: usize = 1024;
+
+/// A [`KmerHasher`] that hashes k-mers through a spaced-seed mask.
+///
+/// The mask is an ASCII string of `1` (care) and `0` (don't care) positions,
+/// e.g. `b"1101011"`. Each k-mer of length `mask.len()` is reduced to its care
+/// bases, which are then hashed with the inner hasher. Substitutions at
+/// don't-care positions therefore leave the hash unchanged.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerHasher, NtHash64, SpacedSeed};
+/// let spaced = SpacedSeed::new(b"11011", NtHash64).unwrap();
+/// let a = spaced.hash_all(b"ACGTA", 5).unwrap();
+/// let b = spaced.hash_all(b"ACTTA", 5).unwrap();
+/// assert_eq!(a, b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpacedSeed<H> {
+    offsets: Vec<usize>, // Care positions within the k-mer
+    span: usize,         // Mask length (k)
+    inner: H,            // Hasher applied to the gathered care bases
+}
+
+impl<H: KmerHasher> SpacedSeed<H> {
+    /// Creates a spaced hasher from a `1`/`0` mask and an inner hasher.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SpacedSeed)` on success.
+    /// * `Err(StrobeError::InvalidSpacedMask)` if the mask is rejected by [`parse_mask`].
+    pub fn new(mask: &[u8], inner: H) -> Result<Self> {
+        let offsets = parse_mask(mask)?;
+        Ok(Self {
+            offsets,
+            span: mask.len(),
+            inner,
+        })
+    }
+}
+
+impl<H: KmerHasher> KmerHasher for SpacedSeed<H> {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        if k != self.span {
+            return Err(StrobeError::InvalidSpacedMask);
+        }
+        hash_all_spaced(seq, &self.offsets, self.span, &self.inner)
+    }
+}
+
+/// Parses a spaced-seed mask into the offsets of its care positions.
+///
+/// A valid mask is 1 to 64 bytes of ASCII `1`/`0` that starts and ends with `1`.
+pub fn parse_mask(mask: &[u8]) -> Result<Vec<usize>> {
+    if !(1..=64).contains(&mask.len())
+        || mask.first() != Some(&b'1')
+        || mask.last() != Some(&b'1')
+        || mask.iter().any(|&b| b != b'0' && b != b'1')
+    {
+        return Err(StrobeError::InvalidSpacedMask);
+    }
+    Ok(mask
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &b)| (b == b'1').then_some(i))
+        .collect())
+}
+
+/// Hashes every `span`-long window of `seq` through the care `offsets`.
+///
+/// Windows are processed in blocks: the care bases of consecutive windows are
+/// concatenated, hashed in one pass with `inner`, and every `weight`-th hash
+/// (the ones aligned with a window) is kept.
+pub(crate) fn hash_all_spaced<H: KmerHasher + ?Sized>(
+    seq: &[u8],
+    offsets: &[usize],
+    span: usize,
+    inner: &H,
+) -> Result<Vec<u64>> {
+    if seq.len() < span {
+        return Err(StrobeError::SequenceTooShort);
+    }
+    let weight = offsets.len();
+    let total = seq.len() - span + 1;
+
+    let mut out = Vec::with_capacity(total);
+    let mut buf = Vec::with_capacity(SPACED_BLOCK * weight);
+    for block in (0..total).step_by(SPACED_BLOCK) {
+        buf.clear();
+        let end = (block + SPACED_BLOCK).min(total);
+        for pos in block..end {
+            buf.extend(offsets.iter().map(|&o| seq[pos + o]));
+        }
+        let hashes = inner.hash_all(&buf, weight)?;
+        if hashes.len() != buf.len() - weight + 1 {
+            return Err(StrobeError::IncompleteHashValues);
+        }
+        out.extend(hashes.into_iter().step_by(weight));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_validation() {
+        assert_eq!(parse_mask(b"1011").unwrap(), vec![0, 2, 3]);
+        assert!(parse_mask(b"0110").is_err());
+        assert!(parse_mask(b"1x1").is_err());
+        assert!(parse_mask(b"").is_err());
+    }
+}
@@ -0,0 +1,388 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    CombineScheme, MinStrobes, RandStrobes, Result, StrobeError, Strobemer,
+    hashes::NtHash64,
+    scratch::Scratch,
+    selection::{MaskedSum, MinAbsDiff, MinXor, SelectionStrategy},
+};
+
+/// Strobemer protocol named by a [`StrobeSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// [`RandStrobes`], written `rand`.
+    Rand,
+    /// [`MinStrobes`], written `min`.
+    Min,
+}
+
+/// Built-in selection strategy named by a [`StrobeSpec`].
+///
+/// Dispatches to [`MaskedSum`], [`MinXor`] or [`MinAbsDiff`], so iterators
+/// built from a spec share one concrete type whatever the strategy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// [`MaskedSum`], the default (not written).
+    #[default]
+    Sum,
+    /// [`MinXor`], written `xor`.
+    Xor,
+    /// [`MinAbsDiff`], written `absdiff`.
+    AbsDiff,
+}
+
+impl SelectionStrategy for Selection {
+    #[inline(always)]
+    fn score(&self, base: u64, candidate: u64, prime: u64) -> u64 {
+        match self {
+            Self::Sum => MaskedSum.score(base, candidate, prime),
+            Self::Xor => MinXor.score(base, candidate, prime),
+            Self::AbsDiff => MinAbsDiff.score(base, candidate, prime),
+        }
+    }
+}
+
+/// Built-in combination of strobe hashes named by a [`StrobeSpec`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Combine {
+    /// [`CombineScheme::Mix`], the default (not written).
+    #[default]
+    Mix,
+    /// [`CombineScheme::Legacy`], written `legacy`.
+    Legacy,
+    /// [`SymmetricCombiner`](crate::SymmetricCombiner), written `sym`.
+    Symmetric,
+}
+
+/// Portable, one-line description of a seeding configuration.
+///
+/// The textual form is a `-`-separated list of fields, e.g.
+/// `rand3-k20-w25:60-canon-frac0.2`:
+///
+/// | Field          | Meaning                                            |
+/// |----------------|----------------------------------------------------|
+/// | `rand2`, `min3`, … | Protocol and order (required, first)           |
+/// | `k20`          | Strobe length (required)                           |
+/// | `w25:60`       | Window offsets `w_min:w_max` (required)            |
+/// | `xor`, `absdiff` | Selection strategy (default: masked sum)         |
+/// | `legacy`, `sym` | Strobe combination (default: mix)                |
+/// | `canon`        | Canonical (strand-neutral) seeds                   |
+/// | `frac0.2`      | Density thinning, see `set_density`                |
+/// | `step4`        | Stride, see `set_stride`                           |
+///
+/// Fields after the protocol may appear in any order but at most once;
+/// [`Display`](fmt::Display) writes them in the order above and omits
+/// defaults, so equal specs format identically and `parse(format(spec))`
+/// round-trips.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::StrobeSpec;
+/// let spec: StrobeSpec = "rand2-w3:5-k3-frac0.5".parse().unwrap();
+/// assert_eq!(spec.to_string(), "rand2-k3-w3:5-frac0.5");
+/// let rs = spec.rand_strobes(b"ACGATCTGGTACCTAGGATTACACG").unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrobeSpec {
+    /// Strobemer protocol.
+    pub protocol: Protocol,
+    /// Strobemer order (2 or 3).
+    pub order: u8,
+    /// Strobe length.
+    pub k: usize,
+    /// Minimum window offset.
+    pub w_min: usize,
+    /// Maximum window offset.
+    pub w_max: usize,
+    /// Selection strategy (RandStrobes only).
+    pub selection: Selection,
+    /// Combination of strobe hashes.
+    pub combine: Combine,
+    /// Whether seeds are canonical (RandStrobes only).
+    pub canonical: bool,
+    /// Density fraction in `(0, 1]`, if thinned.
+    pub density: Option<f64>,
+    /// Distance between first-strobe positions.
+    pub stride: usize,
+}
+
+impl StrobeSpec {
+    /// Builds the [`RandStrobes`] iterator described by the spec.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandStrobes)` – Configured as described.
+    /// * `Err(StrobeError::InvalidSpec)` – If the protocol is not `rand`.
+    /// * `Err(StrobeError)` – If the constructor rejects the sequence or parameters.
+    pub fn rand_strobes(&self, seq: &[u8]) -> Result<RandStrobes<Selection>> {
+        self.rand_strobes_with(seq, &mut Scratch::new())
+    }
+
+    /// Builds the [`RandStrobes`] iterator described by the spec, taking its
+    /// hash buffer from `scratch` (unless canonical).
+    fn rand_strobes_with(
+        &self,
+        seq: &[u8],
+        scratch: &mut Scratch,
+    ) -> Result<RandStrobes<Selection>> {
+        if self.protocol != Protocol::Rand {
+            return Err(StrobeError::InvalidSpec("protocol is not rand".into()));
+        }
+        let (n, k, w_min, w_max) = (self.order, self.k, self.w_min, self.w_max);
+        let rs = match self.canonical {
+            true => RandStrobes::new_canonical(seq, n, k, w_min, w_max)?,
+            false => RandStrobes::with_scratch(seq, n, k, w_min, w_max, &NtHash64, scratch)?,
+        };
+        let mut rs = rs.with_strategy(self.selection);
+        match self.combine {
+            Combine::Mix => {}
+            Combine::Legacy => rs.set_combine_scheme(CombineScheme::Legacy),
+            Combine::Symmetric => rs.set_symmetric(true),
+        }
+        if let Some(f) = self.density {
+            rs.set_density(f)?;
+        }
+        rs.set_stride(self.stride)?;
+        Ok(rs)
+    }
+
+    /// Builds the [`MinStrobes`] iterator described by the spec.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MinStrobes)` – Configured as described.
+    /// * `Err(StrobeError::InvalidSpec)` – If the protocol is not `min`, or the
+    ///   spec asks for a selection strategy or canonical seeds.
+    /// * `Err(StrobeError)` – If the constructor rejects the sequence or parameters.
+    pub fn min_strobes(&self, seq: &[u8]) -> Result<MinStrobes> {
+        self.min_strobes_with(seq, &mut Scratch::new())
+    }
+
+    /// Builds the [`MinStrobes`] iterator described by the spec, taking its
+    /// buffers from `scratch`.
+    fn min_strobes_with(&self, seq: &[u8], scratch: &mut Scratch) -> Result<MinStrobes> {
+        if self.protocol != Protocol::Min {
+            return Err(StrobeError::InvalidSpec("protocol is not min".into()));
+        }
+        if self.selection != Selection::Sum || self.canonical {
+            return Err(StrobeError::InvalidSpec("not supported by MinStrobes".into()));
+        }
+        let (n, k, w_min, w_max) = (self.order, self.k, self.w_min, self.w_max);
+        let mut ms = MinStrobes::with_scratch(seq, n, k, w_min, w_max, &NtHash64, scratch)?;
+        match self.combine {
+            Combine::Mix => {}
+            Combine::Legacy => ms.set_combine_scheme(CombineScheme::Legacy),
+            Combine::Symmetric => ms.set_symmetric(true),
+        }
+        if let Some(f) = self.density {
+            ms.set_density(f)?;
+        }
+        ms.set_stride(self.stride)?;
+        Ok(ms)
+    }
+
+    /// Collects the seeds of `seq` with the iterator described by the spec.
+    ///
+    /// A sequence too short for a single seed (or empty) gets no seeds
+    /// rather than an error, as is usual when seeding many records.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Strobemer>)` – Seeds in iteration order.
+    /// * `Err(StrobeError)` – If the constructor rejects the sequence or spec.
+    pub fn seeds(&self, seq: &[u8]) -> Result<Vec<Strobemer>> {
+        let mut seeds = Vec::new();
+        self.for_each_seed(seq, &mut Scratch::new(), |s| seeds.push(s))?;
+        Ok(seeds)
+    }
+
+    /// Calls `f` on each seed of `seq` in iteration order, as for
+    /// [`seeds`](Self::seeds), reusing the buffers in `scratch`.
+    pub(crate) fn for_each_seed<F>(&self, seq: &[u8], scratch: &mut Scratch, mut f: F) -> Result<()>
+    where
+        F: FnMut(Strobemer),
+    {
+        if seq.is_empty() {
+            return Ok(());
+        }
+        let built = match self.protocol {
+            Protocol::Rand => self.rand_strobes_with(seq, scratch).map(|mut rs| {
+                while let Some(s) = rs.next_seed() {
+                    f(s);
+                }
+                rs.recycle(scratch);
+            }),
+            Protocol::Min => self.min_strobes_with(seq, scratch).map(|mut ms| {
+                while let Some(s) = ms.next_seed() {
+                    f(s);
+                }
+                ms.recycle(scratch);
+            }),
+        };
+        match built {
+            Err(StrobeError::SequenceTooShort) => Ok(()),
+            built => built,
+        }
+    }
+}
+
+impl fmt::Display for StrobeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = match self.protocol {
+            Protocol::Rand => "rand",
+            Protocol::Min => "min",
+        };
+        write!(f, "{protocol}{}-k{}-w{}:{}", self.order, self.k, self.w_min, self.w_max)?;
+        match self.selection {
+            Selection::Sum => {}
+            Selection::Xor => f.write_str("-xor")?,
+            Selection::AbsDiff => f.write_str("-absdiff")?,
+        }
+        match self.combine {
+            Combine::Mix => {}
+            Combine::Legacy => f.write_str("-legacy")?,
+            Combine::Symmetric => f.write_str("-sym")?,
+        }
+        if self.canonical {
+            f.write_str("-canon")?;
+        }
+        if let Some(frac) = self.density {
+            write!(f, "-frac{frac}")?;
+        }
+        if self.stride != 1 {
+            write!(f, "-step{}", self.stride)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for StrobeSpec {
+    type Err = StrobeError;
+
+    /// Parses the textual form; see [`StrobeSpec`] for the grammar.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StrobeSpec)` – On success.
+    /// * `Err(StrobeError::InvalidSpec)` – On unknown, repeated, missing or malformed fields.
+    /// * `Err(StrobeError::OrderNotSupported)` – If the order is not 2 or 3.
+    /// * `Err(StrobeError::InvalidDensity)` – If `frac` is not in `(0, 1]`.
+    /// * `Err(StrobeError::InvalidStride)` – If `step` is 0.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |field: &str| StrobeError::InvalidSpec(field.to_string());
+        let mut fields = s.split('-');
+
+        let head = fields.next().unwrap_or_default();
+        let (protocol, order) = if let Some(o) = head.strip_prefix("rand") {
+            (Protocol::Rand, o)
+        } else if let Some(o) = head.strip_prefix("min") {
+            (Protocol::Min, o)
+        } else {
+            return Err(invalid(head));
+        };
+        let order: u8 = order.parse().map_err(|_| invalid(head))?;
+        if !(2..=3).contains(&order) {
+            return Err(StrobeError::OrderNotSupported);
+        }
+
+        let mut k = None;
+        let mut window = None;
+        let mut selection = None;
+        let mut combine = None;
+        let mut canonical = None;
+        let mut density = None;
+        let mut stride = None;
+
+        // Stores `value` into `slot`, rejecting repeated fields
+        fn set<T>(slot: &mut Option<T>, value: T, field: &str) -> Result<()> {
+            match slot.replace(value) {
+                Some(_) => Err(StrobeError::InvalidSpec(field.to_string())),
+                None => Ok(()),
+            }
+        }
+
+        for field in fields {
+            if let Some(v) = field.strip_prefix("k") {
+                set(&mut k, v.parse().map_err(|_| invalid(field))?, field)?;
+            } else if let Some(v) = field.strip_prefix("w") {
+                let (lo, hi) = v.split_once(':').ok_or_else(|| invalid(field))?;
+                let lo = lo.parse().map_err(|_| invalid(field))?;
+                let hi = hi.parse().map_err(|_| invalid(field))?;
+                set(&mut window, (lo, hi), field)?;
+            } else if let Some(v) = field.strip_prefix("frac") {
+                let f: f64 = v.parse().map_err(|_| invalid(field))?;
+                if !(f > 0.0 && f <= 1.0) {
+                    return Err(StrobeError::InvalidDensity);
+                }
+                set(&mut density, f, field)?;
+            } else if let Some(v) = field.strip_prefix("step") {
+                let step: usize = v.parse().map_err(|_| invalid(field))?;
+                if step == 0 {
+                    return Err(StrobeError::InvalidStride);
+                }
+                set(&mut stride, step, field)?;
+            } else {
+                match field {
+                    "xor" => set(&mut selection, Selection::Xor, field)?,
+                    "absdiff" => set(&mut selection, Selection::AbsDiff, field)?,
+                    "legacy" => set(&mut combine, Combine::Legacy, field)?,
+                    "sym" => set(&mut combine, Combine::Symmetric, field)?,
+                    "canon" => set(&mut canonical, true, field)?,
+                    _ => return Err(invalid(field)),
+                }
+            }
+        }
+
+        let k = k.ok_or_else(|| invalid("missing k"))?;
+        let (w_min, w_max) = window.ok_or_else(|| invalid("missing w"))?;
+        Ok(Self {
+            protocol,
+            order,
+            k,
+            w_min,
+            w_max,
+            selection: selection.unwrap_or_default(),
+            combine: combine.unwrap_or_default(),
+            canonical: canonical.unwrap_or(false),
+            density,
+            stride: stride.unwrap_or(1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_round_trips() {
+        for s in [
+            "rand3-k20-w25:60-canon-frac0.2",
+            "min2-k15-w5:11",
+            "rand2-k3-w3:5-absdiff-sym-step4",
+            "min3-k15-w5:11-legacy",
+        ] {
+            let spec: StrobeSpec = s.parse().unwrap();
+            assert_eq!(spec.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_fields() {
+        let err = |s: &str| s.parse::<StrobeSpec>().unwrap_err();
+        assert_eq!(err("hash2-k3-w3:5"), StrobeError::InvalidSpec("hash2".into()));
+        assert_eq!(err("rand4-k3-w3:5"), StrobeError::OrderNotSupported);
+        assert_eq!(err("rand2-k3-k4-w3:5"), StrobeError::InvalidSpec("k4".into()));
+        assert_eq!(err("rand2-k3-w3"), StrobeError::InvalidSpec("w3".into()));
+        assert_eq!(err("rand2-w3:5"), StrobeError::InvalidSpec("missing k".into()));
+        assert_eq!(err("rand2-k3-w3:5-frac0"), StrobeError::InvalidDensity);
+    }
+
+    #[test]
+    fn short_sequences_have_no_seeds() {
+        let spec: StrobeSpec = "rand2-k3-w3:5".parse().unwrap();
+        assert_eq!(spec.seeds(b"ACG"), Ok(Vec::new()));
+        assert_eq!(spec.seeds(b""), Ok(Vec::new()));
+    }
+}
@@ -0,0 +1,18 @@
+/// DNA strand orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    /// The input sequence as given.
+    Forward,
+    /// The reverse complement of the input sequence.
+    Reverse,
+}
+
+impl Strand {
+    /// Returns the opposite strand.
+    pub fn flip(self) -> Self {
+        match self {
+            Strand::Forward => Strand::Reverse,
+            Strand::Reverse => Strand::Forward,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use std::{io::Read, vec};
+
+use crate::{MinStrobes, Protocol, RandStrobes, Result, StrobeError, Strobemer};
+
+/// Default number of bases read per chunk by [`ReaderStrobes`].
+const DEFAULT_CHUNK_SIZE: usize = 1 << 20;
+
+/// Iterator over the seeds of a sequence read incrementally from an [`io::Read`](Read).
+///
+/// Built by `from_reader` on [`RandStrobes`] or [`MinStrobes`]. The input is
+/// read in chunks; only the current chunk plus the `(n − 1)·w_max + k − 1`
+/// bases still needed by unfinished windows are held in memory, together
+/// with their hashes. Seeds are identical to those of `new` on the whole
+/// sequence, with positions counted from the start of the stream.
+///
+/// The input is the bare sequence: ASCII whitespace (such as line breaks) is
+/// skipped, everything else is a base. Read errors and parameter errors are
+/// yielded once, after which the iterator is exhausted.
+#[derive(Debug)]
+pub struct ReaderStrobes<R> {
+    reader: R,
+    protocol: Protocol,
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    chunk_size: usize,                 // Bases read per chunk
+    buf: Vec<u8>,                      // Bases still needed by unfinished windows
+    base: usize,                       // Stream position of `buf[0]`
+    pending: vec::IntoIter<Strobemer>, // Finished seeds of the last chunk
+    done: bool,                        // End of input or error reached
+}
+
+impl<R: Read> ReaderStrobes<R> {
+    pub(crate) fn new(
+        reader: R,
+        protocol: Protocol,
+        n: u8,
+        k: usize,
+        w_min: usize,
+        w_max: usize,
+    ) -> Result<Self> {
+        if !matches!(n, 2 | 3) {
+            return Err(StrobeError::OrderNotSupported);
+        }
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if w_max == 0 || w_min > w_max {
+            return Err(StrobeError::InvalidWindowOffsets);
+        }
+        Ok(Self {
+            reader,
+            protocol,
+            n,
+            k,
+            w_min,
+            w_max,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            buf: Vec::new(),
+            base: 0,
+            pending: Vec::new().into_iter(),
+            done: false,
+        })
+    }
+
+    /// Sets the number of bases read per chunk (default 1 MiB; 0 is treated as 1).
+    ///
+    /// Larger chunks amortize the bases re-hashed at each chunk boundary;
+    /// smaller ones bound memory more tightly.
+    pub fn with_chunk_size(mut self, bases: usize) -> Self {
+        self.chunk_size = bases.max(1);
+        self
+    }
+
+    /// Bases kept after the last anchor seeded from a non-final buffer.
+    fn keep(&self) -> usize {
+        block_overlap(self.n, self.k, self.w_max)
+    }
+
+    /// Appends up to `chunk_size` further bases to `buf`; returns `false` at end of input.
+    fn fill(&mut self) -> Result<bool> {
+        let target = self.buf.len() + self.chunk_size;
+        let mut raw = vec![0; self.chunk_size.min(64 * 1024)];
+        while self.buf.len() < target {
+            let read = match self.reader.read(&mut raw) {
+                Ok(0) => return Ok(false),
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(StrobeError::Io(e.to_string())),
+            };
+            self.buf.extend(raw[..read].iter().filter(|b| !b.is_ascii_whitespace()));
+        }
+        Ok(true)
+    }
+
+    /// Seeds of `buf` with first strobe before `limit`, in stream coordinates.
+    fn seed_buffer(&self, limit: Option<usize>) -> Result<Vec<Strobemer>> {
+        let (seq, n, k, w_min, w_max) = (&self.buf[..], self.n, self.k, self.w_min, self.w_max);
+        let seeds: Box<dyn Iterator<Item = Strobemer>> = match self.protocol {
+            Protocol::Rand => Box::new(RandStrobes::new(seq, n, k, w_min, w_max)?.iter_seeds()),
+            Protocol::Min => Box::new(MinStrobes::new(seq, n, k, w_min, w_max)?.iter_seeds()),
+        };
+        let limit = limit.unwrap_or(usize::MAX);
+        Ok(seeds
+            .take_while(|s| s.positions[0] < limit)
+            .map(|mut s| {
+                let order = s.order as usize;
+                s.positions[..order].iter_mut().for_each(|p| *p += self.base);
+                s
+            })
+            .collect())
+    }
+
+    /// Reads the next chunk and queues its finished seeds.
+    fn advance(&mut self) -> Result<()> {
+        let more = self.fill()?;
+        if !more {
+            self.done = true;
+            // A carried-over tail too short for `n` strobes holds no seed
+            if self.base > 0 && self.buf.len() < self.n as usize * self.k {
+                return Ok(());
+            }
+            return match self.seed_buffer(None) {
+                Err(StrobeError::SequenceTooShort) if self.base > 0 => Ok(()),
+                seeds => {
+                    self.pending = seeds?.into_iter();
+                    Ok(())
+                }
+            };
+        }
+        // Anchors up to `safe` have every window inside `buf`
+        let safe = self.buf.len().saturating_sub(self.keep());
+        if safe > 0 {
+            self.pending = self.seed_buffer(Some(safe))?.into_iter();
+            self.buf.drain(..safe);
+            self.base += safe;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for ReaderStrobes<R> {
+    type Item = Result<Strobemer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(seed) = self.pending.next() {
+                return Some(Ok(seed));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.advance() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Bases a non-final block of a sequence must hold after its last anchor
+/// for the anchor's seed to equal the one of the whole sequence.
+///
+/// Covers the windows of that anchor, room for `n` strobes after it (the
+/// iterators' stop condition), and the constructors' length check.
+pub(crate) fn block_overlap(n: u8, k: usize, w_max: usize) -> usize {
+    let (n, links) = (n as usize, n as usize - 1);
+    let windows = links * w_max + k - 1;
+    windows.max(n * k - 1).max(links * (w_max + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rand(seq: &[u8], n: u8) -> RandStrobes {
+        RandStrobes::new(seq, n, 3, 3, 5).unwrap()
+    }
+
+    fn min(seq: &[u8], n: u8) -> MinStrobes {
+        MinStrobes::new(seq, n, 3, 3, 5).unwrap()
+    }
+
+    #[test]
+    fn matches_whole_sequence_across_chunk_sizes() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCATTGCAGGCTAAGCTTGCA";
+        for protocol in [Protocol::Rand, Protocol::Min] {
+            for n in [2, 3] {
+                let expected: Vec<Strobemer> = match protocol {
+                    Protocol::Rand => rand(seq, n).iter_seeds().collect(),
+                    Protocol::Min => min(seq, n).iter_seeds().collect(),
+                };
+                for chunk in [1, 4, 13, 64] {
+                    let it = ReaderStrobes::new(&seq[..], protocol, n, 3, 3, 5).unwrap();
+                    let got: Result<Vec<_>> = it.with_chunk_size(chunk).collect();
+                    assert_eq!(got.unwrap(), expected, "{protocol:?} n={n} chunk={chunk}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn yields_read_error_once() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk gone"))
+            }
+        }
+        let mut it = ReaderStrobes::new(Failing, Protocol::Rand, 2, 3, 3, 5).unwrap();
+        assert_eq!(it.next(), Some(Err(StrobeError::Io("disk gone".into()))));
+        assert_eq!(it.next(), None);
+    }
+}
@@ -0,0 +1,294 @@
+use std::{fmt, hash::Hasher};
+
+use crate::{
+    Result, StrobeError,
+    hashes::{KmerCodeHasher, KmerHasher, NtHash64, NtHashCanonical, StdHasherKmer},
+    util::nt4,
+};
+
+/// Bases fed per call by [`StreamingHashes`].
+const STREAM_BLOCK: usize = 4096;
+
+/// K-mer hasher that consumes a sequence incrementally.
+///
+/// Unlike [`KmerHasher::hash_all`], which returns the hashes of a whole
+/// sequence at once, a streaming hasher keeps a small rolling state and
+/// emits the hash of each k-mer as soon as its last base has been fed, so
+/// callers hold only as many hashes as they still need (e.g. a `w_max`-sized
+/// window) instead of `8·(L − k + 1)` bytes per sequence.
+///
+/// The hashes equal those of `hash_all` on the concatenation of everything
+/// fed since [`start`](Self::start), however the input is split. Only hashes
+/// are produced; validity masks (see [`KmerHasher::validity`]) are not.
+///
+/// Hashers without a true rolling update can keep a [`KmerCarry`], which
+/// re-hashes the last `k − 1` bases with every chunk.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerHasher, NtHash64, StreamingKmerHasher};
+/// let seq = b"ACGATCTGGTACCTAGGATTACACG";
+/// let mut state = NtHash64.start(5).unwrap();
+/// let mut hashes = Vec::new();
+/// for line in seq.chunks(7) {
+///     NtHash64.feed(&mut state, line, &mut hashes).unwrap();
+/// }
+/// assert_eq!(hashes, NtHash64.hash_all(seq, 5).unwrap());
+/// ```
+pub trait StreamingKmerHasher {
+    /// Rolling state over the most recently fed bases.
+    type State: Clone + fmt::Debug + Send;
+
+    /// Returns the state for hashing `k`-mers, with no bases fed yet.
+    ///
+    /// # Errors
+    ///
+    /// * `StrobeLengthTooSmall` if the hasher does not support `k`.
+    fn start(&self, k: usize) -> Result<Self::State>;
+
+    /// Feeds `bases` and appends to `out` the hashes of the k-mers they complete, in order.
+    fn feed(&self, state: &mut Self::State, bases: &[u8], out: &mut Vec<u64>) -> Result<()>;
+
+    /// Returns an iterator over the k-mer hashes of `seq`, computed block by block.
+    fn stream<'a>(&'a self, seq: &'a [u8], k: usize) -> Result<StreamingHashes<'a, Self>>
+    where
+        Self: Sized,
+    {
+        Ok(StreamingHashes {
+            hasher: self,
+            state: self.start(k)?,
+            rest: seq,
+            out: Vec::new(),
+            next: 0,
+            error: None,
+            failed: false,
+        })
+    }
+}
+
+/// Streaming state that re-hashes a `k − 1`-base overlap with each chunk.
+///
+/// Turns any [`KmerHasher`] whose hashes depend on the k-mer alone into a
+/// streaming one: each chunk is hashed with `hash_all` together with the
+/// last `k − 1` bases of the previous one. Memory is bounded by the chunk
+/// size; feeding single bases works but costs O(k) per base.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerCarry, KmerHasher, NtHash64, Result, StreamingKmerHasher};
+///
+/// struct Upper;
+/// impl KmerHasher for Upper {
+///     fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+///         NtHash64.hash_all(&seq.to_ascii_uppercase(), k)
+///     }
+/// }
+/// impl StreamingKmerHasher for Upper {
+///     type State = KmerCarry;
+///     fn start(&self, k: usize) -> Result<KmerCarry> {
+///         KmerCarry::new(k)
+///     }
+///     fn feed(&self, state: &mut KmerCarry, bases: &[u8], out: &mut Vec<u64>) -> Result<()> {
+///         state.feed(self, bases, out)
+///     }
+/// }
+///
+/// let hashes: Result<Vec<u64>> = Upper.stream(b"acgatctggtacctag", 4).unwrap().collect();
+/// assert_eq!(hashes.unwrap(), Upper.hash_all(b"ACGATCTGGTACCTAG", 4).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct KmerCarry {
+    k: usize,
+    bases: Vec<u8>, // Last `k − 1` bases fed, then the current chunk
+}
+
+impl KmerCarry {
+    /// Creates an empty carry for `k`-mers.
+    ///
+    /// # Errors
+    ///
+    /// * `StrobeLengthTooSmall` if `k` is not in `[1, 64]`.
+    pub fn new(k: usize) -> Result<Self> {
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        Ok(Self { k, bases: Vec::new() })
+    }
+
+    /// Hashes the k-mers completed by `bases` with `hasher`, appending them to `out`.
+    pub fn feed<H>(&mut self, hasher: &H, bases: &[u8], out: &mut Vec<u64>) -> Result<()>
+    where
+        H: KmerHasher + ?Sized,
+    {
+        self.bases.extend_from_slice(bases);
+        let len = self.bases.len();
+        if len < self.k {
+            return Ok(());
+        }
+        out.extend(hasher.hash_all(&self.bases, self.k)?);
+        self.bases.drain(..len - (self.k - 1));
+        Ok(())
+    }
+}
+
+impl StreamingKmerHasher for NtHash64 {
+    type State = KmerCarry;
+
+    fn start(&self, k: usize) -> Result<KmerCarry> {
+        KmerCarry::new(k)
+    }
+
+    fn feed(&self, state: &mut KmerCarry, bases: &[u8], out: &mut Vec<u64>) -> Result<()> {
+        state.feed(self, bases, out)
+    }
+}
+
+impl StreamingKmerHasher for NtHashCanonical {
+    type State = KmerCarry;
+
+    fn start(&self, k: usize) -> Result<KmerCarry> {
+        KmerCarry::new(k)
+    }
+
+    fn feed(&self, state: &mut KmerCarry, bases: &[u8], out: &mut Vec<u64>) -> Result<()> {
+        state.feed(self, bases, out)
+    }
+}
+
+impl<H> StreamingKmerHasher for StdHasherKmer<H>
+where
+    H: Hasher + Clone,
+{
+    type State = KmerCarry;
+
+    fn start(&self, k: usize) -> Result<KmerCarry> {
+        KmerCarry::new(k)
+    }
+
+    fn feed(&self, state: &mut KmerCarry, bases: &[u8], out: &mut Vec<u64>) -> Result<()> {
+        state.feed(self, bases, out)
+    }
+}
+
+/// Rolling state of [`KmerCodeHasher`]: the 2-bit code of the last `k` bases.
+#[derive(Debug, Clone)]
+pub struct KmerCodeState {
+    k: usize,
+    mask: u64,     // Low `2k` bits
+    code: u64,     // Code of the most recent bases
+    filled: usize, // Bases fed so far, saturating at `k`
+}
+
+impl StreamingKmerHasher for KmerCodeHasher {
+    type State = KmerCodeState;
+
+    /// # Errors
+    ///
+    /// * `StrobeLengthTooSmall` if `k` is not in `[1, 32]`.
+    fn start(&self, k: usize) -> Result<KmerCodeState> {
+        if !(1..=32).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        let mask = if k == 32 { u64::MAX } else { (1u64 << (2 * k)) - 1 };
+        Ok(KmerCodeState { k, mask, code: 0, filled: 0 })
+    }
+
+    /// # Errors
+    ///
+    /// * `InvalidSequence` if `bases` contains a byte other than `ACGTU` (any case).
+    fn feed(&self, state: &mut KmerCodeState, bases: &[u8], out: &mut Vec<u64>) -> Result<()> {
+        for &b in bases {
+            let c = nt4(b);
+            if c > 3 {
+                return Err(StrobeError::InvalidSequence);
+            }
+            state.code = ((state.code << 2) | c as u64) & state.mask;
+            state.filled = (state.filled + 1).min(state.k);
+            if state.filled == state.k {
+                out.push(state.code);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the k-mer hashes of a sequence, built by [`StreamingKmerHasher::stream`].
+///
+/// Feeds the sequence in blocks of a few thousand bases, so at most one
+/// block of hashes is held at a time. A hashing error is yielded once, after
+/// the hashes completed before it, and then the iterator is exhausted.
+#[derive(Debug, Clone)]
+pub struct StreamingHashes<'a, H: StreamingKmerHasher> {
+    hasher: &'a H,
+    state: H::State,
+    rest: &'a [u8],             // Bases not fed yet
+    out: Vec<u64>,              // Hashes of the last block
+    next: usize,                // Next hash of `out` to yield
+    error: Option<StrobeError>, // Error to yield once `out` is drained
+    failed: bool,               // Stop after yielding an error
+}
+
+impl<H: StreamingKmerHasher> Iterator for StreamingHashes<'_, H> {
+    type Item = Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next == self.out.len() {
+            if let Some(e) = self.error.take() {
+                return Some(Err(e));
+            }
+            if self.failed || self.rest.is_empty() {
+                return None;
+            }
+            let (block, rest) = self.rest.split_at(self.rest.len().min(STREAM_BLOCK));
+            self.rest = rest;
+            self.out.clear();
+            self.next = 0;
+            if let Err(e) = self.hasher.feed(&mut self.state, block, &mut self.out) {
+                (self.error, self.failed) = (Some(e), true);
+            }
+        }
+        self.next += 1;
+        Some(Ok(self.out[self.next - 1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn check<H: KmerHasher + StreamingKmerHasher>(hasher: &H, seq: &[u8], k: usize) {
+        let expected = hasher.hash_all(seq, k).unwrap();
+        for chunk in [1, 3, k, 50] {
+            let mut state = hasher.start(k).unwrap();
+            let mut got = Vec::new();
+            for bases in seq.chunks(chunk) {
+                hasher.feed(&mut state, bases, &mut got).unwrap();
+            }
+            assert_eq!(got, expected, "k={k} chunk={chunk}");
+        }
+        let streamed: Result<Vec<u64>> = hasher.stream(seq, k).unwrap().collect();
+        assert_eq!(streamed.unwrap(), expected);
+    }
+
+    #[test]
+    fn matches_hash_all_for_any_split() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCATTGCAGGCTAAGCTTGCA";
+        for k in [1, 4, 15] {
+            check(&NtHash64, seq, k);
+            check(&NtHashCanonical, seq, k);
+            check(&KmerCodeHasher, seq, k);
+            check(&StdHasherKmer::new(DefaultHasher::new()), seq, k);
+        }
+    }
+
+    #[test]
+    fn yields_error_once() {
+        let mut it = KmerCodeHasher.stream(b"ACGNTACG", 2).unwrap();
+        assert_eq!(it.next(), Some(Ok(0b00_01)));
+        assert_eq!(it.next(), Some(Ok(0b01_10)));
+        assert_eq!(it.next(), Some(Err(StrobeError::InvalidSequence)));
+        assert_eq!(it.next(), None);
+        assert!(KmerCodeHasher.start(33).is_err() && KmerCarry::new(0).is_err());
+    }
+}
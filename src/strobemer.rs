@@ -0,0 +1,104 @@
+use crate::{alphabet::mix, util::reverse_complement_in_place};
+
+/// Seeds stored column-wise, `(hashes, m1, m2, m3)`, as returned by
+/// `collect_soa` on [`MinStrobes`](crate::MinStrobes) and
+/// [`RandStrobes`](crate::RandStrobes).
+pub type SeedColumns = (Vec<u64>, Vec<u32>, Vec<u32>, Vec<u32>);
+
+/// A strobemer together with its strobe positions.
+///
+/// Yielded by `iter_seeds` on [`MinStrobes`](crate::MinStrobes) and
+/// [`RandStrobes`](crate::RandStrobes), so positions travel with the hash
+/// through adapters such as `filter` or `collect` instead of being read back
+/// with `indexes()` after each `next()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Strobemer {
+    /// Combined strobemer hash, as returned by `next()`.
+    pub hash: u64,
+    /// Strobe start positions `[m1, m2, m3]`; `m3` is 0 for order 2.
+    pub positions: [usize; 3],
+    /// Number of bases from the leftmost strobe start to the rightmost strobe end.
+    pub span: usize,
+    /// Strobemer order (2 or 3; 1 for the k-mers of
+    /// [`MinStrobes::with_kmer_fallback`](crate::MinStrobes::with_kmer_fallback)).
+    pub order: u8,
+}
+
+impl Strobemer {
+    /// Builds an item from the iterator state after a successful `next()`.
+    pub(crate) fn new(hash: u64, positions: [usize; 3], order: u8, k: usize) -> Self {
+        let used = &positions[..order as usize];
+        let first = used.iter().min().copied().unwrap_or(0);
+        let last = used.iter().max().copied().unwrap_or(0);
+        Self {
+            hash,
+            positions,
+            span: last + k - first,
+            order,
+        }
+    }
+
+    /// Returns the strobe positions actually used by this order.
+    pub fn strobes(&self) -> &[usize] {
+        &self.positions[..self.order as usize]
+    }
+}
+
+/// A strobemer with a 128-bit hash, for low-collision indexing.
+///
+/// Yielded by `iter_seeds128` on [`MinStrobes`](crate::MinStrobes) and
+/// [`RandStrobes`](crate::RandStrobes). The high 64 bits are the seed's usual
+/// hash; the low 64 bits hash the strobe bases (as returned by `seed_bytes`)
+/// independently, so two distinct seeds collide only if both halves do.
+/// Strobe positions are those of the 64-bit iterator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Strobemer128 {
+    /// Combined strobemer hash (high half) and strobe-bases hash (low half).
+    pub hash: u128,
+    /// Strobe start positions `[m1, m2, m3]`; `m3` is 0 for order 2.
+    pub positions: [usize; 3],
+    /// Number of bases from the leftmost strobe start to the rightmost strobe end.
+    pub span: usize,
+    /// Strobemer order (2 or 3; 1 for the k-mers of
+    /// [`MinStrobes::with_kmer_fallback`](crate::MinStrobes::with_kmer_fallback)).
+    pub order: u8,
+}
+
+impl Strobemer128 {
+    /// Widens `seed` with a hash of its strobe bases `bytes`.
+    pub(crate) fn new(seed: Strobemer, bytes: &[u8]) -> Self {
+        let mut words = bytes.chunks(8);
+        let low = words.by_ref().fold(mix(bytes.len() as u64), |h, word| {
+            let mut w = [0u8; 8];
+            w[..word.len()].copy_from_slice(word);
+            mix(h.rotate_left(23) ^ u64::from_le_bytes(w))
+        });
+        Self {
+            hash: (seed.hash as u128) << 64 | low as u128,
+            positions: seed.positions,
+            span: seed.span,
+            order: seed.order,
+        }
+    }
+
+    /// Returns the strobe positions actually used by this order.
+    pub fn strobes(&self) -> &[usize] {
+        &self.positions[..self.order as usize]
+    }
+}
+
+/// Concatenates the k-mers of `seq` starting at `positions`, each
+/// reverse-complemented if `revcomp`.
+///
+/// Panics if a k-mer extends past the end of `seq`.
+pub(crate) fn strobe_bytes(seq: &[u8], positions: &[usize], k: usize, revcomp: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(positions.len() * k);
+    for &p in positions {
+        let start = out.len();
+        out.extend_from_slice(&seq[p..p + k]);
+        if revcomp {
+            reverse_complement_in_place(&mut out[start..]);
+        }
+    }
+    out
+}
@@ -35,6 +35,39 @@ pub const fn complement(b: u8) -> u8 {
     COMPL_BASES[b as usize]
 }
 
+/// Returns the reverse complement of a DNA/RNA sequence.
+///
+/// Bases are complemented as in [`complement`] (`U/u` pair with `A`), except
+/// that case is preserved, so soft-masked (lowercase) regions stay lowercase.
+/// Any other byte becomes `N` (or `n` if lowercase).
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{reverse_complement, reverse_complement_in_place};
+/// assert_eq!(reverse_complement(b"ACGTacgu"), b"acgtACGT");
+/// let mut seq = b"GATTNc".to_vec();
+/// reverse_complement_in_place(&mut seq);
+/// assert_eq!(seq, b"gNAATC");
+/// ```
+pub fn reverse_complement(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|&b| complement_preserving_case(b)).collect()
+}
+
+/// Reverse-complements `seq` in place; see [`reverse_complement`].
+pub fn reverse_complement_in_place(seq: &mut [u8]) {
+    seq.reverse();
+    for b in seq.iter_mut() {
+        *b = complement_preserving_case(*b);
+    }
+}
+
+/// [`complement`] that keeps lowercase input lowercase.
+#[inline(always)]
+fn complement_preserving_case(b: u8) -> u8 {
+    let c = complement(b);
+    if b.is_ascii_lowercase() { c.to_ascii_lowercase() } else { c }
+}
+
 /// Encodes a nucleotide ASCII byte into its 2-bit code (0‒3), or 4 for invalid.
 ///
 /// Uses the `SEQ_NT4_TABLE`, which assigns:
@@ -60,30 +93,49 @@ pub const fn nt4(b: u8) -> u8 {
 ///
 /// This macro is intended to be invoked at the start of constructors or functions
 /// that require:
-/// - A non-empty, ASCII-only sequence slice (`$seq`)
+/// - A non-empty, ASCII-only sequence slice (`$seq`), or with a trailing
+///   `$hasher` argument, one accepted by its
+///   [`validate_sequence`](crate::KmerHasher::validate_sequence)
 /// - An order (`$n`) of either 2 or 3
 /// - A strobe length (`$l`) between 1 and 64
-/// - Window offsets (`$w_min`, `$w_max`) where both are > 0 and `w_min ≤ w_max`
+/// - Window offsets (`$w_min`, `$w_max`) where `w_max > 0` and `w_min ≤ w_max`
 /// - Sequence length sufficient to accommodate `(n - 1)` windows of size `(w_max + 1)`
 ///
 /// Returns the corresponding `StrobeError` on any validation failure:
-/// - `InvalidSequence` if the sequence is empty
+/// - `InvalidSequence` if the sequence is empty (or not ASCII without a hasher)
+/// - Whatever the hasher's `validate_sequence` returns
 /// - `OrderNotSupported` if `n` is not 2 or 3
 /// - `StrobeLengthTooSmall` if `l` is outside [1..=64]
-/// - `InvalidWindowOffsets` if `w_min` or `w_max` are zero or `w_min > w_max`
-/// - `SequenceTooShort` if `seq.len()` is too small for the given parameters
+/// - `InvalidWindowOffsets` if `w_max` is zero or `w_min > w_max`
+/// - `SequenceTooShort` if `seq.len()` is below `(n − 1)·(w_max + 1)` or `n·l`
 ///
 /// # Example
 ///
 /// ```ignore
 /// validate_params!(seq, n, l, w_min, w_max);
+/// validate_params!(seq, n, l, w_min, w_max, hasher);
 /// ```
 macro_rules! validate_params {
     ($seq:expr, $n:expr, $l:expr, $w_min:expr, $w_max:expr) => {{
-        // Sequence must be non-empty
+        // Sequence must be non-empty ASCII
         if $seq.is_empty() || !$seq.is_ascii() {
             return Err(StrobeError::InvalidSequence);
         }
+        validate_params!(@shape $seq, $n, $l, $w_min, $w_max);
+    }};
+    ($seq:expr, $n:expr, $l:expr, $w_min:expr, $w_max:expr, $hasher:expr) => {{
+        // Sequence must be non-empty and made of symbols the hasher accepts
+        if $seq.is_empty() {
+            return Err(StrobeError::InvalidSequence);
+        }
+        $hasher.validate_sequence($seq)?;
+        validate_params!(@shape $seq, $n, $l, $w_min, $w_max);
+    }};
+    (@shape $seq:expr, $n:expr, $l:expr, $w_min:expr, $w_max:expr) => {{
+        validate_params!(@len $seq.len(), $n, $l, $w_min, $w_max);
+    }};
+    // Checks for a sequence of `$len` bases already hashed or validated
+    (@len $len:expr, $n:expr, $l:expr, $w_min:expr, $w_max:expr) => {{
         // Order must be exactly 2 or 3
         if !matches!($n, 2 | 3) {
             return Err(StrobeError::OrderNotSupported);
@@ -92,12 +144,13 @@ macro_rules! validate_params {
         if !(1..=64).contains(&$l) {
             return Err(StrobeError::StrobeLengthTooSmall);
         }
-        // Window offsets must be greater than zero and w_min ≤ w_max
-        if $w_min == 0 || $w_max == 0 || $w_min > $w_max {
+        // w_min may be 0 (a strobe may overlap m1), but windows must reach past m1
+        if $w_max == 0 || $w_min > $w_max {
             return Err(StrobeError::InvalidWindowOffsets);
         }
         // Sequence must be long enough to fit (n − 1) windows of size (w_max + 1)
-        if $seq.len() < ($n as usize - 1) * ($w_max + 1) {
+        // and n whole strobes
+        if $len < ($n as usize - 1) * ($w_max + 1) || $len < $n as usize * $l {
             return Err(StrobeError::SequenceTooShort);
         }
     }};
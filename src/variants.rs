@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use crate::{Result, StrobeError, randstrobes::RandStrobes};
+
+/// A reference interval whose seeds are missing from the reads covering it.
+///
+/// Produced by [`screen_variants`]. Coordinates are half-open `[start, end)`
+/// on the reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CandidateRegion {
+    /// First reference base of the region.
+    pub start: usize,
+    /// One past the last reference base of the region.
+    pub end: usize,
+    /// Number of unsupported reference seeds merged into the region.
+    pub seeds: usize,
+}
+
+/// Screens anchored reads for reference regions where their seeds systematically disagree.
+///
+/// Every reference seed depends on a fixed footprint of
+/// `(n − 1) · w_max + k` bases starting at m1. A read whose span covers that
+/// footprint reproduces the seed unless it differs from the reference
+/// somewhere inside it. A seed is flagged when at least `min_coverage` reads
+/// cover its footprint and at most a `max_support` fraction of them reproduce
+/// it. Footprints of flagged seeds are merged into candidate regions for
+/// targeted realignment.
+///
+/// Reads are given as `(offset, sequence)` pairs, where `offset` is the
+/// reference position of the read's first base and the read is on the
+/// reference strand. Reads too short to produce a strobemer are skipped.
+///
+/// # Arguments
+///
+/// * `reference` – Reference sequence.
+/// * `reads` – Anchored reads as `(offset, sequence)`.
+/// * `n`, `k`, `w_min`, `w_max` – Strobemer parameters, as for [`RandStrobes::new`].
+/// * `min_coverage` – Minimum number of covering reads for a seed to be judged.
+/// * `max_support` – Largest fraction of covering reads that may reproduce a flagged seed.
+///
+/// # Returns
+///
+/// * `Ok(Vec<CandidateRegion>)` – Disjoint regions in increasing order.
+/// * `Err(StrobeError)` if a sequence is invalid or the parameters are rejected.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::screen_variants;
+/// let reference = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+/// let mut alt = reference.to_vec();
+/// alt[20] = b'G';
+/// let reads = [(0, &alt[..]), (0, &alt[..])];
+/// let regions = screen_variants(reference, &reads, 2, 3, 3, 5, 2, 0.0).unwrap();
+/// assert!(regions.iter().any(|r| (r.start..r.end).contains(&20)));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn screen_variants<S: AsRef<[u8]>>(
+    reference: &[u8],
+    reads: &[(usize, S)],
+    n: u8,
+    k: usize,
+    w_min: usize,
+    w_max: usize,
+    min_coverage: usize,
+    max_support: f64,
+) -> Result<Vec<CandidateRegion>> {
+    let mut rs = RandStrobes::new(reference, n, k, w_min, w_max)?;
+    let footprint = (n as usize - 1) * w_max + k;
+
+    // Coverage of each footprint start, as a difference array
+    let mut coverage = vec![0isize; reference.len() + 1];
+    // Number of reads reproducing each (m1, hash) in reference coordinates
+    let mut support: HashMap<(usize, u64), usize> = HashMap::new();
+    for (offset, read) in reads {
+        let read = read.as_ref();
+        let mut seeds = match RandStrobes::new(read, n, k, w_min, w_max) {
+            Ok(seeds) => seeds,
+            Err(StrobeError::SequenceTooShort) => continue,
+            Err(e) => return Err(e),
+        };
+        while let Some(h) = seeds.next() {
+            let pos = seeds.index().unwrap_or(0) + offset;
+            *support.entry((pos, h)).or_default() += 1;
+        }
+        if read.len() >= footprint && *offset < reference.len() {
+            let last = (offset + read.len() - footprint).min(reference.len() - 1);
+            coverage[*offset] += 1;
+            coverage[last + 1] -= 1;
+        }
+    }
+    let mut depth = 0isize;
+    let coverage: Vec<usize> = coverage
+        .iter()
+        .map(|&d| {
+            depth += d;
+            depth as usize
+        })
+        .collect();
+
+    let mut regions: Vec<CandidateRegion> = Vec::new();
+    while let Some(h) = rs.next() {
+        let pos = rs.index().unwrap_or(0);
+        // Seeds drawn from shrunken windows at the reference end are not comparable
+        if pos + footprint > reference.len() {
+            break;
+        }
+        let cov = coverage[pos];
+        let sup = support.get(&(pos, h)).copied().unwrap_or(0);
+        if cov == 0 || cov < min_coverage || sup as f64 > max_support * cov as f64 {
+            continue;
+        }
+        match regions.last_mut() {
+            Some(r) if pos <= r.end => {
+                r.end = pos + footprint;
+                r.seeds += 1;
+            }
+            _ => regions.push(CandidateRegion { start: pos, end: pos + footprint, seeds: 1 }),
+        }
+    }
+    Ok(regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_reads_yield_no_regions() {
+        let reference = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+        let reads = [(0, &reference[..30]), (10, &reference[10..])];
+        let regions = screen_variants(reference, &reads, 2, 3, 3, 5, 1, 0.5).unwrap();
+        assert!(regions.is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! wyhash k-mer hasher (feature `wyhash`).
+
+use crate::{Result, StrobeError, hashes::KmerHasher};
+
+/// Default secret of wyhash (final version 4).
+const SECRET: [u64; 4] = [
+    0x2d35_8dcc_aa6c_78a5,
+    0x8bb8_4b93_962e_acc9,
+    0x4b33_a62e_d433_d4a3,
+    0x4d5a_2da5_1de1_aa47,
+];
+
+/// K-mer hasher applying wyhash to each k-mer's bytes.
+///
+/// wyhash is among the fastest high-quality hashes for short keys, which
+/// makes it a good fit for sketching with small `k`. Every k-mer is hashed
+/// from scratch, in O(n·k). Bytes are hashed as given: lower- and uppercase
+/// bases differ, and any ASCII byte is accepted. Values equal those of the
+/// reference wyhash (final version 4, default secret) with the same seed.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerHasher, RandStrobes, WyHashHasher};
+/// let hasher = WyHashHasher::new(0);
+/// let hashes = hasher.hash_all(b"ACGTACG", 3).unwrap();
+/// assert_eq!(hashes[0], hashes[4]);
+/// let rs = RandStrobes::with_hasher(b"ACGATCTGGTACCTAG", 2, 3, 3, 5, &hasher).unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WyHashHasher {
+    seed: u64,
+}
+
+impl WyHashHasher {
+    /// Creates a hasher using wyhash with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl KmerHasher for WyHashHasher {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+        out.clear();
+        out.extend(seq.windows(k).map(|kmer| wyhash(kmer, self.seed)));
+        Ok(())
+    }
+}
+
+fn read8(b: &[u8]) -> u64 {
+    u64::from_le_bytes(b[..8].try_into().expect("8 bytes"))
+}
+
+fn read4(b: &[u8]) -> u64 {
+    u32::from_le_bytes(b[..4].try_into().expect("4 bytes")) as u64
+}
+
+/// 128-bit product of `a` and `b`, as `(low, high)`.
+fn mum(a: u64, b: u64) -> (u64, u64) {
+    let r = a as u128 * b as u128;
+    (r as u64, (r >> 64) as u64)
+}
+
+fn mix(a: u64, b: u64) -> u64 {
+    let (lo, hi) = mum(a, b);
+    lo ^ hi
+}
+
+/// wyhash of `input` with `seed` and the default secret.
+fn wyhash(input: &[u8], mut seed: u64) -> u64 {
+    let len = input.len();
+    seed ^= mix(seed ^ SECRET[0], SECRET[1]);
+    let (a, b) = if len <= 16 {
+        if len >= 4 {
+            let off = (len >> 3) << 2;
+            let a = (read4(input) << 32) | read4(&input[off..]);
+            let b = (read4(&input[len - 4..]) << 32) | read4(&input[len - 4 - off..]);
+            (a, b)
+        } else if len > 0 {
+            let a = (input[0] as u64) << 16 | (input[len >> 1] as u64) << 8 | input[len - 1] as u64;
+            (a, 0)
+        } else {
+            (0, 0)
+        }
+    } else {
+        let mut p = input;
+        if p.len() > 48 {
+            let (mut see1, mut see2) = (seed, seed);
+            while p.len() > 48 {
+                seed = mix(read8(p) ^ SECRET[1], read8(&p[8..]) ^ seed);
+                see1 = mix(read8(&p[16..]) ^ SECRET[2], read8(&p[24..]) ^ see1);
+                see2 = mix(read8(&p[32..]) ^ SECRET[3], read8(&p[40..]) ^ see2);
+                p = &p[48..];
+            }
+            seed ^= see1 ^ see2;
+        }
+        while p.len() > 16 {
+            seed = mix(read8(p) ^ SECRET[1], read8(&p[8..]) ^ seed);
+            p = &p[16..];
+        }
+        // The last 16 bytes of the input, overlapping data already consumed
+        (read8(&input[len - 16..]), read8(&input[len - 8..]))
+    };
+    let (a, b) = mum(a ^ SECRET[1], b ^ seed);
+    mix(a ^ SECRET[0] ^ len as u64, b ^ SECRET[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_vectors() {
+        let vectors: [(&[u8], u64); 7] = [
+            (b"", 0x93228a4de0eec5a2),
+            (b"a", 0xc5bac3db178713c4),
+            (b"abc", 0xa97f2f7b1d9b3314),
+            (b"message digest", 0x786d1f1df3801df4),
+            (b"abcdefghijklmnopqrstuvwxyz", 0xdca5a8138ad37c87),
+            (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", 0xb9e734f117cfaf70),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                0x6cc5eab49a92d617,
+            ),
+        ];
+        for (seed, (input, expected)) in vectors.into_iter().enumerate() {
+            assert_eq!(wyhash(input, seed as u64), expected, "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! XXH64 k-mer hasher (feature `xxhash`).
+
+use crate::{Result, StrobeError, hashes::KmerHasher};
+
+const P1: u64 = 0x9E37_79B1_85EB_CA87;
+const P2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const P3: u64 = 0x1656_67B1_9E37_79F9;
+const P4: u64 = 0x85EB_CA77_C2B2_AE63;
+const P5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// K-mer hasher applying XXH64 to each k-mer's bytes.
+///
+/// Every k-mer is hashed from scratch, in O(n·k), but XXH64 is fast enough
+/// on short keys to beat rolling hashes for small `k`, and its values do not
+/// depend on ntHash, which makes it a second reference hasher for tests.
+/// Bytes are hashed as given: lower- and uppercase bases differ, and any
+/// ASCII byte is accepted. Values equal those of other XXH64 implementations
+/// with the same seed.
+///
+/// # Example
+/// ```
+/// use strobemers_rs::{KmerHasher, RandStrobes, XxHash64Hasher};
+/// let hasher = XxHash64Hasher::new(0);
+/// let hashes = hasher.hash_all(b"ACGTACG", 3).unwrap();
+/// assert_eq!(hashes[0], hashes[4]);
+/// let rs = RandStrobes::with_hasher(b"ACGATCTGGTACCTAG", 2, 3, 3, 5, &hasher).unwrap();
+/// assert!(rs.count() > 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct XxHash64Hasher {
+    seed: u64,
+}
+
+impl XxHash64Hasher {
+    /// Creates a hasher using XXH64 with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl KmerHasher for XxHash64Hasher {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let mut out = Vec::new();
+        self.hash_all_into(seq, k, &mut out)?;
+        Ok(out)
+    }
+
+    fn hash_all_into(&self, seq: &[u8], k: usize, out: &mut Vec<u64>) -> Result<()> {
+        if !(1..=64).contains(&k) {
+            return Err(StrobeError::StrobeLengthTooSmall);
+        }
+        if seq.len() < k {
+            return Err(StrobeError::SequenceTooShort);
+        }
+        out.clear();
+        out.extend(seq.windows(k).map(|kmer| xxh64(kmer, self.seed)));
+        Ok(())
+    }
+}
+
+fn read64(b: &[u8]) -> u64 {
+    u64::from_le_bytes(b[..8].try_into().expect("8 bytes"))
+}
+
+fn read32(b: &[u8]) -> u64 {
+    u32::from_le_bytes(b[..4].try_into().expect("4 bytes")) as u64
+}
+
+fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(P2)).rotate_left(31).wrapping_mul(P1)
+}
+
+fn merge(acc: u64, v: u64) -> u64 {
+    (acc ^ round(0, v)).wrapping_mul(P1).wrapping_add(P4)
+}
+
+/// XXH64 of `input` with `seed`.
+fn xxh64(input: &[u8], seed: u64) -> u64 {
+    let mut rest = input;
+    let mut h = if input.len() >= 32 {
+        let mut v = [
+            seed.wrapping_add(P1).wrapping_add(P2),
+            seed.wrapping_add(P2),
+            seed,
+            seed.wrapping_sub(P1),
+        ];
+        while rest.len() >= 32 {
+            for (i, lane) in v.iter_mut().enumerate() {
+                *lane = round(*lane, read64(&rest[8 * i..]));
+            }
+            rest = &rest[32..];
+        }
+        let h = v[0]
+            .rotate_left(1)
+            .wrapping_add(v[1].rotate_left(7))
+            .wrapping_add(v[2].rotate_left(12))
+            .wrapping_add(v[3].rotate_left(18));
+        v.iter().fold(h, |h, &lane| merge(h, lane))
+    } else {
+        seed.wrapping_add(P5)
+    };
+    h = h.wrapping_add(input.len() as u64);
+
+    while rest.len() >= 8 {
+        h ^= round(0, read64(rest));
+        h = h.rotate_left(27).wrapping_mul(P1).wrapping_add(P4);
+        rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+        h ^= read32(rest).wrapping_mul(P1);
+        h = h.rotate_left(23).wrapping_mul(P2).wrapping_add(P3);
+        rest = &rest[4..];
+    }
+    for &b in rest {
+        h ^= (b as u64).wrapping_mul(P5);
+        h = h.rotate_left(11).wrapping_mul(P1);
+    }
+
+    h ^= h >> 33;
+    h = h.wrapping_mul(P2);
+    h ^= h >> 29;
+    h = h.wrapping_mul(P3);
+    h ^ (h >> 32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_vectors() {
+        assert_eq!(xxh64(b"", 0), 0xEF46_DB37_51D8_E999);
+        assert_eq!(xxh64(b"abc", 0), 0x44BC_2CF5_AD77_0999);
+    }
+
+    #[test]
+    fn hashes_each_kmer() {
+        let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC";
+        let hasher = XxHash64Hasher::new(9);
+        let hashes = hasher.hash_all(seq, 33).unwrap();
+        for (i, &h) in hashes.iter().enumerate() {
+            assert_eq!(h, xxh64(&seq[i..i + 33], 9));
+        }
+        assert_ne!(hashes[0], XxHash64Hasher::new(0).hash_all(seq, 33).unwrap()[0]);
+    }
+}
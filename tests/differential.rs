@@ -0,0 +1,27 @@
+//! Differential tests comparing the optimized iterators against the naive
+//! reference implementations on random sequences and parameters.
+//! Run with `cargo test --features reference`.
+#![cfg(feature = "reference")]
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use strobemers_rs::reference::assert_matches_reference;
+
+/// Number of random parameter sets to check.
+const CASES: usize = 500;
+
+#[test]
+fn optimized_matches_reference() {
+    let mut rng = StdRng::seed_from_u64(0x5EED);
+    for _ in 0..CASES {
+        let n = rng.random_range(2..=3u8);
+        let k = rng.random_range(1..=8usize);
+        let w_min = rng.random_range(0..=k);
+        let w_max = rng.random_range(w_min..=w_min + 6);
+        let min_len = n as usize * k + 2 * w_max;
+        let len = rng.random_range(min_len..=min_len + 100);
+        let seq: Vec<u8> = (0..len).map(|_| b"ACGT"[rng.random_range(0..4)]).collect();
+        let shrink = rng.random_bool(0.5);
+
+        assert_matches_reference(&seq, n, k, w_min, w_max, shrink);
+    }
+}
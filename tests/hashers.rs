@@ -0,0 +1,65 @@
+//! Distribution smoke tests shared by the built-in k-mer hashers.
+//!
+//! Strobe selection assumes k-mer hashes look uniformly random; these checks
+//! catch a hasher whose values cluster in a range or leave bits unused.
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use strobemers_rs::{KmerHasher, NtHash64, Result};
+
+/// Number of k-mers hashed per check.
+const KMERS: usize = 20_000;
+/// k-mer length.
+const K: usize = 15;
+
+/// Hashes of a random sequence yielding `KMERS` k-mers.
+fn random_hashes<H: KmerHasher>(hasher: &H) -> Result<Vec<u64>> {
+    let mut rng = StdRng::seed_from_u64(7);
+    let seq: Vec<u8> = (0..KMERS + K - 1).map(|_| b"ACGT"[rng.random_range(0..4)]).collect();
+    hasher.hash_all(&seq, K)
+}
+
+/// Asserts that the top 4 bits fill 16 buckets evenly and every bit is set
+/// about half of the time.
+fn assert_uniform(name: &str, hashes: &[u64]) {
+    let mut buckets = [0usize; 16];
+    let mut ones = [0usize; 64];
+    for &h in hashes {
+        buckets[(h >> 60) as usize] += 1;
+        for (bit, count) in ones.iter_mut().enumerate() {
+            *count += (h >> bit) as usize & 1;
+        }
+    }
+    let expected = hashes.len() / 16;
+    for (b, &count) in buckets.iter().enumerate() {
+        assert!(count.abs_diff(expected) < expected / 5, "{name}: bucket {b} holds {count}");
+    }
+    for (bit, &count) in ones.iter().enumerate() {
+        let share = count as f64 / hashes.len() as f64;
+        assert!((0.45..0.55).contains(&share), "{name}: bit {bit} set in {share:.3}");
+    }
+}
+
+/// ntHash values of random k-mers are spread evenly.
+#[test]
+fn nthash_distribution() -> Result<()> {
+    assert_uniform("NtHash64", &random_hashes(&NtHash64)?);
+    Ok(())
+}
+
+/// wyhash values of random k-mers are spread evenly.
+#[cfg(feature = "wyhash")]
+#[test]
+fn wyhash_distribution() -> Result<()> {
+    let hashes = random_hashes(&strobemers_rs::WyHashHasher::new(0))?;
+    assert_uniform("WyHashHasher", &hashes);
+    Ok(())
+}
+
+/// XXH64 values of random k-mers are spread evenly.
+#[cfg(feature = "xxhash")]
+#[test]
+fn xxhash_distribution() -> Result<()> {
+    let hashes = random_hashes(&strobemers_rs::XxHash64Hasher::new(0))?;
+    assert_uniform("XxHash64Hasher", &hashes);
+    Ok(())
+}
@@ -2,7 +2,13 @@
 //! ensuring that the Rust version produces at least as many strobes as the Go reference.
 //! The tests validate both order-2 and order-3 MinStrobes over a fixed sequence.
 
-use strobemers_rs::{MinStrobes, Result};
+use std::sync::Arc;
+
+use strobemers_rs::{
+    AmbiguityPolicy, CombineScheme, KmerCodeHasher, KmerHasher, LegacyCombiner, MinStrobes,
+    MinTable, ModMinimizers, NtHash64, Result, StrobeCombiner, StrobeError, Strobemer,
+    Strobemer128, TieBreak, WindowAnchor,
+};
 
 /// Fixed test sequence (ASCII bytes).
 const SEQ: &[u8] = b"ACGATCTGGTACCTAG";
@@ -120,3 +126,555 @@ fn minstrobes_order3() -> Result<()> {
     assert!(iter_count > 0, "iterator produced no items");
     Ok(())
 }
+
+/// Verifies that restricting m1 to mod-minimizer positions emits exactly one
+/// strobemer per selected position, in order.
+#[test]
+fn minstrobes_first_strobe_positions() -> Result<()> {
+    let all: Vec<u64> = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?.collect();
+    let anchors: Vec<usize> = ModMinimizers::new(SEQ, L, 4, 2)?.collect();
+
+    let mut ms = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    ms.set_first_strobe_positions(anchors.iter().copied());
+    let mut seen = Vec::new();
+    while let Some(h) = ms.next() {
+        let i1 = ms.index().unwrap();
+        assert_eq!(h, all[i1]);
+        seen.push(i1);
+    }
+    let expected: Vec<usize> = anchors.into_iter().filter(|&p| p < all.len()).collect();
+    assert_eq!(seen, expected);
+    Ok(())
+}
+
+/// Verifies density thinning: the output is the subsequence of seeds whose
+/// hash lies below the threshold, and out-of-range fractions are rejected.
+#[test]
+fn minstrobes_density() -> Result<()> {
+    let all: Vec<u64> = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?.collect();
+
+    let mut ms = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    ms.set_density(0.5)?;
+    let thinned: Vec<u64> = ms.collect();
+    let limit = (0.5 * u64::MAX as f64) as u64;
+    let expected: Vec<u64> = all.into_iter().filter(|&h| h <= limit).collect();
+    assert_eq!(thinned, expected);
+
+    let mut ms = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    assert!(ms.set_density(0.0).is_err());
+    assert!(ms.set_density(f64::NAN).is_err());
+    Ok(())
+}
+
+/// Verifies local first-strobe counts on a sequence with a repeated k-mer.
+#[test]
+fn minstrobes_local_counts() -> Result<()> {
+    let seq = b"ACGTTTACGTTTACGGCATT";
+    let mut ms = MinStrobes::new(seq, 2, 4, 2, 4)?;
+    assert_eq!(ms.first_strobe_count(), None);
+
+    ms.set_local_counts(true);
+    assert!(ms.next().is_some());
+    // "ACGT" occurs at positions 0 and 6
+    assert_eq!(ms.first_strobe_count(), Some(2));
+    Ok(())
+}
+
+/// Verifies that symmetric combination ignores strobe order and agrees with
+/// the legacy formula whenever the strobe hashes are already ascending.
+#[test]
+fn minstrobes_symmetric() -> Result<()> {
+    let hashes = NtHash64.hash_all(SEQ, L)?;
+    let mut legacy = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    legacy.set_combine_scheme(CombineScheme::Legacy);
+    let mut sym = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    sym.set_symmetric(true);
+
+    while let (Some(h), Some(s)) = (legacy.next(), sym.next()) {
+        let [m1, m2, _] = sym.indexes();
+        let (a, b) = (hashes[m1], hashes[m2]);
+        assert_eq!(s, a.min(b) / 2 + a.max(b) / 3);
+        if a <= b {
+            assert_eq!(s, h);
+        }
+    }
+    assert!(legacy.next().is_none() && sym.next().is_none());
+    Ok(())
+}
+
+/// Verifies that a closure combiner receives the strobe hashes in order.
+#[test]
+fn minstrobes_closure_combiner() -> Result<()> {
+    let hashes = NtHash64.hash_all(SEQ, L)?;
+    let mut ms = MinStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?;
+    ms.set_combiner(|s: &[u64]| s[0] ^ s[1].rotate_left(1) ^ s[2].rotate_left(2));
+
+    let mut seen = 0;
+    while let Some(h) = ms.next() {
+        let [m1, m2, m3] = ms.indexes();
+        assert_eq!(h, hashes[m1] ^ hashes[m2].rotate_left(1) ^ hashes[m3].rotate_left(2));
+        seen += 1;
+    }
+    assert!(seen > 0);
+    Ok(())
+}
+
+/// `with_positions` pairs each hash with the indexes reported after `next()`.
+#[test]
+fn minstrobes_with_positions() -> Result<()> {
+    for n in [2, 3] {
+        let mut ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        let pairs: Vec<(u64, [usize; 3])> =
+            MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        assert!(!pairs.is_empty());
+        for (h, positions) in pairs {
+            assert_eq!(ms.next(), Some(h));
+            assert_eq!(ms.indexes(), positions);
+        }
+        assert_eq!(ms.next(), None);
+    }
+    Ok(())
+}
+
+/// `remaining_upper_bound()` matches the number of remaining seeds at every step.
+#[test]
+fn minstrobes_size_hint() -> Result<()> {
+    for n in [2, 3] {
+        for shrink in [true, false] {
+            let mut ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            ms.set_window_shrink(shrink);
+            loop {
+                assert_eq!(ms.remaining_upper_bound(), ms.clone().count());
+                if ms.next().is_none() {
+                    break;
+                }
+            }
+            assert_eq!(ms.remaining_upper_bound(), 0);
+        }
+    }
+    Ok(())
+}
+
+/// Backward iteration yields the forward seeds in reverse order.
+#[test]
+fn minstrobes_double_ended() -> Result<()> {
+    for n in [2, 3] {
+        for shrink in [true, false] {
+            let mut ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            ms.set_window_shrink(shrink);
+            let forward: Vec<(u64, [usize; 3])> = ms.clone().with_positions().collect();
+            let mut backward = Vec::new();
+            while let Some(h) = ms.next_back() {
+                backward.push((h, ms.indexes()));
+            }
+            backward.reverse();
+            assert_eq!(backward, forward);
+            assert_eq!(ms.next(), None);
+        }
+    }
+    Ok(())
+}
+
+/// Iterators built from one shared table match independently built ones.
+#[test]
+fn minstrobes_shared_table() -> Result<()> {
+    let table = Arc::new(MinTable::new(SEQ, L, W_MIN, W_MAX)?);
+    for n in [2, 3] {
+        let shared: Vec<u64> = MinStrobes::from_table(Arc::clone(&table), n)?.collect();
+        let fresh: Vec<u64> = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.collect();
+        assert_eq!(shared, fresh);
+    }
+    assert!(MinStrobes::from_table(Arc::clone(&table), 4).is_err());
+
+    let ms = MinStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    let other = MinStrobes::from_table(Arc::clone(ms.table()), 3)?;
+    assert_eq!(other.table().hashes(), table.hashes());
+    assert_eq!(other.table().min_locations(), table.min_locations());
+    Ok(())
+}
+
+/// `step_by` over the fast `nth` yields every `step`-th seed.
+#[test]
+fn minstrobes_step_by() -> Result<()> {
+    for n in [2, 3] {
+        let all: Vec<u64> = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.collect();
+        for step in 1..=4 {
+            let ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            let stepped: Vec<u64> = ms.step_by(step).collect();
+            assert_eq!(stepped, all.iter().copied().step_by(step).collect::<Vec<_>>());
+        }
+    }
+    Ok(())
+}
+
+/// `seek(i)` resumes at the first seed whose first strobe is at `i` or later.
+#[test]
+fn minstrobes_seek() -> Result<()> {
+    for n in [2, 3] {
+        let all: Vec<(u64, [usize; 3])> =
+            MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        let mut ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        ms.next();
+        for i in [4, 1, SEQ.len()] {
+            ms.seek(i);
+            let rest: Vec<_> = ms.clone().with_positions().collect();
+            let expected: Vec<_> = all.iter().copied().filter(|s| s.1[0] >= i).collect();
+            assert_eq!(rest, expected);
+        }
+    }
+    Ok(())
+}
+
+/// With a stride `s`, only first strobes at multiples of `s` produce seeds.
+#[test]
+fn minstrobes_stride() -> Result<()> {
+    for n in [2, 3] {
+        let all: Vec<(u64, [usize; 3])> =
+            MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        for s in 1..=4 {
+            let mut ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            ms.set_stride(s)?;
+            let expected: Vec<_> = all.iter().copied().filter(|p| p.1[0] % s == 0).collect();
+            assert_eq!(ms.remaining_upper_bound(), expected.len());
+            assert_eq!(ms.clone().rev().count(), expected.len());
+            assert_eq!(ms.with_positions().collect::<Vec<_>>(), expected);
+        }
+    }
+    Ok(())
+}
+
+/// Region iterators yield the seeds of the region alone, shifted to global coordinates.
+#[test]
+fn minstrobes_in_region() -> Result<()> {
+    let (start, end) = (2, 14);
+    for n in [2, 3] {
+        let local: Vec<(u64, [usize; 3])> =
+            MinStrobes::new(&SEQ[start..end], n, L, W_MIN, W_MAX)?.with_positions().collect();
+        let mut ms = MinStrobes::new_in_region(SEQ, start..end, n, L, W_MIN, W_MAX)?;
+        ms.set_first_strobe_positions(local.iter().map(|s| s.1[0] + start));
+        let global: Vec<_> = ms.with_positions().collect();
+        assert_eq!(global.len(), local.len());
+        for ((hg, pg), (hl, pl)) in global.iter().zip(&local) {
+            assert_eq!(hg, hl);
+            assert_eq!(pg[..n as usize], pl.map(|p| p + start)[..n as usize]);
+        }
+    }
+    Ok(())
+}
+
+/// Under `SkipAffectedSeeds`, no strobe overlaps an ambiguous base.
+#[test]
+fn minstrobes_skip_ambiguous() -> Result<()> {
+    let seq = b"ACGATCTGGTACNNAGGATTACACGATCGGNTCCAGTTGACCA";
+    let policy = AmbiguityPolicy::SkipAffectedSeeds;
+    for n in [2, 3] {
+        let mut ms = MinStrobes::with_ambiguity_policy(seq, n, L, W_MIN, W_MAX, &NtHash64, policy)?;
+        let mut seeds = 0;
+        while ms.next().is_some() {
+            let strobes = &ms.indexes()[..n as usize];
+            assert!(strobes.iter().all(|&p| !seq[p..p + L].contains(&b'N')));
+            seeds += 1;
+        }
+        assert!(seeds > 0);
+    }
+    Ok(())
+}
+
+/// Masking low-quality bases matches skipping the same bases as ambiguous.
+#[test]
+fn minstrobes_quality_mask() -> Result<()> {
+    let seq = b"ACGATCTGGTACNNAGGATTACACGATCGGNTCCAGTTGACCA";
+    let clean = String::from_utf8_lossy(seq).replace('N', "A");
+    let qual: Vec<u8> = seq.iter().map(|&b| if b == b'N' { b'#' } else { b'I' }).collect();
+    let policy = AmbiguityPolicy::SkipAffectedSeeds;
+    let clean = clean.as_bytes();
+    for n in [2, 3] {
+        let masked = MinStrobes::with_quality_mask(clean, &qual, 20, n, L, W_MIN, W_MAX)?;
+        let skipped =
+            MinStrobes::with_ambiguity_policy(seq, n, L, W_MIN, W_MAX, &NtHash64, policy)?;
+        assert!(masked.iter_seeds().eq(skipped.iter_seeds()));
+    }
+    Ok(())
+}
+
+/// Hasher that marks k-mers overlapping a hard-masked `X` as invalid.
+struct HardMasked;
+
+impl KmerHasher for HardMasked {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let unmasked: Vec<u8> = seq.iter().map(|&b| if b == b'X' { b'A' } else { b }).collect();
+        NtHash64.hash_all(&unmasked, k)
+    }
+
+    fn validity(&self, seq: &[u8], k: usize) -> Option<Vec<bool>> {
+        Some(seq.windows(k).map(|w| !w.contains(&b'X')).collect())
+    }
+}
+
+/// Window minima and first strobes skip k-mers the hasher reports as invalid.
+#[test]
+fn minstrobes_hasher_validity() -> Result<()> {
+    let seq = b"ACGATCTGGTACXXXXGGATTACACGATCGGATCCAGTTGACCA";
+    for n in [2, 3] {
+        let mut ms = MinStrobes::with_hasher(seq, n, L, W_MIN, W_MAX, &HardMasked)?;
+        let mut seeds = 0;
+        while ms.next().is_some() {
+            let strobes = &ms.indexes()[..n as usize];
+            assert!(strobes.iter().all(|&p| !seq[p..p + L].contains(&b'X')));
+            seeds += 1;
+        }
+        assert!(seeds > 0);
+    }
+    Ok(())
+}
+
+/// Masked intervals are given in global coordinates for region iterators.
+#[test]
+fn minstrobes_masked_intervals_in_region() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+    let mut ms = MinStrobes::new_in_region(seq, 10..40, 2, L, W_MIN, W_MAX)?;
+    ms.set_masked_intervals([(20, 25)])?;
+    let mut seeds = 0;
+    while ms.next().is_some() {
+        assert!(ms.indexes()[..2].iter().all(|&p| p + L <= 20 || p >= 25));
+        seeds += 1;
+    }
+    assert!(seeds > 0);
+    Ok(())
+}
+
+/// 128-bit seeds keep the 64-bit hash and positions in their high half, and
+/// seeds with equal strobe bases get equal low halves.
+#[test]
+fn minstrobes_iter_seeds128() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGACGATCTGGTACCTAG";
+    let narrow: Vec<_> = MinStrobes::new(seq, 2, L, W_MIN, W_MAX)?.iter_seeds().collect();
+    let wide: Vec<_> = MinStrobes::new(seq, 2, L, W_MIN, W_MAX)?.iter_seeds128(seq).collect();
+    assert_eq!(wide.len(), narrow.len());
+    for (w, n) in wide.iter().zip(&narrow) {
+        assert_eq!(((w.hash >> 64) as u64, w.positions, w.span), (n.hash, n.positions, n.span));
+    }
+    let bases = |s: &Strobemer128| -> Vec<u8> {
+        s.strobes().iter().flat_map(|&p| seq[p..p + L].to_vec()).collect()
+    };
+    for a in &wide {
+        for b in &wide {
+            assert_eq!(a.hash as u64 == b.hash as u64, bases(a) == bases(b));
+        }
+    }
+    Ok(())
+}
+
+/// The default `Mix` scheme and `Legacy` differ only in the emitted hashes.
+#[test]
+fn minstrobes_combine_scheme() -> Result<()> {
+    let hashes = NtHash64.hash_all(SEQ, L)?;
+    for n in [2, 3] {
+        let mixed: Vec<Strobemer> =
+            MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.iter_seeds().collect();
+        let mut legacy = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        legacy.set_combine_scheme(CombineScheme::Legacy);
+        let legacy: Vec<Strobemer> = legacy.iter_seeds().collect();
+        assert_eq!(mixed.len(), legacy.len());
+        for (m, l) in mixed.iter().zip(&legacy) {
+            assert_eq!(m.positions, l.positions);
+            assert_ne!(m.hash, l.hash);
+            let strobes: Vec<u64> = m.positions[..n as usize].iter().map(|&p| hashes[p]).collect();
+            assert_eq!(m.hash, CombineScheme::Mix.combine(&strobes));
+            assert_eq!(l.hash, LegacyCombiner.combine(&strobes));
+        }
+        let mut restored = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        restored.set_combine_scheme(CombineScheme::Legacy);
+        restored.set_combine_scheme(CombineScheme::Mix);
+        assert!(restored.iter_seeds().eq(mixed));
+    }
+    Ok(())
+}
+
+/// Full and shrunk windows of a homopolymer follow the same explicit tie rule.
+#[test]
+fn minstrobes_tie_break() -> Result<()> {
+    let seq = [b'A'; 24];
+    for (tie, offset) in [(TieBreak::Leftmost, W_MIN), (TieBreak::Rightmost, W_MAX)] {
+        let mut ms = MinStrobes::with_hasher(&seq, 2, L, W_MIN, W_MAX, &KmerCodeHasher)?;
+        ms.set_tie_break(tie);
+        while ms.next().is_some() {
+            let [m1, m2, _] = ms.indexes();
+            let last = seq.len() - L;
+            let expected = match tie {
+                TieBreak::Rightmost => (m1 + offset).min(last),
+                _ => m1 + offset,
+            };
+            assert_eq!(m2, expected, "{tie:?} m1={m1}");
+        }
+    }
+    Ok(())
+}
+
+/// A span cap bounds every seed and keeps all anchors.
+#[test]
+fn minstrobes_max_span() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC";
+    for n in [2, 3] {
+        let plain: Vec<Strobemer> = MinStrobes::new(seq, n, L, 2, 8)?.iter_seeds().collect();
+        let cap = L + (n as usize - 2) * 8 + 4;
+        let mut capped = MinStrobes::new(seq, n, L, 2, 8)?;
+        capped.set_max_span(cap)?;
+        let capped: Vec<Strobemer> = capped.iter_seeds().collect();
+        assert_eq!(capped.len(), plain.len());
+        assert!(capped.iter().all(|s| s.span <= cap), "n={n}");
+        assert!(plain.iter().any(|s| s.span > cap));
+    }
+    Ok(())
+}
+
+/// A span floor lifts every seed's span and rejects floors no window reaches.
+#[test]
+fn minstrobes_min_span() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC";
+    for n in [2, 3] {
+        let plain: Vec<Strobemer> = MinStrobes::new(seq, n, L, 2, 8)?.iter_seeds().collect();
+        let floor = L + (n as usize - 2) * 8 + 6;
+        let mut raised = MinStrobes::new(seq, n, L, 2, 8)?;
+        raised.set_min_span(floor)?;
+        let raised: Vec<Strobemer> = raised.iter_seeds().collect();
+        assert!(!raised.is_empty());
+        assert!(raised.iter().all(|s| s.span >= floor), "n={n}");
+        assert!(plain.iter().any(|s| s.span < floor));
+
+        let mut strict = MinStrobes::new(seq, n, L, 2, 8)?;
+        let longest = L + (n as usize - 1) * 8;
+        assert_eq!(strict.set_min_span(longest + 1), Err(StrobeError::MinSpanTooLarge));
+        strict.set_min_span(longest)?;
+        assert!(strict.iter_seeds().all(|s| s.span == longest));
+    }
+    Ok(())
+}
+
+/// Anchoring m3's window at m2 keeps the anchors and places m3 within the
+/// offsets from m2.
+#[test]
+fn minstrobes_window_anchor() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCGTAGCATGCAAGT";
+    let first: Vec<Strobemer> = MinStrobes::new(seq, 3, L, 2, 8)?.iter_seeds().collect();
+    let mut prev = MinStrobes::new(seq, 3, L, 2, 8)?;
+    prev.set_window_anchor(WindowAnchor::FromPreviousStrobe);
+    let prev: Vec<Strobemer> = prev.iter_seeds().collect();
+    assert_eq!(prev.len(), first.len());
+    assert!(prev.iter().zip(&first).all(|(p, f)| p.positions[..2] == f.positions[..2]));
+    assert!(prev.iter().all(|s| (2..=8).contains(&(s.positions[2] - s.positions[1]))));
+    assert!(first.iter().any(|s| s.positions[2] - s.positions[1] > 8));
+    Ok(())
+}
+
+/// Sequences too short for `n` strobes are rejected rather than panicking,
+/// and the opt-in fallback seeds them with plain k-mers instead.
+#[test]
+fn minstrobes_short_sequences() -> Result<()> {
+    for len in 1..=SEQ.len() {
+        let seq = &SEQ[..len];
+        for (n, k, w_max) in [(2, 3, 1), (2, 6, 2), (3, 3, 2), (3, 5, 1), (3, 2, 4)] {
+            let fits = len >= n as usize * k && len >= (n as usize - 1) * (w_max + 1);
+            match MinStrobes::new(seq, n, k, 1, w_max) {
+                Ok(ms) => assert!(fits && ms.count() > 0),
+                Err(e) => assert!(!fits && e == StrobeError::SequenceTooShort),
+            }
+            let fallback = MinStrobes::with_kmer_fallback(seq, n, k, 1, w_max, &NtHash64);
+            match (fits, len >= k) {
+                (true, _) => assert!(fallback?.eq(MinStrobes::new(seq, n, k, 1, w_max)?)),
+                (false, true) => {
+                    let seeds: Vec<Strobemer> = fallback?.iter_seeds().collect();
+                    let kmers = NtHash64.hash_all(seq, k)?;
+                    assert!(seeds.iter().map(|s| s.hash).eq(kmers));
+                    assert!(seeds.iter().enumerate().all(|(i, s)| s.strobes() == [i]));
+                }
+                (false, false) => assert_eq!(fallback.err(), Some(StrobeError::SequenceTooShort)),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Tables with `u32` locations produce the seeds of the default `usize` ones,
+/// including after changing the tie rule.
+#[test]
+fn minstrobes_u32_positions() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCAAAAAAAAACCGT".repeat(3);
+    for n in [2, 3] {
+        let compact = MinStrobes::with_u32_positions(&seq, n, 4, 2, 9, &NtHash64)?;
+        let wide = MinStrobes::new(&seq, n, 4, 2, 9)?;
+        assert!(compact.clone().iter_seeds().eq(wide.clone().iter_seeds()));
+
+        let (hashes, m1, m2, m3) = compact.clone().collect_soa()?;
+        assert_eq!((hashes, m1, m2, m3), wide.clone().collect_soa()?);
+
+        let (mut compact, mut wide) = (compact, wide);
+        compact.set_tie_break(TieBreak::Leftmost);
+        wide.set_tie_break(TieBreak::Leftmost);
+        assert!(compact.iter_seeds().eq(wide.iter_seeds()));
+    }
+
+    let mut table = MinTable::with_u32_positions(&seq, 4, 2, 9, &NtHash64)?;
+    table.set_tie_break(TieBreak::Leftmost);
+    let mut wide = MinTable::new(&seq, 4, 2, 9)?;
+    wide.set_tie_break(TieBreak::Leftmost);
+    assert!(table.min_locations().iter().map(|&p| p as usize).eq(wide.min_locations().to_vec()));
+    let ms = MinStrobes::from_table(Arc::new(table), 3)?;
+    assert!(ms.eq(MinStrobes::from_table(Arc::new(wide), 3)?));
+    Ok(())
+}
+
+/// Bulk output yields the seeds, and leaves the state, of per-item iteration.
+#[test]
+fn minstrobes_fill_slice() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCAAAAAAAAACCGT".repeat(4);
+    for setup in 0..4 {
+        let build = || -> Result<MinStrobes> {
+            let mut ms = MinStrobes::new(&seq, 3, 4, 2, 9)?;
+            match setup {
+                1 => ms.set_stride(3)?,
+                2 => ms.set_density(0.5)?,
+                3 => drop(ms.next_back()),
+                _ => {}
+            }
+            Ok(ms)
+        };
+        let expected: Vec<u64> = build()?.collect();
+        assert_eq!(build()?.into_vec(), expected);
+        for chunk in [1, 7, 1000] {
+            let (mut bulk, mut single) = (build()?, build()?);
+            let mut buf = vec![0; chunk];
+            let mut seeds = Vec::new();
+            loop {
+                let n = bulk.fill_slice(&mut buf);
+                seeds.extend_from_slice(&buf[..n]);
+                single.by_ref().take(n).for_each(drop);
+                if n < chunk {
+                    assert_eq!(single.next(), None);
+                }
+                assert_eq!(bulk.indexes(), single.indexes());
+                if n < chunk {
+                    break;
+                }
+            }
+            assert_eq!(seeds, expected, "setup {setup} chunk {chunk}");
+        }
+    }
+    Ok(())
+}
+
+/// Caller-supplied hashes seed like the hasher that produced them.
+#[test]
+fn minstrobes_from_hashes() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCAAAAAAAAACCGT".repeat(2);
+    for (n, k, w_min, w_max) in [(2, 3, 3, 5), (3, 4, 0, 9), (3, 1, 2, 2), (2, 7, 10, 40)] {
+        let hashes = KmerCodeHasher.hash_all(&seq, k)?;
+        let supplied = MinStrobes::from_hashes(&hashes, n, k, w_min, w_max)?;
+        let hashed = MinStrobes::with_hasher(&seq, n, k, w_min, w_max, &KmerCodeHasher)?;
+        assert!(supplied.iter_seeds().eq(hashed.iter_seeds()));
+    }
+    assert_eq!(MinStrobes::from_hashes(&[], 2, 3, 3, 5).err(), Some(StrobeError::InvalidSequence));
+    let few = MinStrobes::from_hashes(&[1, 2, 3], 2, 3, 3, 5);
+    assert_eq!(few.err(), Some(StrobeError::SequenceTooShort));
+    Ok(())
+}
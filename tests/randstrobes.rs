@@ -2,7 +2,15 @@
 //! ensuring that the Rust version produces at least one strobemer per order.
 //! The tests validate both order-2 and order-3 RandStrobes over a fixed sequence.
 
-use strobemers_rs::{RandStrobes, Result};
+use std::collections::HashMap;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use strobemers_rs::{
+    Alphabet, AlphabetHasher, AmbiguityPolicy, CombineScheme, DualStrand, KmerCodeHasher,
+    KmerHasher, LegacyCombiner, MaskedSum, MinXor, NtHash64, PerformanceMode, RandStrobes, Result,
+    SeedVerdict, SoftMask, Strand, StrobeError, StrobeSpec, Strobemer, TieBreak, WindowAnchor,
+    nt4, reverse_complement,
+};
 
 /// Fixed test sequence (ASCII bytes).
 const SEQ: &[u8] = b"ACGATCTGGTACCTAG";
@@ -122,3 +130,1022 @@ fn randstrobes_order3() -> Result<()> {
     assert!(iter_count > 0, "iterator produced no items");
     Ok(())
 }
+
+/// Verifies that swapping in the default strategy explicitly leaves the
+/// output unchanged, and that an alternative strategy still yields one
+/// strobemer per start position.
+#[test]
+fn randstrobes_selection_strategy() -> Result<()> {
+    let default: Vec<u64> = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?.collect();
+    let explicit: Vec<u64> = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?
+        .with_strategy(MaskedSum)
+        .collect();
+    assert_eq!(default, explicit);
+
+    let xor: Vec<u64> = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?
+        .with_strategy(MinXor)
+        .collect();
+    assert_eq!(xor.len(), default.len());
+    Ok(())
+}
+
+/// Verifies that a prohibitive distance penalty always selects the first
+/// candidate of the window, i.e. m2 = m1 + w_min.
+#[test]
+fn randstrobes_distance_penalty() -> Result<()> {
+    let mut rs = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    rs.set_distance_penalty(u64::MAX);
+    while rs.next().is_some() {
+        let [i1, i2, _] = rs.indexes();
+        assert_eq!(i2, i1 + W_MIN);
+    }
+    Ok(())
+}
+
+/// Verifies ambiguity flagging: no seed is unstable while the check is off,
+/// and every seed is unstable with the widest possible margin.
+#[test]
+fn randstrobes_ambiguity_margin() -> Result<()> {
+    let mut rs = RandStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?;
+    while rs.next().is_some() {
+        assert!(!rs.is_unstable());
+    }
+
+    let mut rs = RandStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?;
+    rs.set_ambiguity_margin(Some(u64::MAX));
+    while rs.next().is_some() {
+        assert!(rs.is_unstable());
+    }
+    Ok(())
+}
+
+/// Verifies that draining the iterator in bounded chunks yields the same
+/// hashes as plain iteration.
+#[test]
+fn randstrobes_poll_seeds() -> Result<()> {
+    let all: Vec<u64> = RandStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?.collect();
+
+    let mut rs = RandStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?;
+    let mut out = Vec::new();
+    let mut calls = 0;
+    while !rs.poll_seeds(2, &mut out) {
+        calls += 1;
+    }
+    assert_eq!(out, all);
+    assert!(calls >= all.len() / 2);
+    Ok(())
+}
+
+/// Verifies that gapped strobemers with all-care masks reproduce the ungapped
+/// output, and that a mismatched mask count or an unsupported order is rejected.
+#[test]
+fn randstrobes_spaced_masks() -> Result<()> {
+    let plain: Vec<u64> = RandStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?.collect();
+    let masks: [&[u8]; 3] = [b"111", b"111", b"111"];
+    let gapped: Vec<u64> =
+        RandStrobes::with_spaced_masks(SEQ, 3, &masks, W_MIN, W_MAX, &NtHash64)?.collect();
+    assert_eq!(plain, gapped);
+
+    let err = RandStrobes::with_spaced_masks(SEQ, 2, &masks, W_MIN, W_MAX, &NtHash64);
+    assert_eq!(err.unwrap_err(), StrobeError::InvalidSpacedMask);
+    let err = RandStrobes::with_spaced_masks(SEQ, 0, &[], W_MIN, W_MAX, &NtHash64);
+    assert_eq!(err.unwrap_err(), StrobeError::OrderNotSupported);
+    let err = RandStrobes::with_spaced_masks(SEQ, 2, &[], W_MIN, W_MAX, &NtHash64);
+    assert_eq!(err.unwrap_err(), StrobeError::InvalidSpacedMask);
+    Ok(())
+}
+
+/// Verifies that canonical RandStrobes emit the same multiset of hashes for a
+/// sequence and its reverse complement.
+#[test]
+fn randstrobes_canonical_strand_neutral() -> Result<()> {
+    let fwd = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+    let rev = reverse_complement(fwd);
+
+    for n in [2, 3] {
+        let mut a: Vec<u64> = RandStrobes::new_canonical(fwd, n, L, W_MIN, W_MAX)?.collect();
+        let mut b: Vec<u64> = RandStrobes::new_canonical(&rev, n, L, W_MIN, W_MAX)?.collect();
+        assert!(!a.is_empty());
+        a.sort_unstable();
+        b.sort_unstable();
+        assert_eq!(a, b);
+    }
+    Ok(())
+}
+
+/// Canonical seeds over a custom non-ASCII alphabet are strand-neutral too.
+#[test]
+fn randstrobes_canonical_custom_alphabet() -> Result<()> {
+    // Two complementary symbol pairs outside ASCII
+    let alphabet = Alphabet::from_symbols(&[0x80, 0x81, 0xF0, 0xF1])?;
+    let hasher = AlphabetHasher::new(alphabet.with_complement(&[(0x80, 0xF0), (0x81, 0xF1)])?);
+    let fwd: Vec<u8> = SEQ.iter().map(|&b| [0x80, 0x81, 0xF0, 0xF1][nt4(b) as usize]).collect();
+    let rev = hasher.alphabet().reverse_complement(&fwd)?;
+
+    let mut a: Vec<u64> = RandStrobes::canonical_with_hasher(&fwd, 2, L, 2, 3, &hasher)?.collect();
+    let mut b: Vec<u64> = RandStrobes::canonical_with_hasher(&rev, 2, L, 2, 3, &hasher)?.collect();
+    assert!(!a.is_empty());
+    a.sort_unstable();
+    b.sort_unstable();
+    assert_eq!(a, b);
+
+    let err = RandStrobes::new(&fwd, 2, L, W_MIN, W_MAX).unwrap_err();
+    assert_eq!(err, StrobeError::InvalidSequence);
+    Ok(())
+}
+
+/// Canonical seeds report their orientation, and every strobe k-mer reports
+/// the opposite strand when the sequence is reverse complemented.
+#[test]
+fn randstrobes_canonical_strand() -> Result<()> {
+    let fwd = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+    let rev = reverse_complement(fwd);
+
+    let mut plain = RandStrobes::new(fwd, 2, L, W_MIN, W_MAX)?;
+    plain.next();
+    assert_eq!(plain.strand(), None);
+    assert_eq!(plain.strobe_strands(), None);
+
+    // Strand of each strobe k-mer, keyed by forward-strand position
+    let strobe_strands = |seq: &[u8], mirror: bool| -> Result<Vec<(usize, Strand)>> {
+        let mut rs = RandStrobes::new_canonical(seq, 2, L, W_MIN, W_MAX)?;
+        assert_eq!(rs.strand(), None);
+        let mut v = Vec::new();
+        while rs.next().is_some() {
+            let [m1, m2, _] = rs.indexes();
+            let [s1, s2, _] = rs.strobe_strands().unwrap();
+            assert_eq!(rs.strand() == Some(Strand::Forward), m2 > m1);
+            for (pos, strand) in [(m1, s1), (m2, s2)] {
+                v.push(if mirror { (seq.len() - L - pos, strand.flip()) } else { (pos, strand) });
+            }
+        }
+        Ok(v)
+    };
+    let a = strobe_strands(fwd, false)?;
+    let b = strobe_strands(&rev, true)?;
+    assert!(!a.is_empty());
+    // An odd k-mer is never its own reverse complement, so orientations must agree
+    for (pos, strand) in &a {
+        if let Some((_, other)) = b.iter().find(|(p, _)| p == pos) {
+            assert_eq!(strand, other);
+        }
+    }
+    Ok(())
+}
+
+/// Reverse-complement seeding matches seeding the reverse complement by hand,
+/// with positions reported in forward coordinates.
+#[test]
+fn randstrobes_revcomp_coordinates() -> Result<()> {
+    let fwd = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+    let rev = reverse_complement(fwd);
+    let last = fwd.len() - L;
+
+    for n in [2, 3] {
+        let mut by_hand = RandStrobes::new(&rev, n, L, W_MIN, W_MAX)?;
+        let mut mapped = RandStrobes::new_revcomp(fwd, n, L, W_MIN, W_MAX)?;
+        assert_eq!(mapped.index(), None);
+        while let Some(h) = by_hand.next() {
+            assert_eq!(mapped.next(), Some(h));
+            let [m1, m2, m3] = by_hand.indexes();
+            let expected_m3 = if n == 3 { last - m3 } else { 0 };
+            assert_eq!(mapped.indexes(), [last - m1, last - m2, expected_m3]);
+            assert_eq!(mapped.index(), Some(last - m1));
+        }
+        assert_eq!(mapped.next(), None);
+    }
+
+    // First-strobe positions are given in forward coordinates
+    let mut rs = RandStrobes::new_revcomp(fwd, 2, L, W_MIN, W_MAX)?;
+    rs.set_first_strobe_positions([last - 4]);
+    assert!(rs.next().is_some());
+    assert_eq!(rs.index(), Some(last - 4));
+    assert_eq!(rs.next(), None);
+    Ok(())
+}
+
+/// The dual-strand iterator reproduces the forward and reverse-complement
+/// iterators, alternating between them.
+#[test]
+fn randstrobes_dual_strand() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+
+    for n in [2, 3] {
+        let mut fwd = RandStrobes::new(seq, n, L, W_MIN, W_MAX)?;
+        let mut rev = RandStrobes::new_revcomp(seq, n, L, W_MIN, W_MAX)?;
+        let mut dual = DualStrand::new(seq, n, L, W_MIN, W_MAX)?;
+        assert_eq!(dual.strand(), None);
+
+        let mut expected = Vec::new();
+        loop {
+            let (f, r) = (fwd.next(), rev.next());
+            if f.is_none() && r.is_none() {
+                break;
+            }
+            if let Some(h) = f {
+                expected.push((Strand::Forward, h, fwd.indexes()));
+            }
+            if let Some(h) = r {
+                expected.push((Strand::Reverse, h, rev.indexes()));
+            }
+        }
+
+        let mut got = Vec::new();
+        while let Some((strand, h)) = dual.next() {
+            assert_eq!(dual.strand(), Some(strand));
+            got.push((strand, h, dual.indexes()));
+        }
+        assert_eq!(got, expected);
+    }
+    Ok(())
+}
+
+/// Setting the legacy combiner explicitly reproduces `CombineScheme::Legacy`,
+/// and the default `Mix` scheme keeps the strobes.
+#[test]
+fn randstrobes_legacy_combiner() -> Result<()> {
+    for n in [2, 3] {
+        let mut legacy = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        legacy.set_combine_scheme(CombineScheme::Legacy);
+        let legacy: Vec<Strobemer> = legacy.iter_seeds().collect();
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        rs.set_combiner(LegacyCombiner);
+        assert!(rs.iter_seeds().eq(legacy.iter().copied()));
+        let mixed = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.iter_seeds();
+        assert!(mixed.map(|s| s.positions).eq(legacy.iter().map(|s| s.positions)));
+    }
+    Ok(())
+}
+
+/// Seed items carry the same hashes and positions as `next()` + `indexes()`.
+#[test]
+fn randstrobes_iter_seeds() -> Result<()> {
+    for n in [2, 3] {
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        let seeds: Vec<Strobemer> =
+            RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.iter_seeds().collect();
+        for seed in &seeds {
+            assert_eq!(rs.next(), Some(seed.hash));
+            assert_eq!(seed.positions, rs.indexes());
+            assert_eq!(seed.order, n);
+            assert_eq!(seed.strobes().len(), n as usize);
+            assert_eq!(seed.span, seed.strobes().last().unwrap() + L - seed.positions[0]);
+        }
+        assert_eq!(rs.next(), None);
+    }
+    Ok(())
+}
+
+/// `explain` agrees with iteration: emitted links are `Generated`, other window
+/// candidates are `Outscored` by the emitted strobe, positions outside every
+/// window are `OutsideWindow` and excluded anchors are `NotAnchor`.
+#[test]
+fn randstrobes_explain() -> Result<()> {
+    for n in [2, 3] {
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        let probe = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        while rs.next().is_some() {
+            let [m1, m2, m3] = rs.indexes();
+            let strobes = if n == 2 { vec![m2] } else { vec![m2, m3] };
+            for (j, &m) in strobes.iter().enumerate() {
+                let ex = probe.explain(m1, m);
+                assert_eq!(ex.verdict, SeedVerdict::Generated);
+                assert_eq!(ex.strobe, Some(j as u8 + 2));
+                assert_eq!(ex.chosen, Some(m));
+                let (lo, hi) = ex.windows[j];
+                for other in (lo..=hi).filter(|&p| p != m) {
+                    let ex = probe.explain(m1, other);
+                    assert_eq!(ex.verdict, SeedVerdict::Outscored);
+                    assert!(ex.score_of(m) <= ex.score_of(other));
+                }
+            }
+            assert_eq!(probe.explain(m1, m1 + 1).verdict, SeedVerdict::OutsideWindow);
+        }
+    }
+
+    let mut rs = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    assert_eq!(rs.explain(SEQ.len(), 0).verdict, SeedVerdict::NotAnchor);
+    rs.set_first_strobe_positions([1]);
+    assert_eq!(rs.explain(0, 4).verdict, SeedVerdict::NotAnchor);
+    assert_ne!(rs.explain(1, 4).verdict, SeedVerdict::NotAnchor);
+    Ok(())
+}
+
+/// Exact mode reproduces the default seeds; sampling modes only pick m2 at
+/// multiples of their candidate step from the window start.
+#[test]
+fn randstrobes_performance_mode() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+    let default: Vec<u64> = RandStrobes::new(seq, 2, L, 2, 12)?.collect();
+    let mut exact = RandStrobes::new(seq, 2, L, 2, 12)?;
+    exact.set_performance_mode(PerformanceMode::Exact);
+    assert_eq!(exact.collect::<Vec<u64>>(), default);
+
+    for mode in [PerformanceMode::Balanced, PerformanceMode::Fast] {
+        let mut rs = RandStrobes::new(seq, 2, L, 2, 12)?;
+        rs.set_performance_mode(mode);
+        for (_, [m1, m2, _]) in rs.with_positions() {
+            assert_eq!((m2 - m1 - 2) % mode.candidate_step(), 0);
+        }
+    }
+    Ok(())
+}
+
+/// Reverse-complement seeds expose the same bases as plain seeds of the
+/// reverse-complemented sequence.
+#[test]
+fn randstrobes_seed_bytes() -> Result<()> {
+    let rc = reverse_complement(SEQ);
+    for n in [2, 3] {
+        let mut rev = RandStrobes::new_revcomp(SEQ, n, L, W_MIN, W_MAX)?;
+        let mut plain = RandStrobes::new(&rc, n, L, W_MIN, W_MAX)?;
+        assert!(rev.seed_bytes(SEQ).is_empty());
+        while let Some(h) = plain.next() {
+            assert_eq!(rev.next(), Some(h));
+            assert_eq!(rev.seed_bytes(SEQ), plain.seed_bytes(&rc));
+            assert_eq!(plain.seed_bytes(&rc).len(), n as usize * L);
+        }
+    }
+    Ok(())
+}
+
+/// An owning iterator seeds like `new` and extracts bases from its own copy
+/// of the sequence on another thread.
+#[test]
+fn randstrobes_from_owned() -> Result<()> {
+    for n in [2, 3] {
+        let owned = RandStrobes::from_owned(SEQ.to_vec(), n, L, W_MIN, W_MAX)?;
+        let handle = std::thread::spawn(move || {
+            let mut rs = owned;
+            let mut out = Vec::new();
+            while let Some(h) = rs.next() {
+                out.push((h, rs.seed_bytes(rs.sequence().unwrap())));
+            }
+            out
+        });
+        let mut plain = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        let mut expected = Vec::new();
+        while let Some(h) = plain.next() {
+            expected.push((h, plain.seed_bytes(SEQ)));
+        }
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+    assert_eq!(RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?.sequence(), None);
+    Ok(())
+}
+
+/// `remaining_upper_bound()` matches the number of remaining seeds at every
+/// step, and filters only lower the lower bound of `size_hint`.
+#[test]
+fn randstrobes_size_hint() -> Result<()> {
+    for n in [2, 3] {
+        for shrink in [true, false] {
+            let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            rs.set_window_shrink(shrink);
+            loop {
+                assert_eq!(rs.remaining_upper_bound(), rs.clone().count());
+                if rs.next().is_none() {
+                    break;
+                }
+            }
+            assert_eq!(rs.remaining_upper_bound(), 0);
+        }
+    }
+
+    let seq = b"ACGATCTGGTACCTAGGATTACACG";
+    let canonical = RandStrobes::new_canonical(seq, 2, L, W_MIN, W_MAX)?;
+    assert_eq!(canonical.remaining_upper_bound(), canonical.clone().count());
+
+    let mut rs = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    let all = rs.remaining_upper_bound();
+    rs.set_density(0.5)?;
+    assert_eq!(rs.size_hint(), (0, Some(all)));
+    assert_eq!(rs.remaining_upper_bound(), all);
+    assert!(rs.count() <= all);
+    Ok(())
+}
+
+/// Backward iteration yields the forward seeds in reverse order, also with
+/// filters, and both ends meet without overlap.
+#[test]
+fn randstrobes_double_ended() -> Result<()> {
+    for n in [2, 3] {
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        rs.set_first_strobe_positions([0, 2, 3, 5, 7]);
+        rs.set_density(0.6)?;
+        let forward: Vec<(u64, [usize; 3])> = rs.clone().with_positions().collect();
+        let mut backward = Vec::new();
+        while let Some(h) = rs.next_back() {
+            backward.push((h, rs.indexes()));
+        }
+        backward.reverse();
+        assert_eq!(backward, forward);
+
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        let all: Vec<u64> = rs.clone().collect();
+        let mut mixed = Vec::new();
+        let mut tail = Vec::new();
+        loop {
+            match (rs.next(), rs.next_back()) {
+                (Some(a), Some(b)) => {
+                    mixed.push(a);
+                    tail.push(b);
+                }
+                (Some(a), None) => mixed.push(a),
+                (None, _) => break,
+            }
+        }
+        mixed.extend(tail.into_iter().rev());
+        assert_eq!(mixed, all);
+        assert_eq!(rs.next(), None);
+    }
+    Ok(())
+}
+
+/// `nth` and `step_by` skip exactly like repeated `next` calls.
+#[test]
+fn randstrobes_nth() -> Result<()> {
+    for n in [2, 3] {
+        let all: Vec<(u64, [usize; 3])> =
+            RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        for step in 1..=all.len() + 1 {
+            let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            let mut stepped = Vec::new();
+            while let Some(h) = rs.nth(step - 1) {
+                stepped.push((h, rs.indexes()));
+            }
+            let expected: Vec<_> = all.iter().copied().skip(step - 1).step_by(step).collect();
+            assert_eq!(stepped, expected);
+        }
+
+        let mut thinned = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        thinned.set_density(0.5)?;
+        let kept: Vec<u64> = thinned.clone().collect();
+        let every_other: Vec<u64> = thinned.step_by(2).collect();
+        assert_eq!(every_other, kept.into_iter().step_by(2).collect::<Vec<_>>());
+    }
+    Ok(())
+}
+
+/// GC-equalized thinning keeps about the same fraction of seeds in AT-rich
+/// and GC-rich regions, and validates its parameters.
+#[test]
+fn randstrobes_gc_density() -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut seq: Vec<u8> = (0..200).map(|_| b"AT"[rng.random_range(0..2)]).collect();
+    seq.extend((0..200).map(|_| b"GC"[rng.random_range(0..2)]));
+
+    let mut rs = RandStrobes::new(&seq, 2, 5, 3, 8)?;
+    rs.set_gc_density(&seq, 0.5, 10, 4)?;
+    let (mut at, mut gc) = (0.0, 0.0);
+    for (_, [m1, _, _]) in rs.with_positions() {
+        if m1 < 200 { at += 1.0 } else { gc += 1.0 }
+    }
+    let all = RandStrobes::new(&seq, 2, 5, 3, 8)?.count() as f64;
+    // Each half holds about half of all anchors
+    for kept in [at, gc] {
+        assert!((kept / (all / 2.0) - 0.5).abs() < 0.1, "{kept} of {all}");
+    }
+
+    let mut rs = RandStrobes::new(&seq, 2, 5, 3, 8)?;
+    assert_eq!(rs.set_gc_density(&seq, 0.0, 10, 4), Err(StrobeError::InvalidDensity));
+    assert_eq!(rs.set_gc_density(&seq, 0.5, 0, 4), Err(StrobeError::InvalidWindowLength));
+    assert_eq!(rs.set_gc_density(&seq, 0.5, 10, 0), Err(StrobeError::InvalidBinCount));
+    assert_eq!(rs.set_gc_density(&seq[1..], 0.5, 10, 4), Err(StrobeError::InvalidSequence));
+    Ok(())
+}
+
+/// After `seek(i)`, iteration continues with the seeds whose first strobe is
+/// at `i` or later (earlier on the input in reverse-complement mode).
+#[test]
+fn randstrobes_seek() -> Result<()> {
+    for n in [2, 3] {
+        let all: Vec<(u64, [usize; 3])> =
+            RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        rs.by_ref().for_each(drop);
+        for i in [5, 0, SEQ.len()] {
+            rs.seek(i);
+            let rest: Vec<_> = rs.clone().with_positions().collect();
+            let expected: Vec<_> = all.iter().copied().filter(|s| s.1[0] >= i).collect();
+            assert_eq!(rest, expected);
+        }
+
+        let all: Vec<(u64, [usize; 3])> =
+            RandStrobes::new_revcomp(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        let mut rs = RandStrobes::new_revcomp(SEQ, n, L, W_MIN, W_MAX)?;
+        rs.seek(9);
+        let rest: Vec<_> = rs.with_positions().collect();
+        let expected: Vec<_> = all.iter().copied().filter(|s| s.1[0] <= 9).collect();
+        assert_eq!(rest, expected);
+    }
+    Ok(())
+}
+
+/// `top_informative` keeps the rarest seeds in read order and drops unknown ones.
+#[test]
+fn randstrobes_top_informative() -> Result<()> {
+    let seeds: Vec<(u64, [usize; 3])> =
+        RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?.with_positions().collect();
+    // Reference abundance decreasing along the read, last seed missing
+    let counts: HashMap<u64, usize> = seeds
+        .iter()
+        .take(seeds.len() - 1)
+        .enumerate()
+        .map(|(i, s)| (s.0, seeds.len() - i))
+        .collect();
+    let rs = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    let top = rs.top_informative(3, &counts);
+    let n = seeds.len();
+    assert_eq!(top, seeds[n - 4..n - 1]);
+    Ok(())
+}
+
+/// With a stride `s`, only first strobes on the grid `0, s, 2s, …` produce
+/// seeds, identical to the seeds at those positions without a stride.
+#[test]
+fn randstrobes_stride() -> Result<()> {
+    for n in [2, 3] {
+        let all: Vec<(u64, [usize; 3])> =
+            RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        for s in 1..=4 {
+            let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+            rs.set_stride(s)?;
+            let expected: Vec<_> = all.iter().copied().filter(|p| p.1[0] % s == 0).collect();
+            assert_eq!(rs.remaining_upper_bound(), expected.len());
+            let mut back = rs.clone();
+            let mut rev = Vec::new();
+            while let Some(h) = back.next_back() {
+                rev.push((h, back.indexes()));
+            }
+            rev.reverse();
+            assert_eq!(rev, expected);
+            assert_eq!(rs.clone().nth(1), expected.get(1).map(|p| p.0));
+            assert_eq!(rs.with_positions().collect::<Vec<_>>(), expected);
+        }
+    }
+    let mut rs = RandStrobes::new(SEQ, 2, L, W_MIN, W_MAX)?;
+    assert_eq!(rs.set_stride(0), Err(StrobeError::InvalidStride));
+    Ok(())
+}
+
+/// Iterators built from a `StrobeSpec` match the equivalent manual setup.
+#[test]
+fn randstrobes_from_spec() -> Result<()> {
+    let spec: StrobeSpec = "rand3-k3-w3:5-xor-frac0.5-step2".parse()?;
+    let mut rs = RandStrobes::new(SEQ, 3, L, W_MIN, W_MAX)?.with_strategy(MinXor);
+    rs.set_density(0.5)?;
+    rs.set_stride(2)?;
+    let from_spec: Vec<u64> = spec.rand_strobes(SEQ)?.collect();
+    assert_eq!(from_spec, rs.collect::<Vec<u64>>());
+    assert!(matches!(spec.min_strobes(SEQ), Err(StrobeError::InvalidSpec(_))));
+    Ok(())
+}
+
+/// Region iterators yield the seeds of the region alone, shifted to global
+/// coordinates, and position-based calls take global coordinates too.
+#[test]
+fn randstrobes_in_region() -> Result<()> {
+    let (start, end) = (3, 15);
+    for n in [2, 3] {
+        let local: Vec<(u64, [usize; 3])> =
+            RandStrobes::new(&SEQ[start..end], n, L, W_MIN, W_MAX)?.with_positions().collect();
+        let mut rs = RandStrobes::new_in_region(SEQ, start..end, n, L, W_MIN, W_MAX)?;
+        let global: Vec<_> = rs.clone().with_positions().collect();
+        assert_eq!(global.len(), local.len());
+        for ((hg, pg), (hl, pl)) in global.iter().zip(&local) {
+            assert_eq!(hg, hl);
+            assert_eq!(pg[0], pl[0] + start);
+            assert_eq!(pg[n as usize - 1], pl[n as usize - 1] + start);
+        }
+
+        rs.seek(global[1].1[0]);
+        rs.next();
+        let [m1, m2, _] = rs.indexes();
+        assert_eq!(m1, global[1].1[0]);
+        assert_eq!(rs.explain(m1, m2).verdict, SeedVerdict::Generated);
+        assert_eq!(rs.seed_bytes(SEQ)[..L], SEQ[m1..m1 + L]);
+    }
+    assert_eq!(
+        RandStrobes::new_in_region(SEQ, 4..SEQ.len() + 1, 2, L, W_MIN, W_MAX).err(),
+        Some(StrobeError::InvalidRegion)
+    );
+    Ok(())
+}
+
+/// `collect_soa` matches `with_positions`, column by column.
+#[test]
+fn randstrobes_collect_soa() -> Result<()> {
+    for n in [2, 3] {
+        let rows: Vec<(u64, [usize; 3])> =
+            RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.with_positions().collect();
+        let (hashes, m1, m2, m3) = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.collect_soa()?;
+        assert_eq!(hashes, rows.iter().map(|r| r.0).collect::<Vec<_>>());
+        for (i, col) in [m1, m2, m3].iter().enumerate() {
+            assert_eq!(*col, rows.iter().map(|r| r.1[i] as u32).collect::<Vec<_>>());
+        }
+    }
+    Ok(())
+}
+
+/// Ambiguity policies: reject, rewrite to `A`, or keep ambiguous k-mers out of every seed.
+#[test]
+fn randstrobes_ambiguity_policy() -> Result<()> {
+    let seq = b"ACGATCTGGTACNNAGGATTACACGATCGGNTCCAGTTGACCA";
+    let (k, w_min, w_max) = (L, W_MIN, W_MAX);
+    let build = |policy| {
+        RandStrobes::with_ambiguity_policy(seq, 3, k, w_min, w_max, &NtHash64, policy)
+    };
+
+    assert_eq!(build(AmbiguityPolicy::Error).err(), Some(StrobeError::AmbiguousBase(12)));
+
+    let as_a: Vec<u64> = build(AmbiguityPolicy::TreatAsA)?.collect();
+    let rewritten = String::from_utf8_lossy(seq).replace('N', "A");
+    let expected: Vec<u64> = RandStrobes::new(rewritten.as_bytes(), 3, k, w_min, w_max)?.collect();
+    assert_eq!(as_a, expected);
+
+    let mut rs = build(AmbiguityPolicy::SkipAffectedSeeds)?;
+    let mut seeds = 0;
+    while rs.next().is_some() {
+        assert!(rs.indexes().iter().all(|&p| !seq[p..p + k].contains(&b'N')));
+        seeds += 1;
+    }
+    assert!(seeds > 0 && seeds < expected.len());
+    Ok(())
+}
+
+/// Low-quality bases are skipped exactly like ambiguous bases, leaving the input untouched.
+#[test]
+fn randstrobes_quality_mask() -> Result<()> {
+    let seq = b"ACGATCTGGTACNNAGGATTACACGATCGGNTCCAGTTGACCA";
+    let clean = String::from_utf8_lossy(seq).replace('N', "A");
+    let qual: Vec<u8> = seq.iter().map(|&b| if b == b'N' { b'#' } else { b'I' }).collect();
+    let policy = AmbiguityPolicy::SkipAffectedSeeds;
+
+    let (clean, k) = (clean.as_bytes(), L);
+    let mut masked = RandStrobes::with_quality_mask(clean, &qual, 20, 3, k, W_MIN, W_MAX)?;
+    let mut skipped =
+        RandStrobes::with_ambiguity_policy(seq, 3, k, W_MIN, W_MAX, &NtHash64, policy)?;
+    while let Some(h) = masked.next() {
+        assert_eq!(skipped.next(), Some(h));
+        assert_eq!(skipped.indexes(), masked.indexes());
+    }
+    assert_eq!(skipped.next(), None);
+
+    let all_pass = RandStrobes::with_quality_mask(clean, &qual, 2, 3, k, W_MIN, W_MAX)?;
+    assert_eq!(all_pass.count(), RandStrobes::new(clean, 3, k, W_MIN, W_MAX)?.count());
+    let short = RandStrobes::with_quality_mask(seq, &qual[1..], 20, 3, k, W_MIN, W_MAX);
+    assert_eq!(short.err(), Some(StrobeError::InvalidSequence));
+    Ok(())
+}
+
+/// Soft-masking drops seeds with lowercase strobes and leaves the others untouched,
+/// on both strands.
+#[test]
+fn randstrobes_soft_mask() -> Result<()> {
+    let seq = b"ACGATCTGGTacctaggattACACGATCGGATCCAGTTGACCA";
+    let lower = |p: usize| seq[p..p + L].iter().all(u8::is_ascii_lowercase);
+    for revcomp in [false, true] {
+        let build = || match revcomp {
+            true => RandStrobes::new_revcomp(seq, 3, L, W_MIN, W_MAX),
+            false => RandStrobes::new(seq, 3, L, W_MIN, W_MAX),
+        };
+        let all: Vec<(u64, [usize; 3])> = build()?.with_positions().collect();
+        for (mode, checked) in [(SoftMask::SkipFirstStrobe, 1), (SoftMask::SkipAnyStrobe, 3)] {
+            let mut rs = build()?;
+            rs.set_soft_mask(seq, mode)?;
+            let kept: Vec<_> = rs.with_positions().collect();
+            let expected: Vec<_> =
+                all.iter().copied().filter(|s| !s.1[..checked].iter().any(|&m| lower(m))).collect();
+            assert!(expected.len() < all.len());
+            assert_eq!(kept, expected);
+        }
+    }
+    let mut rs = RandStrobes::new(seq, 3, L, W_MIN, W_MAX)?;
+    let err = rs.set_soft_mask(&seq[1..], SoftMask::SkipAnyStrobe);
+    assert_eq!(err, Err(StrobeError::InvalidSequence));
+    Ok(())
+}
+
+/// Masked intervals drop exactly the seeds with a strobe intersecting them,
+/// in input coordinates on either strand.
+#[test]
+fn randstrobes_masked_intervals() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACCA";
+    let intervals = [(20, 24), (8, 12), (35, 36)];
+    let hits = |p: usize| intervals.iter().any(|&(s, e)| p < e && p + L > s);
+    for revcomp in [false, true] {
+        let build = || match revcomp {
+            true => RandStrobes::new_revcomp(seq, 3, L, W_MIN, W_MAX),
+            false => RandStrobes::new(seq, 3, L, W_MIN, W_MAX),
+        };
+        let all: Vec<(u64, [usize; 3])> = build()?.with_positions().collect();
+        let mut rs = build()?;
+        rs.set_masked_intervals(intervals)?;
+        let kept: Vec<_> = rs.with_positions().collect();
+        let expected: Vec<_> =
+            all.iter().copied().filter(|s| !s.1.iter().any(|&m| hits(m))).collect();
+        assert!(!expected.is_empty() && expected.len() < all.len());
+        assert_eq!(kept, expected);
+    }
+    Ok(())
+}
+
+/// Hasher borrowing its per-base table, so neither `'static` nor `Send`.
+struct TableHasher<'a>(&'a std::rc::Rc<[u64; 256]>);
+
+impl KmerHasher for TableHasher<'_> {
+    fn hash_all(&self, seq: &[u8], k: usize) -> Result<Vec<u64>> {
+        let hash = |w: &[u8]| w.iter().fold(0u64, |h, &b| h.rotate_left(5) ^ self.0[b as usize]);
+        Ok(seq.windows(k).map(hash).collect())
+    }
+}
+
+/// Borrowing hashers and hashers behind `&dyn KmerHasher` seed like their static counterparts.
+#[test]
+fn randstrobes_dyn_and_borrowing_hashers() -> Result<()> {
+    let table = std::rc::Rc::new(std::array::from_fn(|b| (b as u64).wrapping_mul(0x9E37_79B9)));
+    let borrowed = TableHasher(&table);
+    let direct: Vec<u64> = RandStrobes::with_hasher(SEQ, 2, L, W_MIN, W_MAX, &borrowed)?.collect();
+    let hashers: [&dyn KmerHasher; 2] = [&borrowed, &NtHash64];
+    let by_dyn = RandStrobes::with_dyn_hasher(SEQ, 2, L, W_MIN, W_MAX, hashers[0])?;
+    assert_eq!(by_dyn.collect::<Vec<_>>(), direct);
+    for n in [2, 3] {
+        let expected: Vec<u64> = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?.collect();
+        let seeds = RandStrobes::with_dyn_hasher(SEQ, n, L, W_MIN, W_MAX, hashers[1])?;
+        assert_eq!(seeds.collect::<Vec<_>>(), expected);
+    }
+    Ok(())
+}
+
+/// Quality weights steer m2 away from low-quality k-mers without dropping seeds.
+#[test]
+fn randstrobes_quality_weights() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC";
+    let qual: Vec<u8> = (0..seq.len()).map(|i| if i % 7 == 3 { b'#' } else { b'I' }).collect();
+    let low = |p: usize| (p..p + L).any(|b| qual[b] == b'#');
+
+    let plain: Vec<u64> = RandStrobes::new(seq, 2, L, W_MIN, W_MAX)?.collect();
+    let mut neutral = RandStrobes::new(seq, 2, L, W_MIN, W_MAX)?;
+    neutral.set_quality_weights(&qual, 0, u64::MAX)?;
+    assert_eq!(neutral.collect::<Vec<_>>(), plain);
+
+    let mut rs = RandStrobes::new(seq, 2, L, W_MIN, W_MAX)?;
+    rs.set_quality_weights(&qual, 30, u64::MAX / 2)?;
+    let mut count = 0;
+    while rs.next().is_some() {
+        let [m1, m2, _] = rs.indexes();
+        let window = m1 + W_MIN..=(m1 + W_MAX).min(seq.len() - L);
+        assert!(!low(m2) || window.clone().all(low), "m1={m1} m2={m2}");
+        count += 1;
+    }
+    assert_eq!(count, plain.len());
+    assert_eq!(rs.set_quality_weights(&qual[1..], 30, 1), Err(StrobeError::InvalidSequence));
+    Ok(())
+}
+
+/// In a homopolymer every candidate ties; the tie rule alone decides m2.
+#[test]
+fn randstrobes_tie_break() -> Result<()> {
+    let seq = [b'A'; 24];
+    for (tie, offset) in [(TieBreak::Leftmost, W_MIN), (TieBreak::Rightmost, W_MAX)] {
+        let mut rs = RandStrobes::with_hasher(&seq, 2, L, W_MIN, W_MAX, &KmerCodeHasher)?;
+        rs.set_window_shrink(false);
+        rs.set_tie_break(tie);
+        while rs.next().is_some() {
+            let [m1, m2, _] = rs.indexes();
+            assert_eq!(m2, m1 + offset, "{tie:?}");
+        }
+    }
+    Ok(())
+}
+
+/// `w_min = 0` lets the second strobe start anywhere from m1 on; `w_max` must stay positive.
+#[test]
+fn randstrobes_zero_w_min() -> Result<()> {
+    for n in [2, 3] {
+        let mut rs = RandStrobes::new(SEQ, n, L, 0, W_MAX)?;
+        let mut count = 0;
+        while rs.next().is_some() {
+            let [m1, m2, m3] = rs.indexes();
+            assert!((m1..=m1 + W_MAX).contains(&m2));
+            assert!(n == 2 || (m1 + W_MAX..=m1 + 2 * W_MAX).contains(&m3));
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+    assert!(matches!(RandStrobes::new(SEQ, 2, L, 0, 0), Err(StrobeError::InvalidWindowOffsets)));
+    Ok(())
+}
+
+/// Order-3 windows shrink or stop at the sequence end for every length, so
+/// anchors run up to the last one whose windows (or their starts) fit.
+#[test]
+fn randstrobes_order3_sequence_end() -> Result<()> {
+    let base = b"ACGATCTGGTACCTAGGATTACACGATCGGATCC";
+    for (w_min, w_max) in [(0, 4), (2, 4), (3, 5)] {
+        for len in 0..=base.len() {
+            for (shrink, anchor) in [
+                (true, WindowAnchor::FromFirstStrobe),
+                (false, WindowAnchor::FromFirstStrobe),
+                (true, WindowAnchor::FromPreviousStrobe),
+            ] {
+                let Ok(mut rs) = RandStrobes::new(&base[..len], 3, L, w_min, w_max) else {
+                    continue;
+                };
+                rs.set_window_shrink(shrink);
+                rs.set_window_anchor(anchor);
+                let seeds: Vec<Strobemer> = rs.iter_seeds().collect();
+                let end_hash = len - L;
+                let reach = if shrink { w_max + w_min } else { 2 * w_max };
+                // m1 also stops where three whole k-mers no longer fit
+                let anchors = (end_hash + 1).saturating_sub(reach).min(len + 1 - 3 * L);
+                assert_eq!(seeds.len(), anchors, "len={len} w={w_min}..{w_max} {shrink}");
+                for (i, s) in seeds.iter().enumerate() {
+                    let [m1, m2, m3] = s.positions;
+                    assert_eq!(m1, i);
+                    assert!((m1 + w_min..=m1 + w_max).contains(&m2));
+                    assert!(m2 <= m3 && m3 <= end_hash);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A span cap bounds every seed, keeps all anchors and is a no-op when loose.
+#[test]
+fn randstrobes_max_span() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC";
+    for (n, canonical) in [(2, false), (3, false), (2, true), (3, true)] {
+        let build = || match canonical {
+            true => RandStrobes::new_canonical(seq, n, L, 2, 8),
+            false => RandStrobes::new(seq, n, L, 2, 8),
+        };
+        let plain: Vec<Strobemer> = build()?.iter_seeds().collect();
+        let mut loose = build()?;
+        loose.set_max_span(1000)?;
+        assert!(loose.iter_seeds().eq(plain.iter().copied()));
+
+        let cap = L + (n as usize - 2) * 8 + 4;
+        let mut capped = build()?;
+        capped.set_max_span(cap)?;
+        let capped: Vec<Strobemer> = capped.iter_seeds().collect();
+        assert_eq!(capped.len(), plain.len());
+        assert!(capped.iter().all(|s| s.span <= cap), "n={n} canonical={canonical}");
+        assert!(plain.iter().any(|s| s.span > cap));
+        assert_eq!(build()?.set_max_span(cap - 3), Err(StrobeError::MaxSpanTooSmall));
+    }
+    Ok(())
+}
+
+/// A span floor lifts every seed's span, also for mirrored canonical windows.
+#[test]
+fn randstrobes_min_span() -> Result<()> {
+    let seq = b"ACGATCTGGTACCTAGGATTACACGATCGGATCCAGTTGACC";
+    for (n, canonical) in [(2, false), (3, false), (2, true), (3, true)] {
+        let build = || match canonical {
+            true => RandStrobes::new_canonical(seq, n, L, 2, 8),
+            false => RandStrobes::new(seq, n, L, 2, 8),
+        };
+        let plain: Vec<Strobemer> = build()?.iter_seeds().collect();
+        let floor = L + (n as usize - 2) * 8 + 6;
+        let mut raised = build()?;
+        raised.set_min_span(floor)?;
+        let raised: Vec<Strobemer> = raised.iter_seeds().collect();
+        assert!(!raised.is_empty());
+        assert!(raised.iter().all(|s| s.span >= floor), "n={n} canonical={canonical}");
+        assert!(plain.iter().any(|s| s.span < floor));
+
+        let longest = L + (n as usize - 1) * 8;
+        assert_eq!(build()?.set_min_span(longest + 1), Err(StrobeError::MinSpanTooLarge));
+        let mut capped = build()?;
+        capped.set_max_span(floor + 2)?;
+        assert_eq!(capped.set_min_span(floor + 3), Err(StrobeError::MinSpanTooLarge));
+        capped.set_min_span(floor)?;
+        assert_eq!(capped.set_max_span(floor - 1), Err(StrobeError::MaxSpanTooSmall));
+    }
+    Ok(())
+}
+
+/// Anchoring m3's window at m2 keeps the anchors and places m3 within the
+/// offsets from m2, also for mirrored canonical windows.
+#[test]
+fn randstrobes_window_anchor() -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(11);
+    let seq: Vec<u8> = (0..300).map(|_| b"ACGT"[rng.random_range(0..4)]).collect();
+    for canonical in [false, true] {
+        let build = || match canonical {
+            true => RandStrobes::new_canonical(&seq, 3, L, 2, 8),
+            false => RandStrobes::new(&seq, 3, L, 2, 8),
+        };
+        let first: Vec<Strobemer> = build()?.iter_seeds().collect();
+        let mut explicit = build()?;
+        explicit.set_window_anchor(WindowAnchor::FromFirstStrobe);
+        assert!(explicit.iter_seeds().eq(first.iter().copied()));
+
+        let mut prev = build()?;
+        prev.set_window_anchor(WindowAnchor::FromPreviousStrobe);
+        let prev: Vec<Strobemer> = prev.iter_seeds().collect();
+        assert_eq!(prev.len(), first.len());
+        // Canonical seeds may switch strand, and with it m2, when m3 moves
+        let kept = if canonical { 1 } else { 2 };
+        assert!(prev.iter().zip(&first).all(|(p, f)| p.positions[..kept] == f.positions[..kept]));
+        assert!(prev.iter().all(|s| (2..=8).contains(&s.positions[1].abs_diff(s.positions[2]))));
+        assert!(first.iter().any(|s| s.positions[1].abs_diff(s.positions[2]) > 8));
+    }
+    Ok(())
+}
+
+/// Streaming seeds over a ring of hashes equal those over the full hash vector,
+/// under either combination scheme.
+#[test]
+fn randstrobes_streaming() -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(13);
+    for i in 0..60 {
+        let len = rng.random_range(1..400);
+        let seq: Vec<u8> = (0..len).map(|_| b"ACGT"[rng.random_range(0..4)]).collect();
+        let (n, k) = (rng.random_range(2..=3), rng.random_range(1..=12));
+        let w_min = rng.random_range(0..=10);
+        let w_max = rng.random_range(w_min.max(1)..=w_min + 30);
+        let scheme = if i % 2 == 0 { CombineScheme::Mix } else { CombineScheme::Legacy };
+        let whole = RandStrobes::with_hasher(&seq, n, k, w_min, w_max, &KmerCodeHasher);
+        let ring = RandStrobes::streaming(&seq, n, k, w_min, w_max, &KmerCodeHasher);
+        match (whole, ring) {
+            (Ok(mut whole), Ok(mut ring)) => {
+                whole.set_combine_scheme(scheme);
+                ring.set_combine_scheme(scheme);
+                let ring: Vec<Strobemer> = ring.collect::<Result<_>>()?;
+                assert_eq!(ring, whole.iter_seeds().collect::<Vec<_>>(), "{n} {k} {w_min} {w_max}");
+            }
+            (whole, ring) => assert_eq!(whole.err(), ring.err()),
+        }
+    }
+    Ok(())
+}
+
+/// Bulk output yields the seeds, and leaves the state, of per-item iteration.
+#[test]
+fn randstrobes_fill_slice() -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(17);
+    let seq: Vec<u8> = (0..300).map(|_| b"ACGT"[rng.random_range(0..4)]).collect();
+    for setup in 0..5 {
+        let build = || -> Result<RandStrobes> {
+            let mut rs = match setup {
+                3 => RandStrobes::new_canonical(&seq, 3, 5, 2, 9)?,
+                _ => RandStrobes::new(&seq, 3, 5, 2, 9)?,
+            };
+            match setup {
+                1 => rs.set_stride(3)?,
+                2 => rs.set_density(0.5)?,
+                4 => drop(rs.next_back()),
+                _ => {}
+            }
+            Ok(rs)
+        };
+        let expected: Vec<u64> = build()?.collect();
+        assert_eq!(build()?.into_vec(), expected);
+        for chunk in [1, 7, 1000] {
+            let (mut bulk, mut single) = (build()?, build()?);
+            let mut buf = vec![0; chunk];
+            let mut seeds = Vec::new();
+            loop {
+                let n = bulk.fill_slice(&mut buf);
+                seeds.extend_from_slice(&buf[..n]);
+                single.by_ref().take(n).for_each(drop);
+                if n < chunk {
+                    assert_eq!(single.next(), None);
+                }
+                assert_eq!(bulk.indexes(), single.indexes());
+                if n < chunk {
+                    break;
+                }
+            }
+            assert_eq!(seeds, expected, "setup {setup} chunk {chunk}");
+        }
+    }
+    Ok(())
+}
+
+/// Caller-supplied hashes seed like the hasher that produced them.
+#[test]
+fn randstrobes_from_hashes() -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(19);
+    for _ in 0..40 {
+        let len = rng.random_range(1..200);
+        let seq: Vec<u8> = (0..len).map(|_| b"ACGT"[rng.random_range(0..4)]).collect();
+        let (n, k) = (rng.random_range(2..=3), rng.random_range(1..=9));
+        let w_min = rng.random_range(0..=6);
+        let w_max = rng.random_range(w_min.max(1)..=w_min + 20);
+        let hashed = RandStrobes::with_hasher(&seq, n, k, w_min, w_max, &KmerCodeHasher);
+        let hashes = if len >= k { KmerCodeHasher.hash_all(&seq, k)? } else { Vec::new() };
+        match (hashed, RandStrobes::from_hashes(&hashes, n, k, w_min, w_max)) {
+            (Ok(hashed), Ok(supplied)) => assert!(hashed.iter_seeds().eq(supplied.iter_seeds())),
+            (Err(StrobeError::SequenceTooShort), Err(e)) => {
+                assert!(matches!(e, StrobeError::SequenceTooShort | StrobeError::InvalidSequence))
+            }
+            (hashed, supplied) => assert_eq!(hashed.err(), supplied.err()),
+        }
+    }
+    Ok(())
+}
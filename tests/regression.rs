@@ -1,9 +1,10 @@
 //! Regression tests that verify the Rust implementation produces the same
-//! strobemer hash sequences as a previous snapshot (from the Go reference).
+//! strobemer hash sequences as a previous snapshot. The `*_LEGACY` values come
+//! from the Go reference; the defaults changed with `CombineScheme::Mix`.
 //! If the strobemer algorithm is intentionally changed, update these expected
 //! values and document a breaking change in the CHANGELOG.
 
-use strobemers_rs::{MinStrobes, RandStrobes, Result};
+use strobemers_rs::{Alphabet, AlphabetHasher, CombineScheme, MinStrobes, RandStrobes, Result};
 
 // ==== Fixed parameters (identical to the original Go tests) ====
 const SEQ: &[u8] = b"ACGATCTGGTACCTAG"; // Test sequence
@@ -11,7 +12,7 @@ const L: usize = 3; // k-mer (strobe) length
 const W_MIN: usize = 3; // Minimum window offset
 const W_MAX: usize = 5; // Maximum window offset
 
-// ==== Snapshot of expected hash outputs (as of 2025-06-03 commit) ====
+// ==== Snapshot of expected hash outputs with the default `CombineScheme::Mix` ====
 //
 // * MinStrobes order-2 … 11 values
 // * MinStrobes order-3 … 6 values
@@ -19,6 +20,59 @@ const W_MAX: usize = 5; // Maximum window offset
 // * RandStrobes order-3 … 6 values
 //
 const MIN_O2: [u64; 11] = [
+    5034711282162315152,
+    15682012345437303201,
+    408657639958074095,
+    6379396952821927647,
+    243012056038445379,
+    7219222440434913748,
+    8407644649956415235,
+    12736780151204431051,
+    9446427017983908693,
+    12183490699276753011,
+    11474060836110914802,
+];
+
+const MIN_O3: [u64; 6] = [
+    17242662893449726642,
+    6601757538844093807,
+    15448632290553449174,
+    9931110164068205906,
+    3701916203997596898,
+    1017630773415816375,
+];
+
+const RAND_O2: [u64; 11] = [
+    6611472613047971768,
+    9457223817187313966,
+    6379396952821927647,
+    17058793032765871449,
+    243012056038445379,
+    9716160258689208543,
+    15541387209598435103,
+    8246904193545077896,
+    9446427017983908693,
+    12183490699276753011,
+    11474060836110914802,
+];
+
+const RAND_O3: [u64; 6] = [
+    15218581588448553222,
+    9565482896613646657,
+    9931110164068205906,
+    17756113519521576590,
+    3701916203997596898,
+    8436223802644090450,
+];
+
+// ==== Snapshot with `CombineScheme::Legacy` (as of 2025-06-03 commit) ====
+//
+// * MinStrobes order-2 … 11 values
+// * MinStrobes order-3 … 6 values
+// * RandStrobes order-2 … 11 values
+// * RandStrobes order-3 … 6 values
+//
+const MIN_O2_LEGACY: [u64; 11] = [
     5508583604130516576,
     7820137869046132365,
     5541303490076687811,
@@ -32,7 +86,7 @@ const MIN_O2: [u64; 11] = [
     8953117104403771765,
 ];
 
-const MIN_O3: [u64; 6] = [
+const MIN_O3_LEGACY: [u64; 6] = [
     5838247918869859075,
     5824753939158295439,
     4305531019845332403,
@@ -41,7 +95,7 @@ const MIN_O3: [u64; 6] = [
     6896419184433288632,
 ];
 
-const RAND_O2: [u64; 11] = [
+const RAND_O2_LEGACY: [u64; 11] = [
     6508932193244882681,
     8820486458160498470,
     5796921065369559009,
@@ -55,7 +109,7 @@ const RAND_O2: [u64; 11] = [
     8953117104403771765,
 ];
 
-const RAND_O3: [u64; 6] = [
+const RAND_O3_LEGACY: [u64; 6] = [
     7772345821922645402,
     9313381998533055928,
     4497244201314985802,
@@ -64,6 +118,68 @@ const RAND_O3: [u64; 6] = [
     8376214760954553316,
 ];
 
+// ==== Order-3 MinStrobes selection near the sequence end ====
+//
+// Hashed with `AlphabetHasher` (computed within the crate) and combined with
+// `CombineScheme::Legacy` over a longer sequence whose last shrunk third
+// windows pick a different m3 under the legacy `(h2 + hash) & prime` rule and
+// under `set_consistent_selection`.
+const END_SEQ: &[u8] = b"ACGATCTGGTACCTAGGATTACACGATCGGATCC";
+
+const MIN_O3_END_LEGACY: [u64; 24] = [
+    2353391590952911853,
+    4422771417641551373,
+    4497640917828612655,
+    2123711460379604838,
+    3711313268010496454,
+    2345049640017414104,
+    3271736242243070673,
+    4112053540768575420,
+    3203506491579679850,
+    655041144782377225,
+    2594933868213591849,
+    1455725036766207784,
+    7747507125160814751,
+    2058087381991272596,
+    3012721023913971547,
+    5094824580521301479,
+    3248086778896852010,
+    2296332022614775331,
+    3524997384707081159,
+    2492711647195294999,
+    4607289172378501951,
+    2851303955523209309,
+    2583014199962138767,
+    3646597589809149973,
+];
+
+const MIN_O3_END_CONSISTENT: [u64; 24] = [
+    2353391590952911853,
+    4422771417641551373,
+    4497640917828612655,
+    2123711460379604838,
+    3711313268010496454,
+    2345049640017414104,
+    3271736242243070673,
+    4112053540768575420,
+    3203506491579679850,
+    655041144782377225,
+    2594933868213591849,
+    1455725036766207784,
+    7747507125160814751,
+    2058087381991272596,
+    3012721023913971547,
+    5094824580521301479,
+    3248086778896852010,
+    2296332022614775331,
+    3524997384707081159,
+    2492711647195294999,
+    4607289172378501951,
+    2851303955523209309,
+    2171707414304670012,
+    3646597589809149973,
+];
+
 // ---------------------------------------------------------------------
 //                         REGRESSION  TESTS
 // ---------------------------------------------------------------------
@@ -105,3 +221,38 @@ fn regression_randstrobes_order3() -> Result<()> {
     assert_eq!(v, RAND_O3);
     Ok(())
 }
+
+/// Verifies that `CombineScheme::Legacy` still reproduces the Go reference.
+#[test]
+fn regression_legacy_combine_scheme() -> Result<()> {
+    let expected: [(u8, &[u64], &[u64]); 2] =
+        [(2, &MIN_O2_LEGACY, &RAND_O2_LEGACY), (3, &MIN_O3_LEGACY, &RAND_O3_LEGACY)];
+    for (n, min, rand) in expected {
+        let mut ms = MinStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        ms.set_combine_scheme(CombineScheme::Legacy);
+        assert_eq!(ms.collect::<Vec<_>>(), min);
+        let mut rs = RandStrobes::new(SEQ, n, L, W_MIN, W_MAX)?;
+        rs.set_combine_scheme(CombineScheme::Legacy);
+        assert_eq!(rs.collect::<Vec<_>>(), rand);
+    }
+    Ok(())
+}
+
+/// Verifies both m3 selection rules of order-3 MinStrobes in shrunk end windows.
+#[test]
+fn regression_minstrobes_order3_end_windows() -> Result<()> {
+    let hasher = AlphabetHasher::new(Alphabet::dna());
+    for (consistent, expected) in [(false, MIN_O3_END_LEGACY), (true, MIN_O3_END_CONSISTENT)] {
+        let mut ms = MinStrobes::with_hasher(END_SEQ, 3, L, W_MIN, W_MAX, &hasher)?;
+        ms.set_combine_scheme(CombineScheme::Legacy);
+        ms.set_consistent_selection(consistent);
+        assert_eq!(ms.collect::<Vec<_>>(), expected);
+    }
+    Ok(())
+}
+
+/// Verifies that the runtime self-check passes on this target.
+#[test]
+fn regression_self_check() -> Result<()> {
+    strobemers_rs::self_check()
+}